@@ -0,0 +1,151 @@
+use crate::messages::Message;
+use crate::statement::{CommentTarget, Constraint, Statement, TableColumn};
+
+/// The schema knowledge that the analysis passes need: every table that was created by a `CREATE TABLE` statement (and not dropped again by a `DROP TABLE`), together with its column definitions, and every view that was created by a `CREATE VIEW` statement, together with its defining query (the latest one, if the view was replaced with `CREATE OR REPLACE VIEW`). Tables and views share one namespace, and names are looked up case-insensitively, the same way an SQL database treats unquoted identifiers. An object is known by its whole name as it was written, like `public.users`, so it is not found under a name with a different schema or without one. Objects are kept in the order they were created.
+///
+/// `COMMENT ON` statements document the objects of the catalog: the comments of tables and views are kept by their name, while the comment of a column becomes the `Comment` constraint of its definition, the same as if it was written in the `CREATE TABLE` statement.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Catalog {
+    tables: Vec<(String, Vec<TableColumn>)>,
+    views: Vec<(String, Statement)>,
+    comments: Vec<(String, String)>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Builds a catalog out of a whole script. Statements that do not define
+    // schema objects (like SELECT) are ignored.
+    pub fn from_statements(statements: &[Statement]) -> Result<Self, Message> {
+        let mut catalog = Self::new();
+        for statement in statements {
+            catalog.add_statement(statement)?;
+        }
+        Ok(catalog)
+    }
+
+    // Registers the schema object defined by the statement, if any
+    pub fn add_statement(&mut self, statement: &Statement) -> Result<(), Message> {
+        match statement {
+            // With IF NOT EXISTS, an object that already exists (even of the
+            // other kind) is kept as it is
+            Statement::CreateTable { table_name, if_not_exists: true, .. }
+            | Statement::CreateView { name: table_name, if_not_exists: true, .. }
+                if self.check_name_is_free(&table_name.to_string()).is_err() => Ok(()),
+            Statement::CreateTable { table_name, column_list, .. } => {
+                let table_name = table_name.to_string();
+                self.check_name_is_free(&table_name)?;
+                self.tables.push((table_name, column_list.clone()));
+                Ok(())
+            },
+            // OR REPLACE changes the query of an existing view, but can not
+            // replace a table
+            Statement::CreateView { name, or_replace: true, query, .. } if self.view(&name.to_string()).is_some() => {
+                let name = name.to_string();
+                if let Some((_, definition)) = self.views.iter_mut().find(|(view, _)| view.eq_ignore_ascii_case(&name)) {
+                    *definition = *query.clone();
+                }
+                Ok(())
+            },
+            Statement::CreateView { name, query, .. } => {
+                let name = name.to_string();
+                self.check_name_is_free(&name)?;
+                self.views.push((name, *query.clone()));
+                Ok(())
+            },
+            Statement::Comment { target, text } => self.set_comment(target, text.clone()),
+            Statement::DropTable { table_name, if_exists } => {
+                let table_name = table_name.to_string();
+                let position = self.tables.iter().position(|(name, _)| name.eq_ignore_ascii_case(&table_name));
+                match position {
+                    Some(position) => {
+                        self.tables.remove(position);
+                        self.comments.retain(|(object, _)| !object.eq_ignore_ascii_case(&table_name));
+                        Ok(())
+                    },
+                    None if *if_exists => Ok(()),
+                    None => Err(Message::UnknownTable { name: table_name }),
+                }
+            },
+            Statement::Select { .. } | Statement::Insert { .. } | Statement::Custom { .. } => Ok(()),
+        }
+    }
+
+    // Replaces the comment of an object, or removes it when the text is `None`
+    fn set_comment(&mut self, target: &CommentTarget, text: Option<String>) -> Result<(), Message> {
+        match target {
+            CommentTarget::Table(name) | CommentTarget::View(name) => {
+                let name = name.to_string();
+                if matches!(target, CommentTarget::Table(_)) && self.table(&name).is_none() {
+                    return Err(Message::UnknownTable { name });
+                }
+                if matches!(target, CommentTarget::View(_)) && self.view(&name).is_none() {
+                    return Err(Message::UnknownView { name });
+                }
+                self.comments.retain(|(object, _)| !object.eq_ignore_ascii_case(&name));
+                if let Some(text) = text {
+                    self.comments.push((name, text));
+                }
+            },
+            CommentTarget::Column { table, column } => {
+                let table = table.to_string();
+                let (_, columns) = self
+                    .tables
+                    .iter_mut()
+                    .find(|(table_name, _)| table_name.eq_ignore_ascii_case(&table))
+                    .ok_or_else(|| Message::UnknownTable { name: table.clone() })?;
+                let definition = columns
+                    .iter_mut()
+                    .find(|definition| definition.column_name.eq_ignore_ascii_case(column))
+                    .ok_or_else(|| Message::UnknownColumn { name: format!("{}.{}", table, column) })?;
+                definition.constraints.retain(|constraint| !matches!(constraint, Constraint::Comment(_)));
+                if let Some(text) = text {
+                    definition.constraints.push(Constraint::Comment(text));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn check_name_is_free(&self, name: &str) -> Result<(), Message> {
+        if self.table(name).is_some() {
+            Err(Message::TableAlreadyExists { name: name.to_string() })
+        } else if self.view(name).is_some() {
+            Err(Message::ViewAlreadyExists { name: name.to_string() })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn table(&self, name: &str) -> Option<&[TableColumn]> {
+        self.tables
+            .iter()
+            .find(|(table_name, _)| table_name.eq_ignore_ascii_case(name))
+            .map(|(_, columns)| columns.as_slice())
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = (&str, &[TableColumn])> {
+        self.tables.iter().map(|(name, columns)| (name.as_str(), columns.as_slice()))
+    }
+
+    pub fn view(&self, name: &str) -> Option<&Statement> {
+        self.views
+            .iter()
+            .find(|(view_name, _)| view_name.eq_ignore_ascii_case(name))
+            .map(|(_, query)| query)
+    }
+
+    // The comment of a table or a view
+    pub fn comment(&self, name: &str) -> Option<&str> {
+        self.comments
+            .iter()
+            .find(|(object, _)| object.eq_ignore_ascii_case(name))
+            .map(|(_, text)| text.as_str())
+    }
+
+    pub fn views(&self) -> impl Iterator<Item = (&str, &Statement)> {
+        self.views.iter().map(|(name, query)| (name.as_str(), query))
+    }
+}
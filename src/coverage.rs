@@ -0,0 +1,243 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Every grammar rule and branch the parser is instrumented with. The names follow a `rule.branch` scheme, so that the report can be grouped by the grammar rule it belongs to. When a new branch is added to the parser, it should be listed here as well, otherwise it will never show up as unexercised.
+pub const RULES: &[&str] = &[
+    "statement.select",
+    "statement.create_table",
+    "statement.create_view",
+    "statement.comment",
+    "statement.insert",
+    "statement.drop_table",
+    "statement.custom",
+    "create.if_not_exists",
+    "create.or_replace",
+    "create.temporary",
+    "drop.if_exists",
+    "name.qualified",
+    "insert.columns",
+    "insert.values",
+    "insert.multiple_rows",
+    "insert.query",
+    "insert.returning",
+    "select.with",
+    "select.wildcard",
+    "select.columns",
+    "select.without_from",
+    "from.table",
+    "from.alias",
+    "from.derived",
+    "from.parenthesized_join",
+    "from.comma",
+    "join.inner",
+    "join.left",
+    "join.right",
+    "join.full",
+    "select.where",
+    "select.order_by",
+    "select.top",
+    "select.limit",
+    "lock.for_update",
+    "lock.for_share",
+    "lock.nowait",
+    "lock.skip_locked",
+    "create_table.columns",
+    "create_table.named_constraint",
+    "create_table.primary_key",
+    "create_table.unique",
+    "create_table.check",
+    "column.primary_key",
+    "column.not_null",
+    "column.null",
+    "column.check",
+    "column.comment",
+    "column.identity",
+    "column.default",
+    "column.auto_increment",
+    "table_options.with",
+    "table_options.list",
+    "partition.range",
+    "partition.list",
+    "partition.hash",
+    "partition.key",
+    "type.int",
+    "type.smallint",
+    "type.bigint",
+    "type.tinyint",
+    "type.bool",
+    "type.varchar",
+    "type.varchar_without_length",
+    "type.decimal",
+    "type.float",
+    "type.real",
+    "type.double",
+    "type.date",
+    "type.time",
+    "type.timestamp",
+    "type.timestamp_with_time_zone",
+    "type.datetime",
+    "type.blob",
+    "type.uuid",
+    "type.custom",
+    "type.serial",
+    "type.bigserial",
+    "mysql.display_width",
+    "mysql.unsigned",
+    "mysql.unsigned_ignored",
+    "mysql.primary_key",
+    "mysql.composite_primary_key",
+    "mysql.index",
+    "prefix.number",
+    "prefix.float",
+    "prefix.bit_string",
+    "prefix.placeholder",
+    "prefix.numbered_placeholder",
+    "prefix.named_placeholder",
+    "prefix.temporal",
+    "prefix.extract",
+    "prefix.string",
+    "prefix.prefixed_string",
+    "prefix.identifier",
+    "prefix.compound_identifier",
+    "prefix.qualified_wildcard",
+    "prefix.function",
+    "prefix.join_keyword_function",
+    "function.star",
+    "function.distinct",
+    "function.argument_count",
+    "function.over",
+    "comparison.quantified",
+    "over.partition_by",
+    "over.order_by",
+    "prefix.true",
+    "prefix.false",
+    "prefix.null",
+    "prefix.not",
+    "prefix.exists",
+    "prefix.not_exists",
+    "prefix.cast",
+    "prefix.plus",
+    "prefix.minus",
+    "prefix.smallest_number",
+    "prefix.bitwise_not",
+    "prefix.parentheses",
+    "prefix.tuple",
+    "prefix.subquery",
+    "infix.plus",
+    "infix.minus",
+    "infix.multiply",
+    "infix.divide",
+    "infix.modulo",
+    "infix.power",
+    "infix.bitwise_or",
+    "infix.bitwise_xor",
+    "infix.bitwise_and",
+    "infix.shift_left",
+    "infix.shift_right",
+    "infix.equal",
+    "infix.not_equal",
+    "infix.greater_than",
+    "infix.greater_than_or_equal",
+    "infix.less_than",
+    "infix.less_than_or_equal",
+    "infix.in",
+    "infix.not_in",
+    "infix.in_list",
+    "infix.like",
+    "postfix.is_null",
+    "postfix.is_not_null",
+    "infix.is_distinct_from",
+    "infix.is_not_distinct_from",
+    "postfix.cast",
+    "infix.not_like",
+    "infix.similar_to",
+    "infix.not_similar_to",
+    "infix.regexp",
+    "infix.not_regexp",
+    "infix.regex_match",
+    "infix.not_regex_match",
+    "like.escape",
+    "infix.and",
+    "infix.or",
+    "postfix.asc",
+    "postfix.desc",
+];
+
+thread_local! {
+    static HITS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+}
+
+// Called by the parser (through the `cover!` macro) every time a rule is entered
+pub fn record(rule: &'static str) {
+    HITS.with(|hits| *hits.borrow_mut().entry(rule).or_insert(0) += 1);
+}
+
+// Forgets everything recorded so far on the current thread
+pub fn reset() {
+    HITS.with(|hits| hits.borrow_mut().clear());
+}
+
+/// A snapshot of how many times each grammar rule was exercised. Rules from `RULES` that were never hit are kept in the report with a count of zero, which is exactly what makes untested paths visible.
+#[derive(Debug, PartialEq)]
+pub struct CoverageReport {
+    pub hits: Vec<(&'static str, usize)>,
+}
+
+impl CoverageReport {
+    pub fn exercised(&self) -> Vec<&'static str> {
+        self.hits.iter().filter(|(_, count)| *count > 0).map(|(rule, _)| *rule).collect()
+    }
+
+    pub fn unexercised(&self) -> Vec<&'static str> {
+        self.hits.iter().filter(|(_, count)| *count == 0).map(|(rule, _)| *rule).collect()
+    }
+
+    pub fn hits_of(&self, rule: &str) -> usize {
+        self.hits.iter().find(|(name, _)| *name == rule).map_or(0, |(_, count)| *count)
+    }
+}
+
+// Builds a report out of everything recorded on the current thread
+pub fn snapshot() -> CoverageReport {
+    HITS.with(|hits| {
+        let hits = hits.borrow();
+        let mut report: Vec<(&'static str, usize)> = RULES
+            .iter()
+            .map(|rule| (*rule, hits.get(rule).copied().unwrap_or(0)))
+            .collect();
+        // Rules that were recorded but are missing from RULES still get reported
+        let mut unlisted: Vec<(&'static str, usize)> = hits
+            .iter()
+            .filter(|(rule, _)| !RULES.contains(rule))
+            .map(|(rule, count)| (*rule, *count))
+            .collect();
+        unlisted.sort();
+        report.extend(unlisted);
+        CoverageReport { hits: report }
+    })
+}
+
+// Parses every query of the corpus (errors are ignored, failing queries still
+// exercise rules) and reports the coverage of the whole corpus
+pub fn measure<'a>(corpus: impl IntoIterator<Item = &'a str>) -> CoverageReport {
+    reset();
+    for query in corpus {
+        let _ = crate::parser::build_statement(query);
+    }
+    snapshot()
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.hits.iter().map(|(rule, _)| rule.len()).max().unwrap_or(0);
+        for (rule, count) in &self.hits {
+            let marker = if *count == 0 { "!!" } else { "  " };
+            writeln!(f, "{} {:<width$} {}", marker, rule, count, width = width)?;
+        }
+        let exercised = self.exercised().len();
+        let total = self.hits.len();
+        let percentage = if total == 0 { 100.0 } else { exercised as f64 * 100.0 / total as f64 };
+        write!(f, "{}/{} rules exercised ({:.1}%)", exercised, total, percentage)
+    }
+}
@@ -0,0 +1,46 @@
+// Translators of statements into the SQL of real database systems, and the
+// dialects the parser understands besides its own
+pub mod sqlite;
+pub mod mysql;
+
+/// The flavor of SQL the parser accepts, set through `ParserOptions`. The parser hands the dialect on to its tokenizer, see `Tokenizer::set_dialect`.
+/// * `Generic` – The SQL described by `Statement`, and nothing else.
+/// * `MsSql` – Also accepts `TOP n` of SQL Server (T-SQL) right after `SELECT`, which limits the number of rows like `LIMIT n` does. Backtick quoted identifiers and `::` casts are rejected, as SQL Server has neither.
+/// * `MySql` – Also tolerates what MySQL dumps are full of: display widths of integers (`INT(11)`), `UNSIGNED` and key definitions among the columns. Whatever has no place in the AST is skipped with a warning (see `Parser::warnings`). Also accepts the regular expression match `name REGEXP '^A'` (and `NOT REGEXP`).
+/// * `Postgres` – Also accepts the regular expression match operators of PostgreSQL, `~` and `!~`, and reads `^` as the power, like `2 ^ 10`, rather than the exclusive or. Backticks do not quote identifiers in PostgreSQL, so they are rejected.
+/// * `Sqlite` – The generic SQL without the `::` casts, which SQLite does not have.
+///
+/// Besides that, MySQL reads strings with backslash escapes and `#` as the start of a line comment, and only MySQL and SQLite quote identifiers with backticks.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum Dialect {
+    #[default]
+    Generic,
+    MsSql,
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl Dialect {
+    // Whether identifiers can be quoted with backticks, like `order`
+    pub fn has_backtick_identifiers(&self) -> bool {
+        matches!(self, Dialect::Generic | Dialect::MySql | Dialect::Sqlite)
+    }
+    
+    // Whether `value::type` is a cast
+    pub fn has_double_colon_casts(&self) -> bool {
+        matches!(self, Dialect::Generic | Dialect::Postgres)
+    }
+}
+
+impl std::fmt::Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dialect::Generic => write!(f, "generic SQL"),
+            Dialect::MsSql => write!(f, "SQL Server"),
+            Dialect::MySql => write!(f, "MySQL"),
+            Dialect::Postgres => write!(f, "PostgreSQL"),
+            Dialect::Sqlite => write!(f, "SQLite"),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::dialects::Dialect;
+use crate::parser::{Parser, ParserOptions};
+use crate::statement::Statement;
+use crate::tokenizer::Tokenizer;
+
+// Parses every statement of a MySQL dump, returning the statements along with
+// the warnings about the parts of them that were skipped. Strings are read
+// with backslash escapes, the same as MySQL does by default.
+pub fn import(input: &str) -> Result<(Vec<Statement>, Vec<String>), String> {
+    let options = ParserOptions { dialect: Dialect::MySql, ..ParserOptions::default() };
+    let mut parser = Parser::with_options(Tokenizer::new(input), options)?;
+    let statements = parser.parse_statements()?;
+    Ok((statements, parser.warnings().to_vec()))
+}
@@ -0,0 +1,357 @@
+use crate::sql::Clauses;
+use crate::statement::{
+    BinaryOperator, CommentTarget, Constraint, DBType, Expression, InsertSource, DateField, Placeholder, Statement, TableColumn, UnaryOperator,
+};
+
+// Renders a statement as SQL that SQLite accepts, so that the schemas parsed by
+// this crate can be materialized in a real database.
+//
+// SQLite does not enforce the declared types of columns, so the types are
+// mapped to the closest storage class and their restrictions are kept as CHECK
+// constraints: `VARCHAR(n)` becomes `TEXT` limited to `n` characters, and
+// `BOOL` becomes an `INTEGER` that is either 0 or 1. Identifiers are always
+// quoted, so names that are keywords in SQLite stay valid. Locking clauses are
+// left out, as SQLite locks the whole database instead of single rows.
+pub fn render(statement: &Statement) -> String {
+    match statement {
+        Statement::Select { .. } => format!("{};", SQLITE.query(statement)),
+        // The table options are meant for other databases, so they are left out
+        Statement::CreateTable { table_name, temporary, if_not_exists, column_list, constraints, .. } => {
+            let mut columns: Vec<String> = column_list.iter().map(column_definition).collect();
+            columns.extend(constraints.iter().map(|constraint| SQLITE.table_constraint(constraint)));
+            format!(
+                "CREATE {}TABLE {}{} (\n    {}\n);",
+                if *temporary { "TEMPORARY " } else { "" },
+                if_not_exists_guard(*if_not_exists),
+                SQLITE.object_name(table_name),
+                columns.join(",\n    ")
+            )
+        },
+        // SQLite has no OR REPLACE for views, so the old view is dropped first
+        Statement::CreateView { name, or_replace, if_not_exists, query: view_query } => {
+            let create = format!("CREATE VIEW {}{} AS {};", if_not_exists_guard(*if_not_exists), SQLITE.object_name(name), SQLITE.query(view_query));
+            if *or_replace {
+                format!("DROP VIEW IF EXISTS {};\n{}", SQLITE.object_name(name), create)
+            } else {
+                create
+            }
+        },
+        // SQLite has no comments on objects, so the comment is only kept as a
+        // comment of the script
+        Statement::Comment { target, text } => {
+            let object = match target {
+                CommentTarget::Table(name) | CommentTarget::View(name) => SQLITE.object_name(name),
+                CommentTarget::Column { table, column } => format!("{}.{}", SQLITE.object_name(table), identifier(column)),
+            };
+            let text = text.as_deref().unwrap_or("").replace('\n', " ");
+            format!("-- {}: {}", object, text)
+        },
+        Statement::DropTable { table_name, if_exists } => {
+            format!("DROP TABLE {}{};", if *if_exists { "IF EXISTS " } else { "" }, SQLITE.object_name(table_name))
+        },
+        Statement::Insert { table_name, columns, source, returning } => {
+            let mut sql = format!("INSERT INTO {}", SQLITE.object_name(table_name));
+            if !columns.is_empty() {
+                sql.push_str(&format!(" ({})", SQLITE.identifiers(columns)));
+            }
+            match source {
+                InsertSource::Values(rows) => {
+                    let rows: Vec<String> = rows.iter().map(|row| format!("({})", SQLITE.expressions(row))).collect();
+                    sql.push_str(&format!(" VALUES {}", rows.join(", ")));
+                },
+                InsertSource::Query(source) => sql.push_str(&format!(" {}", SQLITE.query(source))),
+            }
+            // SQLite returns the inserted rows from version 3.35
+            if !returning.is_empty() {
+                sql.push_str(&format!(" RETURNING {}", SQLITE.returning(returning)));
+            }
+            format!("{};", sql)
+        },
+        // The payload is only known to the handler that made it
+        Statement::Custom { keyword, payload } => format!("-- {}: {}", keyword, payload.replace('\n', " ")),
+    }
+}
+
+fn if_not_exists_guard(if_not_exists: bool) -> &'static str {
+    if if_not_exists { "IF NOT EXISTS " } else { "" }
+}
+
+fn column_definition(column: &TableColumn) -> String {
+    let name = identifier(&column.column_name);
+    let mut definition = format!("{} {}", name, storage_class(&column.column_type));
+    for constraint in &column.constraints {
+        match constraint {
+            // AUTOINCREMENT can only follow the primary key in SQLite, and
+            // without one the column cannot be generated
+            Constraint::PrimaryKey if column.constraints.contains(&Constraint::AutoIncrement) => {
+                definition.push_str(" PRIMARY KEY AUTOINCREMENT")
+            },
+            Constraint::PrimaryKey => definition.push_str(" PRIMARY KEY"),
+            Constraint::AutoIncrement => {},
+            Constraint::NotNull => definition.push_str(" NOT NULL"),
+            Constraint::Null => definition.push_str(" NULL"),
+            Constraint::Check(check) => definition.push_str(&format!(" CHECK ({})", expression(check, 0))),
+            // Only literals may go without parentheses in SQLite
+            Constraint::Default(value) => definition.push_str(&format!(" DEFAULT ({})", expression(value, 0))),
+            // SQLite has no comments on columns
+            Constraint::Comment(_) => {},
+            // An INTEGER PRIMARY KEY is generated by SQLite on its own, and
+            // other columns cannot be
+            Constraint::Identity { .. } => {},
+        }
+    }
+    // The restriction of the declared type goes last, after the constraints
+    // that were written in the statement
+    match column.column_type {
+        // SQLite does not limit the digits of a number
+        DBType::Int { unsigned: true } | DBType::SmallInt { unsigned: true } | DBType::BigInt { unsigned: true } | DBType::TinyInt { unsigned: true } => {
+            definition.push_str(&format!(" CHECK ({} >= 0)", name))
+        },
+        DBType::Int { .. } | DBType::SmallInt { .. } | DBType::BigInt { .. } | DBType::TinyInt { .. } => {},
+        DBType::Serial | DBType::BigSerial => {},
+        DBType::Decimal { .. } | DBType::Float | DBType::Real | DBType::Double => {},
+        // Dates and times are kept as text, which SQLite does not check
+        DBType::Date | DBType::Time | DBType::Timestamp { .. } | DBType::DateTime => {},
+        DBType::Blob | DBType::Custom(_) => {},
+        // The text of a UUID, with its four dashes, is always of the same length
+        DBType::Uuid => definition.push_str(&format!(" CHECK (length({}) = 36)", name)),
+        DBType::Varchar(None) => {},
+        DBType::Varchar(Some(length)) => definition.push_str(&format!(" CHECK (length({}) <= {})", name, length)),
+        DBType::Bool => definition.push_str(&format!(" CHECK ({} IN (0, 1))", name)),
+    }
+    definition
+}
+
+fn storage_class(column_type: &DBType) -> &str {
+    match column_type {
+        DBType::Int { .. } | DBType::SmallInt { .. } | DBType::BigInt { .. } | DBType::TinyInt { .. } | DBType::Bool => "INTEGER",
+        DBType::Serial | DBType::BigSerial => "INTEGER",
+        DBType::Varchar(_) | DBType::Uuid | DBType::Date | DBType::Time | DBType::Timestamp { .. } | DBType::DateTime => "TEXT",
+        DBType::Decimal { .. } => "NUMERIC",
+        DBType::Float | DBType::Real | DBType::Double => "REAL",
+        DBType::Blob => "BLOB",
+        // SQLite takes any name for a type, and derives the storage class from it
+        DBType::Custom(name) => name,
+    }
+}
+
+fn identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// The clauses are written as in the generic dialect, with every name quoted
+// and the expressions in the form SQLite reads
+const SQLITE: Clauses = Clauses { identifier, expression, statement: render, locking: false };
+
+// The precedence of NOT in SQLite, between AND and the comparisons
+const NOT_PRECEDENCE: u8 = 3;
+
+// The precedence of the binary operators in SQLite, higher binds tighter
+fn precedence(operator: &BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::Or => 1,
+        BinaryOperator::And => 2,
+        BinaryOperator::Equal | BinaryOperator::NotEqual |
+        BinaryOperator::IsDistinctFrom | BinaryOperator::IsNotDistinctFrom |
+        BinaryOperator::SimilarTo | BinaryOperator::NotSimilarTo |
+        BinaryOperator::RegexMatch | BinaryOperator::NotRegexMatch => 4,
+        BinaryOperator::GreaterThan | BinaryOperator::GreaterThanOrEqual |
+        BinaryOperator::LessThan | BinaryOperator::LessThanOrEqual => 5,
+        BinaryOperator::BitwiseAnd | BinaryOperator::BitwiseOr | BinaryOperator::BitwiseXor |
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 6,
+        BinaryOperator::Plus | BinaryOperator::Minus => 7,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 8,
+        // Rendered as a function call, which binds tighter than any operator
+        BinaryOperator::Power => 9,
+    }
+}
+
+// Whether the expression reads the same value wherever it is written: a
+// column or a literal. An anonymous placeholder is left out, as every one of
+// them takes the next parameter.
+fn is_repeatable(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::Identifier(_) | Expression::CompoundIdentifier(_) |
+        Expression::Number(_) | Expression::Float(_) | Expression::BitString(_) | Expression::Bool(_) | Expression::Null | Expression::String(_) |
+        Expression::Placeholder(Placeholder::Numbered(_) | Placeholder::Named(_))
+    )
+}
+
+// Renders an expression that appears as an operand of an operator with the
+// given precedence, adding parentheses when it would bind looser than that
+fn expression(expr: &Expression, parent_precedence: u8) -> String {
+    match expr {
+        Expression::BinaryOperation { left_operand, operator, right_operand } => {
+            let own = precedence(operator);
+            let symbol = match operator {
+                // The power is a function in SQLite (from version 3.35)
+                BinaryOperator::Power => {
+                    return format!("pow({}, {})", expression(left_operand, 0), expression(right_operand, 0));
+                },
+                // SQLite has no exclusive or, but the bits set in exactly one
+                // operand are the bits set in either of them minus the bits set
+                // in both. That writes every operand twice, so it is only done
+                // for the operands that give the same value each time they are
+                // read, and SQLite rejects the `^` of the others.
+                BinaryOperator::BitwiseXor if is_repeatable(left_operand) && is_repeatable(right_operand) => {
+                    let (left, right) = (expression(left_operand, own + 1), expression(right_operand, own + 1));
+                    let sql = format!("({} | {}) - ({} & {})", left, right, left, right);
+                    return if precedence(&BinaryOperator::Minus) < parent_precedence { format!("({})", sql) } else { sql };
+                },
+                BinaryOperator::Plus => "+",
+                BinaryOperator::Minus => "-",
+                BinaryOperator::Multiply => "*",
+                BinaryOperator::Divide => "/",
+                BinaryOperator::Modulo => "%",
+                BinaryOperator::BitwiseAnd => "&",
+                BinaryOperator::BitwiseOr => "|",
+                BinaryOperator::BitwiseXor => "^",
+                BinaryOperator::ShiftLeft => "<<",
+                BinaryOperator::ShiftRight => ">>",
+                // IS and IS NOT are the comparisons of SQLite that treat NULL
+                // as an ordinary value
+                BinaryOperator::IsDistinctFrom => "IS NOT",
+                BinaryOperator::IsNotDistinctFrom => "IS",
+                // SQLite has no SIMILAR TO, so the statement is rejected there,
+                // while REGEXP works once a regexp() function is loaded
+                BinaryOperator::SimilarTo => "SIMILAR TO",
+                BinaryOperator::NotSimilarTo => "NOT SIMILAR TO",
+                BinaryOperator::RegexMatch => "REGEXP",
+                BinaryOperator::NotRegexMatch => "NOT REGEXP",
+                BinaryOperator::GreaterThan => ">",
+                BinaryOperator::GreaterThanOrEqual => ">=",
+                BinaryOperator::LessThan => "<",
+                BinaryOperator::LessThanOrEqual => "<=",
+                BinaryOperator::Equal => "=",
+                BinaryOperator::NotEqual => "<>",
+                BinaryOperator::And => "AND",
+                BinaryOperator::Or => "OR",
+            };
+            // Operators are left-associative, so an operand on the right with the
+            // same precedence must keep its parentheses
+            let sql = format!("{} {} {}", expression(left_operand, own), symbol, expression(right_operand, own + 1));
+            if own < parent_precedence { format!("({})", sql) } else { sql }
+        },
+        Expression::UnaryOperation { operand, operator } => {
+            // A unary operator binds tighter than any binary operator in this
+            // crate, but NOT does not in SQLite, so compound operands keep their
+            // parentheses, and so does NOT itself inside of a comparison. Nested unary operators get
+            // them too, as `--` would start a comment.
+            let operand = match **operand {
+                Expression::UnaryOperation { .. } => format!("({})", expression(operand, 0)),
+                _ => expression(operand, u8::MAX),
+            };
+            match operator {
+                UnaryOperator::Plus => format!("+{}", operand),
+                UnaryOperator::Minus => format!("-{}", operand),
+                UnaryOperator::BitwiseNot => format!("~{}", operand),
+                UnaryOperator::Not if parent_precedence > NOT_PRECEDENCE => format!("(NOT {})", operand),
+                UnaryOperator::Not => format!("NOT {}", operand),
+                UnaryOperator::Asc => format!("{} ASC", operand),
+                UnaryOperator::Desc => format!("{} DESC", operand),
+            }
+        },
+        Expression::Number(number) => number.to_string(),
+        // Debug keeps the fraction of whole numbers (`3.0`), so SQLite still
+        // reads them as real numbers
+        Expression::Float(number) => format!("{:?}", number),
+        // SQLite has no bit strings, so the bits are written as the number they
+        // make up, or kept as text when there are too many of them
+        Expression::BitString(bits) => match i64::from_str_radix(bits, 2) {
+            Ok(number) => number.to_string(),
+            Err(_) => format!("'{}'", bits),
+        },
+        // SQLite numbers its parameters with a question mark
+        Expression::Placeholder(Placeholder::Anonymous) => "?".to_string(),
+        Expression::Placeholder(Placeholder::Numbered(number)) => format!("?{}", number),
+        Expression::Placeholder(Placeholder::Named(name)) => format!(":{}", name),
+        // SQLite keeps dates and times as text, which compares correctly in
+        // the ISO format the literals are written in
+        Expression::Temporal { value, .. } => format!("'{}'", value),
+        // SQLite stores booleans as integers
+        Expression::Bool(value) => if *value { "1".to_string() } else { "0".to_string() },
+        Expression::Null => "NULL".to_string(),
+        Expression::Identifier(name) => identifier(name),
+        Expression::CompoundIdentifier(parts) => {
+            parts.iter().map(|part| identifier(part)).collect::<Vec<String>>().join(".")
+        },
+        Expression::String(string) => format!("'{}'", string.replace('\'', "''")),
+        Expression::Wildcard => "*".to_string(),
+        Expression::QualifiedWildcard(table) => format!("{}.*", identifier(table)),
+        Expression::Subquery(subquery) => format!("({})", SQLITE.query(subquery)),
+        // SQLite does not know the quantifiers, so it rejects the statement
+        // with an error of its own rather than running something else
+        Expression::Quantified { quantifier, subquery } => format!("{} ({})", quantifier, SQLITE.query(subquery)),
+        Expression::Tuple(elements) => format!("({})", SQLITE.expressions(elements)),
+        Expression::InList { expression: value, list, negated } => {
+            // SQLite compares row values against a list of rows only when the
+            // list is written as VALUES
+            let list = if list.iter().any(|item| matches!(item, Expression::Tuple(_))) {
+                let rows: Vec<String> = list
+                    .iter()
+                    .map(|item| match item {
+                        Expression::Tuple(_) => expression(item, 0),
+                        _ => format!("({})", expression(item, 0)),
+                    })
+                    .collect();
+                format!("VALUES {}", rows.join(", "))
+            } else {
+                SQLITE.expressions(list)
+            };
+            let sql = format!("{} {}IN ({})", expression(value, u8::MAX), if *negated { "NOT " } else { "" }, list);
+            if parent_precedence > precedence(&BinaryOperator::Equal) { format!("({})", sql) } else { sql }
+        },
+        Expression::Like { expression: value, pattern, negated, escape } => {
+            let mut sql = format!(
+                "{} {}LIKE {}",
+                expression(value, u8::MAX),
+                if *negated { "NOT " } else { "" },
+                expression(pattern, u8::MAX)
+            );
+            if let Some(escape) = escape {
+                sql.push_str(&format!(" ESCAPE '{}'", escape.to_string().replace('\'', "''")));
+            }
+            if parent_precedence > precedence(&BinaryOperator::Equal) { format!("({})", sql) } else { sql }
+        },
+        Expression::IsNull { expression: value, negated } => {
+            let sql = format!("{} IS {}NULL", expression(value, u8::MAX), if *negated { "NOT " } else { "" });
+            if parent_precedence > precedence(&BinaryOperator::Equal) { format!("({})", sql) } else { sql }
+        },
+        // SQLite has no EXTRACT, the parts of a date are formatted out of it
+        // with strftime instead
+        Expression::Extract { field, expression: value } => {
+            let part = |format: &str| format!("CAST(strftime('{}', {}) AS INTEGER)", format, expression(value, 0));
+            match field {
+                DateField::Year => part("%Y"),
+                DateField::Quarter => {
+                    let sql = format!("({} + 2) / 3", part("%m"));
+                    if parent_precedence > precedence(&BinaryOperator::Divide) { format!("({})", sql) } else { sql }
+                },
+                DateField::Month => part("%m"),
+                DateField::Week => part("%W"),
+                DateField::Day => part("%d"),
+                DateField::Hour => part("%H"),
+                DateField::Minute => part("%M"),
+                DateField::Second => part("%S"),
+            }
+        },
+        Expression::Cast { expression: value, target_type } => {
+            format!("CAST({} AS {})", expression(value, 0), storage_class(target_type))
+        },
+        Expression::InSubquery { expression: value, subquery } => {
+            let sql = format!("{} IN ({})", expression(value, u8::MAX), SQLITE.query(subquery));
+            if parent_precedence > precedence(&BinaryOperator::Equal) { format!("({})", sql) } else { sql }
+        },
+        Expression::Exists { subquery, negated } => {
+            format!("{}EXISTS ({})", if *negated { "NOT " } else { "" }, SQLITE.query(subquery))
+        },
+        Expression::Function { name, distinct, arguments, over } => {
+            let call = format!("{}({}{})", name, if *distinct { "DISTINCT " } else { "" }, SQLITE.expressions(arguments));
+            match over {
+                Some(window) => format!("{} OVER ({})", call, SQLITE.window_specification(window)),
+                None => call,
+            }
+        },
+    }
+}
@@ -0,0 +1,345 @@
+use crate::catalog::Catalog;
+use crate::statement::{
+    BinaryOperator, Constraint, DBType, Expression, Placeholder, Statement, TableColumn, TableReference, UnaryOperator,
+};
+
+// Renders the documentation of the schema as Markdown: a section for every
+// table with its comment and a table of its columns (their types, constraints
+// and comments), followed by a section for every view with its comment and the
+// tables and views it reads from. Objects are listed in the order they were
+// created.
+pub fn markdown(catalog: &Catalog) -> String {
+    let mut output = String::from("# Schema\n");
+    if catalog.tables().next().is_some() {
+        output.push_str("\n## Tables\n");
+    }
+    for (name, columns) in catalog.tables() {
+        output.push_str(&format!("\n### {}\n\n", markdown_text(name)));
+        if let Some(comment) = catalog.comment(name) {
+            output.push_str(&format!("{}\n\n", markdown_text(comment)));
+        }
+        output.push_str("| Column | Type | Constraints | Description |\n");
+        output.push_str("| --- | --- | --- | --- |\n");
+        for column in columns {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                markdown_text(&column.column_name),
+                column_type(&column.column_type),
+                markdown_text(&constraints(column)),
+                markdown_text(description(column).unwrap_or("")),
+            ));
+        }
+    }
+    if catalog.views().next().is_some() {
+        output.push_str("\n## Views\n");
+    }
+    for (name, query) in catalog.views() {
+        output.push_str(&format!("\n### {}\n\n", markdown_text(name)));
+        if let Some(comment) = catalog.comment(name) {
+            output.push_str(&format!("{}\n\n", markdown_text(comment)));
+        }
+        let sources: Vec<String> = sources(query).iter().map(|source| format!("`{}`", source)).collect();
+        output.push_str(&format!("Reads from: {}\n", sources.join(", ")));
+    }
+    output
+}
+
+// Renders the same documentation as `markdown`, as a standalone HTML page
+pub fn html(catalog: &Catalog) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Schema</title>\n</head>\n<body>\n<h1>Schema</h1>\n",
+    );
+    if catalog.tables().next().is_some() {
+        output.push_str("<h2>Tables</h2>\n");
+    }
+    for (name, columns) in catalog.tables() {
+        output.push_str(&format!("<h3>{}</h3>\n", html_text(name)));
+        if let Some(comment) = catalog.comment(name) {
+            output.push_str(&format!("<p>{}</p>\n", html_text(comment)));
+        }
+        output.push_str("<table>\n<tr><th>Column</th><th>Type</th><th>Constraints</th><th>Description</th></tr>\n");
+        for column in columns {
+            output.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                html_text(&column.column_name),
+                column_type(&column.column_type),
+                html_text(&constraints(column)),
+                html_text(description(column).unwrap_or("")),
+            ));
+        }
+        output.push_str("</table>\n");
+    }
+    if catalog.views().next().is_some() {
+        output.push_str("<h2>Views</h2>\n");
+    }
+    for (name, query) in catalog.views() {
+        output.push_str(&format!("<h3>{}</h3>\n", html_text(name)));
+        if let Some(comment) = catalog.comment(name) {
+            output.push_str(&format!("<p>{}</p>\n", html_text(comment)));
+        }
+        let sources: Vec<String> = sources(query)
+            .iter()
+            .map(|source| format!("<code>{}</code>", html_text(source)))
+            .collect();
+        output.push_str(&format!("<p>Reads from: {}</p>\n", sources.join(", ")));
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+// The same summary as `markdown` as a JSON document, for tools that process
+// the schema further:
+// ```json
+// { "tables": [{ "name": ..., "comment": ..., "columns": [{ "name": ..., "type": ..., "constraints": [...], "comment": ... }] }],
+//   "views": [{ "name": ..., "comment": ..., "reads_from": [...] }] }
+// ```
+#[cfg(feature = "serde")]
+pub fn json(catalog: &Catalog) -> serde_json::Value {
+    use serde_json::json;
+
+    let tables: Vec<serde_json::Value> = catalog
+        .tables()
+        .map(|(name, columns)| {
+            let columns: Vec<serde_json::Value> = columns
+                .iter()
+                .map(|column| json!({
+                    "name": column.column_name,
+                    "type": column_type(&column.column_type),
+                    "constraints": constraint_list(column),
+                    "comment": description(column),
+                }))
+                .collect();
+            json!({ "name": name, "comment": catalog.comment(name), "columns": columns })
+        })
+        .collect();
+    let views: Vec<serde_json::Value> = catalog
+        .views()
+        .map(|(name, query)| json!({ "name": name, "comment": catalog.comment(name), "reads_from": sources(query) }))
+        .collect();
+    json!({ "tables": tables, "views": views })
+}
+
+// A pipe would end the cell of a Markdown table, and a line break would end
+// the whole row
+fn markdown_text(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn html_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn integer_type(name: &str, unsigned: bool) -> String {
+    if unsigned { format!("{} UNSIGNED", name) } else { name.to_string() }
+}
+
+fn column_type(column_type: &DBType) -> String {
+    match column_type {
+        DBType::Int { unsigned } => integer_type("INT", *unsigned),
+        DBType::SmallInt { unsigned } => integer_type("SMALLINT", *unsigned),
+        DBType::BigInt { unsigned } => integer_type("BIGINT", *unsigned),
+        DBType::TinyInt { unsigned } => integer_type("TINYINT", *unsigned),
+        DBType::Varchar(Some(length)) => format!("VARCHAR({})", length),
+        DBType::Varchar(None) => "VARCHAR".to_string(),
+        DBType::Bool => "BOOL".to_string(),
+        DBType::Decimal { precision: None, .. } => "DECIMAL".to_string(),
+        DBType::Decimal { precision: Some(precision), scale: None } => format!("DECIMAL({})", precision),
+        DBType::Decimal { precision: Some(precision), scale: Some(scale) } => format!("DECIMAL({}, {})", precision, scale),
+        DBType::Float => "FLOAT".to_string(),
+        DBType::Real => "REAL".to_string(),
+        DBType::Double => "DOUBLE PRECISION".to_string(),
+        DBType::Date => "DATE".to_string(),
+        DBType::Time => "TIME".to_string(),
+        DBType::Timestamp { with_time_zone: false } => "TIMESTAMP".to_string(),
+        DBType::Timestamp { with_time_zone: true } => "TIMESTAMP WITH TIME ZONE".to_string(),
+        DBType::DateTime => "DATETIME".to_string(),
+        DBType::Blob => "BLOB".to_string(),
+        DBType::Uuid => "UUID".to_string(),
+        DBType::Serial => "SERIAL".to_string(),
+        DBType::BigSerial => "BIGSERIAL".to_string(),
+        DBType::Custom(name) => name.clone(),
+    }
+}
+
+// The constraints of the column as they are written in SQL. The comment of the
+// column is its description, so it is not listed here.
+fn constraints(column: &TableColumn) -> String {
+    constraint_list(column).join(", ")
+}
+
+fn constraint_list(column: &TableColumn) -> Vec<String> {
+    column
+        .constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::PrimaryKey => Some("PRIMARY KEY".to_string()),
+            Constraint::NotNull => Some("NOT NULL".to_string()),
+            Constraint::Null => Some("NULL".to_string()),
+            Constraint::Check(check) => Some(format!("CHECK ({})", expression(check))),
+            Constraint::Default(value) => Some(format!("DEFAULT {}", expression(value))),
+            Constraint::AutoIncrement => Some("AUTO_INCREMENT".to_string()),
+            Constraint::Identity { always: true } => Some("GENERATED ALWAYS AS IDENTITY".to_string()),
+            Constraint::Identity { always: false } => Some("GENERATED BY DEFAULT AS IDENTITY".to_string()),
+            Constraint::Comment(_) => None,
+        })
+        .collect()
+}
+
+fn description(column: &TableColumn) -> Option<&str> {
+    column.constraints.iter().find_map(|constraint| match constraint {
+        Constraint::Comment(text) => Some(text.as_str()),
+        _ => None,
+    })
+}
+
+// The names of the tables and views a query reads from, without duplicates.
+// Common table expressions are a part of the query itself, so the tables they
+// read from are listed instead of their names.
+fn sources(query: &Statement) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_sources(query, &mut names);
+    names
+}
+
+fn collect_sources(query: &Statement, names: &mut Vec<String>) {
+    if let Statement::Select { with, from, .. } = query {
+        for cte in with {
+            collect_sources(&cte.query, names);
+        }
+        let mut references: Vec<&TableReference> = from.iter().rev().collect();
+        while let Some(reference) = references.pop() {
+            match reference {
+                TableReference::Table { name, .. } => {
+                    let is_cte = !name.is_qualified() && with.iter().any(|cte| cte.name.eq_ignore_ascii_case(name.base_name()));
+                    let name = name.to_string();
+                    if !is_cte && !names.iter().any(|known| known.eq_ignore_ascii_case(&name)) {
+                        names.push(name);
+                    }
+                },
+                TableReference::Derived { subquery, .. } => collect_sources(subquery, names),
+                TableReference::Join { left, right, .. } => {
+                    references.push(right);
+                    references.push(left);
+                },
+            }
+        }
+    }
+}
+
+// Writes a CHECK expression back as SQL. Nested operations are always put in
+// parentheses, which keeps the meaning of the expression obvious to the reader
+// without having to know the precedence of the operators.
+fn expression(expr: &Expression) -> String {
+    match expr {
+        // Only PostgreSQL has an operator for the power, so it is written as
+        // the function every database has
+        Expression::BinaryOperation { left_operand, operator: BinaryOperator::Power, right_operand } => {
+            format!("POWER({}, {})", expression(left_operand), expression(right_operand))
+        },
+        Expression::BinaryOperation { left_operand, operator, right_operand } => format!(
+            "{} {} {}",
+            operand(left_operand),
+            binary_operator(operator),
+            operand(right_operand)
+        ),
+        Expression::UnaryOperation { operand: inner, operator } => match operator {
+            UnaryOperator::Not => format!("NOT {}", operand(inner)),
+            UnaryOperator::Plus => format!("+{}", operand(inner)),
+            UnaryOperator::Minus => format!("-{}", operand(inner)),
+            UnaryOperator::BitwiseNot => format!("~{}", operand(inner)),
+            UnaryOperator::Asc => format!("{} ASC", operand(inner)),
+            UnaryOperator::Desc => format!("{} DESC", operand(inner)),
+        },
+        Expression::Number(number) => number.to_string(),
+        Expression::Float(number) => format!("{:?}", number),
+        Expression::BitString(bits) => format!("B'{}'", bits),
+        Expression::Placeholder(Placeholder::Anonymous) => "?".to_string(),
+        Expression::Placeholder(Placeholder::Numbered(number)) => format!("${}", number),
+        Expression::Placeholder(Placeholder::Named(name)) => format!(":{}", name),
+        Expression::Temporal { kind, value } => format!("{} '{}'", kind, value),
+        Expression::Bool(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        Expression::Null => "NULL".to_string(),
+        Expression::Identifier(name) => name.clone(),
+        Expression::CompoundIdentifier(parts) => parts.join("."),
+        Expression::String(text) => format!("'{}'", text.replace('\'', "''")),
+        Expression::Wildcard => "*".to_string(),
+        Expression::QualifiedWildcard(table) => format!("{}.*", table),
+        Expression::Function { name, distinct, arguments, .. } => {
+            let arguments: Vec<String> = arguments.iter().map(expression).collect();
+            format!("{}({}{})", name, if *distinct { "DISTINCT " } else { "" }, arguments.join(", "))
+        },
+        // Subqueries are not expected in CHECK constraints, so only the tables
+        // they read from are shown
+        Expression::Subquery(subquery) => format!("(SELECT ... FROM {})", sources(subquery).join(", ")),
+        Expression::Tuple(elements) => {
+            let elements: Vec<String> = elements.iter().map(expression).collect();
+            format!("({})", elements.join(", "))
+        },
+        Expression::InList { expression: inner, list, negated } => {
+            let list: Vec<String> = list.iter().map(expression).collect();
+            format!("{} {}IN ({})", operand(inner), if *negated { "NOT " } else { "" }, list.join(", "))
+        },
+        Expression::Like { expression: inner, pattern, negated, escape } => {
+            let escape = escape.map(|escape| format!(" ESCAPE '{}'", escape)).unwrap_or_default();
+            format!("{} {}LIKE {}{}", operand(inner), if *negated { "NOT " } else { "" }, operand(pattern), escape)
+        },
+        Expression::IsNull { expression: inner, negated } => {
+            format!("{} IS {}NULL", operand(inner), if *negated { "NOT " } else { "" })
+        },
+        Expression::Extract { field, expression: inner } => format!("EXTRACT({} FROM {})", field, expression(inner)),
+        Expression::Cast { expression: inner, target_type } => {
+            format!("CAST({} AS {})", expression(inner), column_type(target_type))
+        },
+        Expression::InSubquery { expression: inner, subquery } => {
+            format!("{} IN (SELECT ... FROM {})", operand(inner), sources(subquery).join(", "))
+        },
+        Expression::Quantified { quantifier, subquery } => {
+            format!("{} (SELECT ... FROM {})", quantifier, sources(subquery).join(", "))
+        },
+        Expression::Exists { subquery, negated } => format!(
+            "{}EXISTS (SELECT ... FROM {})",
+            if *negated { "NOT " } else { "" },
+            sources(subquery).join(", ")
+        ),
+    }
+}
+
+fn operand(expr: &Expression) -> String {
+    match expr {
+        Expression::BinaryOperation { .. } => format!("({})", expression(expr)),
+        _ => expression(expr),
+    }
+}
+
+fn binary_operator(operator: &BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Plus => "+",
+        BinaryOperator::Minus => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Power => "^",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+        BinaryOperator::IsDistinctFrom => "IS DISTINCT FROM",
+        BinaryOperator::IsNotDistinctFrom => "IS NOT DISTINCT FROM",
+        BinaryOperator::SimilarTo => "SIMILAR TO",
+        BinaryOperator::NotSimilarTo => "NOT SIMILAR TO",
+        BinaryOperator::RegexMatch => "REGEXP",
+        BinaryOperator::NotRegexMatch => "NOT REGEXP",
+        BinaryOperator::GreaterThan => ">",
+        BinaryOperator::GreaterThanOrEqual => ">=",
+        BinaryOperator::LessThan => "<",
+        BinaryOperator::LessThanOrEqual => "<=",
+        BinaryOperator::Equal => "=",
+        BinaryOperator::NotEqual => "<>",
+        BinaryOperator::And => "AND",
+        BinaryOperator::Or => "OR",
+    }
+}
@@ -0,0 +1,13 @@
+use crate::token::{Span, Token};
+
+/// Parses the statements that start with a word the parser does not know itself, like `VACUUM` of SQLite or `COPY` of PostgreSQL, into `Statement::Custom`. A handler is registered with `Parser::set_statement_handler`; the parser asks it about every statement whose first word it does not recognize, and hands it the tokens of the statement once it claims the word.
+pub trait StatementHandler {
+    // Whether the handler parses the statements that start with the word,
+    // which is given in upper case
+    fn handles(&self, keyword: &str) -> bool;
+
+    // Makes the payload of the statement out of the tokens after its first
+    // word, up to its semicolon, each with the span of the input it was read
+    // from. An error is reported as the error of the statement.
+    fn parse(&mut self, keyword: &str, tokens: &[(Token, Span)]) -> Result<String, String>;
+}
@@ -0,0 +1,164 @@
+use std::ops::Range;
+use crate::token::Span;
+
+/// A cleanup step that rewrites the input before it is tokenized, so that SQL exported or pasted from other tools parses without manual editing. A filter reads the whole input and writes its result into a `FilterOutput`, telling for every piece of the result which bytes of the input it stands for. That way the spans of the tokens can be traced back to the original input (see `FilteredInput`).
+pub trait InputFilter {
+    fn filter(&self, input: &str, output: &mut FilterOutput);
+}
+
+/// The text produced by an `InputFilter`, together with the range of the input every byte of it comes from.
+#[derive(Debug, Default)]
+pub struct FilterOutput {
+    text: String,
+    origins: Vec<Range<usize>>,
+}
+
+impl FilterOutput {
+    // Appends a part of the input unchanged, `origin` being its offset in the input
+    pub fn copy(&mut self, text: &str, origin: usize) {
+        self.text.push_str(text);
+        self.origins.extend((origin..origin + text.len()).map(|offset| offset..offset + 1));
+    }
+
+    // Appends text that replaces the given range of the input. The range is
+    // dropped from the input when the text is empty.
+    pub fn replace(&mut self, text: &str, original: Range<usize>) {
+        self.text.push_str(text);
+        self.origins.extend(std::iter::repeat_n(original, text.len()));
+    }
+}
+
+/// The input after a chain of filters, which is what the tokenizer should read (`FilteredInput::text`), with the mapping of its offsets back to the original input, which is what spans should be reported in:
+/// ```rust
+/// let input = FilteredInput::standard(&raw);
+/// let mut parser = Parser::new(Tokenizer::new(input.text()))?;
+/// let statement = parser.parse_statement()?;
+/// let line = raw[..input.original_offset(parser.offset())].matches('\n').count() + 1;
+/// ```
+#[derive(Debug)]
+pub struct FilteredInput {
+    text: String,
+    // The range of the original input that every byte of the text comes from
+    origins: Vec<Range<usize>>,
+    original_len: usize,
+}
+
+impl FilteredInput {
+    // Runs the filters over the input, one after another
+    pub fn new(input: &str, filters: &[&dyn InputFilter]) -> Self {
+        let mut filtered = Self {
+            text: input.to_string(),
+            origins: (0..input.len()).map(|offset| offset..offset + 1).collect(),
+            original_len: input.len(),
+        };
+        for filter in filters {
+            let mut output = FilterOutput::default();
+            filter.filter(&filtered.text, &mut output);
+            // The output of the filter points into the text it read, which in
+            // turn points into the original input
+            let origins = output
+                .origins
+                .iter()
+                .map(|range| filtered.original_range(range.clone()))
+                .collect();
+            filtered.text = output.text;
+            filtered.origins = origins;
+        }
+        filtered
+    }
+
+    // Runs every built-in filter: strips the byte order mark and the psql
+    // meta-commands, and normalizes line endings and typographic quotes
+    pub fn standard(input: &str) -> Self {
+        Self::new(input, &[&StripByteOrderMark, &StripPsqlCommands, &NormalizeLineEndings, &NormalizeQuotes])
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // The offset of the original input that an offset of the text comes from
+    pub fn original_offset(&self, offset: usize) -> usize {
+        self.origins.get(offset).map_or(self.original_len, |range| range.start)
+    }
+
+    // The part of the original input that a span of the text covers
+    pub fn original_span(&self, span: Span) -> Span {
+        let range = self.original_range(span.start..span.end);
+        Span { start: range.start, end: range.end }
+    }
+
+    fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.original_offset(range.start);
+        let end = if range.end > range.start {
+            self.origins.get(range.end - 1).map_or(self.original_len, |origin| origin.end)
+        } else {
+            start
+        };
+        start..end
+    }
+}
+
+/// Removes the byte order mark that some editors on Windows put at the start of UTF-8 files.
+pub struct StripByteOrderMark;
+
+impl InputFilter for StripByteOrderMark {
+    fn filter(&self, input: &str, output: &mut FilterOutput) {
+        match input.strip_prefix('\u{FEFF}') {
+            Some(rest) => output.copy(rest, input.len() - rest.len()),
+            None => output.copy(input, 0),
+        }
+    }
+}
+
+/// Replaces the typographic quotes that word processors and chat tools put in place of straight ones (`“users”`, `‘Ann’`) with the straight quotes SQL uses.
+pub struct NormalizeQuotes;
+
+impl InputFilter for NormalizeQuotes {
+    fn filter(&self, input: &str, output: &mut FilterOutput) {
+        for (offset, character) in input.char_indices() {
+            let original = offset..offset + character.len_utf8();
+            match character {
+                '\u{201C}' | '\u{201D}' | '\u{201E}' => output.replace("\"", original),
+                '\u{2018}' | '\u{2019}' | '\u{201A}' => output.replace("'", original),
+                _ => output.copy(&input[original], offset),
+            }
+        }
+    }
+}
+
+/// Turns Windows (`\r\n`) and old Mac (`\r`) line endings into `\n`.
+pub struct NormalizeLineEndings;
+
+impl InputFilter for NormalizeLineEndings {
+    fn filter(&self, input: &str, output: &mut FilterOutput) {
+        let mut offset = 0;
+        while let Some(position) = input[offset..].find('\r') {
+            let carriage_return = offset + position;
+            output.copy(&input[offset..carriage_return], offset);
+            let end = if input[carriage_return..].starts_with("\r\n") { carriage_return + 2 } else { carriage_return + 1 };
+            output.replace("\n", carriage_return..end);
+            offset = end;
+        }
+        output.copy(&input[offset..], offset);
+    }
+}
+
+/// Removes the meta-commands of psql (lines starting with a backslash, like `\connect shop` or `\set ON_ERROR_STOP on`) that appear in dumps made by `pg_dump`. The line breaks are kept, so the lines of the statements stay where they were.
+pub struct StripPsqlCommands;
+
+impl InputFilter for StripPsqlCommands {
+    fn filter(&self, input: &str, output: &mut FilterOutput) {
+        let mut offset = 0;
+        for line in input.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            if content.trim_start().starts_with('\\') {
+                output.replace("", offset..offset + content.len());
+                output.copy(&line[content.len()..], offset + content.len());
+            } else {
+                output.copy(line, offset);
+            }
+            offset += line.len();
+        }
+    }
+}
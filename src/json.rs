@@ -0,0 +1,212 @@
+use crate::messages::Message;
+use crate::statement::Statement;
+use serde_json::{json, Value};
+
+/// The version of the JSON format of statements. A serialized statement is a document of this shape:
+/// ```json
+/// { "schema_version": 9, "statement": { "Select": { ... } } }
+/// ```
+/// The version is increased whenever a change of the AST changes how already serialized statements look (a renamed or removed variant or field). Adding a new variant does not need a new version, as the old documents are still valid. Every increase comes with a new step in `MIGRATIONS`, so that documents stored by older versions of the parser can still be read.
+pub const AST_SCHEMA_VERSION: u64 = 9;
+
+// `MIGRATIONS[i]` turns a statement of version `i + 1` into a statement of
+// version `i + 2`, so there is one step less than there are versions
+const MIGRATIONS: &[fn(Value) -> Value] = &[
+    add_table_options,
+    add_if_not_exists,
+    add_distinct_arguments,
+    add_integer_signedness,
+    add_table_constraints,
+    add_create_modifiers,
+    qualify_object_names,
+    add_returning,
+];
+
+// Version 2 added the table options to `CreateTable`. Tables can not be
+// nested into other statements, so only the top level needs to be changed.
+fn add_table_options(mut statement: Value) -> Value {
+    if let Some(table) = statement.get_mut("CreateTable").and_then(Value::as_object_mut) {
+        table.entry("options").or_insert_with(|| json!([]));
+    }
+    statement
+}
+
+// Version 3 added the IF NOT EXISTS guard to `CreateTable` and `CreateView`,
+// which are both only found at the top level
+fn add_if_not_exists(mut statement: Value) -> Value {
+    for variant in ["CreateTable", "CreateView"] {
+        if let Some(create) = statement.get_mut(variant).and_then(Value::as_object_mut) {
+            create.entry("if_not_exists").or_insert(json!(false));
+        }
+    }
+    statement
+}
+
+// Version 4 added the DISTINCT flag to function calls. Calls can be nested
+// anywhere in a statement, so the whole document is searched for them.
+fn add_distinct_arguments(mut statement: Value) -> Value {
+    match &mut statement {
+        Value::Object(object) => {
+            if let Some(call) = object.get_mut("Function").and_then(Value::as_object_mut) {
+                call.entry("distinct").or_insert(json!(false));
+            }
+            for value in object.values_mut() {
+                *value = add_distinct_arguments(value.take());
+            }
+        },
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = add_distinct_arguments(item.take());
+            }
+        },
+        _ => {},
+    }
+    statement
+}
+
+// Version 5 made the integer types record whether they are unsigned, so they
+// are no longer plain strings. Types are found in the columns of tables and
+// in casts, which can be nested anywhere in a statement.
+fn add_integer_signedness(mut statement: Value) -> Value {
+    match &mut statement {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(name) if (key == "column_type" || key == "target_type")
+                        && ["Int", "SmallInt", "BigInt", "TinyInt"].contains(&name.as_str()) =>
+                    {
+                        *value = json!({ name.as_str(): { "unsigned": false } });
+                    },
+                    _ => *value = add_integer_signedness(value.take()),
+                }
+            }
+        },
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = add_integer_signedness(item.take());
+            }
+        },
+        _ => {},
+    }
+    statement
+}
+
+// Version 6 added the constraints of the whole table to `CreateTable`, which
+// is only found at the top level
+fn add_table_constraints(mut statement: Value) -> Value {
+    if let Some(table) = statement.get_mut("CreateTable").and_then(Value::as_object_mut) {
+        table.entry("constraints").or_insert_with(|| json!([]));
+    }
+    statement
+}
+
+// Version 7 added the TEMPORARY modifier to `CreateTable` and the OR REPLACE
+// modifier to `CreateView`, which are both only found at the top level
+fn add_create_modifiers(mut statement: Value) -> Value {
+    for (variant, modifier) in [("CreateTable", "temporary"), ("CreateView", "or_replace")] {
+        if let Some(create) = statement.get_mut(variant).and_then(Value::as_object_mut) {
+            create.entry(modifier).or_insert(json!(false));
+        }
+    }
+    statement
+}
+
+// Version 8 made the names of tables and views qualified with their schema,
+// so they are lists of parts instead of plain strings. The names of created,
+// dropped, commented and inserted into objects are only found at the top
+// level, but table references can be nested anywhere in a statement.
+fn qualify_object_names(mut statement: Value) -> Value {
+    for (variant, field) in [("CreateTable", "table_name"), ("CreateView", "name"), ("Insert", "table_name"), ("DropTable", "table_name")] {
+        if let Some(name) = statement.get_mut(variant).and_then(|object| object.get_mut(field)) {
+            qualify_name(name);
+        }
+    }
+    if let Some(target) = statement.get_mut("Comment").and_then(|comment| comment.get_mut("target")) {
+        for kind in ["Table", "View"] {
+            if let Some(name) = target.get_mut(kind) {
+                qualify_name(name);
+            }
+        }
+        if let Some(table) = target.get_mut("Column").and_then(|column| column.get_mut("table")) {
+            qualify_name(table);
+        }
+    }
+    qualify_table_references(statement)
+}
+
+fn qualify_table_references(mut value: Value) -> Value {
+    match &mut value {
+        Value::Object(object) => {
+            if let Some(name) = object.get_mut("Table").and_then(|table| table.get_mut("name")) {
+                qualify_name(name);
+            }
+            for value in object.values_mut() {
+                *value = qualify_table_references(value.take());
+            }
+        },
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = qualify_table_references(item.take());
+            }
+        },
+        _ => {},
+    }
+    value
+}
+
+fn qualify_name(name: &mut Value) {
+    if name.is_string() {
+        *name = json!([name.take()]);
+    }
+}
+
+// Version 9 added the RETURNING clause to `Insert`, which is only found at the
+// top level
+fn add_returning(mut statement: Value) -> Value {
+    if let Some(insert) = statement.get_mut("Insert").and_then(Value::as_object_mut) {
+        insert.entry("returning").or_insert_with(|| json!([]));
+    }
+    statement
+}
+
+// Serializes a statement into a document tagged with the current schema version
+pub fn to_json(statement: &Statement) -> Value {
+    // The AST only consists of plain structs, enums, strings and numbers, so
+    // it can always be represented as JSON
+    let statement = serde_json::to_value(statement).expect("statements are always serializable");
+    json!({
+        "schema_version": AST_SCHEMA_VERSION,
+        "statement": statement,
+    })
+}
+
+// Upgrades a document written by any earlier version of the parser to the
+// current schema version
+pub fn migrate_ast_json(document: Value) -> Result<Value, Message> {
+    let version = document
+        .get("schema_version")
+        .ok_or(Message::MissingSchemaVersion)?
+        .as_u64()
+        .ok_or_else(|| Message::InvalidAstJson { reason: "schema_version is not a number".to_string() })?;
+    if version == 0 || version > AST_SCHEMA_VERSION {
+        return Err(Message::UnsupportedSchemaVersion { version });
+    }
+
+    let Some(statement) = document.get("statement") else {
+        return Err(Message::InvalidAstJson { reason: "missing statement".to_string() });
+    };
+    let statement = MIGRATIONS[version as usize - 1..]
+        .iter()
+        .fold(statement.clone(), |statement, migrate| migrate(statement));
+    Ok(json!({
+        "schema_version": AST_SCHEMA_VERSION,
+        "statement": statement,
+    }))
+}
+
+// Deserializes a statement from a document of any supported schema version
+pub fn from_json(document: Value) -> Result<Statement, Message> {
+    let mut document = migrate_ast_json(document)?;
+    serde_json::from_value(document["statement"].take())
+        .map_err(|e| Message::InvalidAstJson { reason: e.to_string() })
+}
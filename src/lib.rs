@@ -1,12 +1,58 @@
+// Marks a grammar rule as exercised. Compiles to nothing unless the
+// `coverage` or the `trace` feature is enabled.
+macro_rules! cover {
+    ($rule:expr) => {
+        #[cfg(feature = "coverage")]
+        crate::coverage::record($rule);
+        #[cfg(feature = "trace")]
+        crate::trace::record_rule($rule);
+    };
+}
+
+pub mod messages;
 pub mod token;
 pub mod tokenizer;
+pub mod filters;
 pub mod statement;
+mod sql;
 pub mod parser;
+pub mod observer;
+pub mod extension;
+pub mod catalog;
+pub mod resolve;
+pub mod rewrite;
+pub mod dialects;
+pub mod session;
+pub mod docs;
+pub mod migrations;
+pub mod path;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "serde")]
+pub mod json;
 
-pub use crate::token::{Token, Keyword};
-pub use crate::tokenizer::Tokenizer;
-pub use crate::parser::{Parser, build_statement};
+pub use crate::messages::{Message, Messages, EnglishMessages, NameKind};
+pub use crate::token::{Token, Keyword, Keywords, Span, Position};
+pub use crate::tokenizer::{Tokenizer, LexicalError};
+pub use crate::filters::{FilteredInput, InputFilter};
+pub use crate::parser::{Parser, ParserOptions, ParseError, DEFAULT_MAX_DEPTH, build_statement, build_located_statement, build_statements};
+pub use crate::dialects::Dialect;
+pub use crate::observer::{ParseObserver, Clause};
+pub use crate::extension::StatementHandler;
+pub use crate::catalog::Catalog;
+pub use crate::path::Node;
+pub use crate::session::Session;
+pub use crate::resolve::{resolve, ColumnBinding, ResolvedStatement};
+pub use crate::rewrite::views::expand_views;
+pub use crate::rewrite::ctes::inline_ctes;
+#[cfg(feature = "serde")]
+pub use crate::json::{to_json, from_json, migrate_ast_json, AST_SCHEMA_VERSION};
 pub use crate::statement::{
-    Statement, Expression, TableColumn, DBType,
-    Constraint, BinaryOperator, UnaryOperator
+    Statement, Expression, TableColumn, TableConstraint, DBType,
+    Constraint, BinaryOperator, UnaryOperator, Placeholder, TemporalKind, Quantifier, DateField,
+    TableReference, JoinType, CommonTableExpression, WindowSpecification,
+    LockingClause, LockStrength, WaitPolicy, Partitioning, PartitionMethod, CommentTarget, InsertSource,
+    ReturningItem, ObjectName
 };
\ No newline at end of file
@@ -1,37 +1,290 @@
-mod statement;
-mod token;
-mod tokenizer;
-mod parser;
-
+use std::collections::BTreeMap;
 use std::io::{self, Write};
-use crate::parser::build_statement;
+use std::time::{Duration, Instant};
+use programming_languages_project_kyrylo_yezholov::{
+    build_located_statement, docs, Catalog, FilteredInput, ParseError, ParseObserver, Parser, Session, Span, Statement, Tokenizer
+};
+
+// Width of the progress bar in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
+// How often the progress bar is redrawn
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Tracks the parsed statements of a file and draws a progress bar on stderr
+struct Progress {
+    total_bytes: usize,
+    statements: usize,
+    by_kind: BTreeMap<&'static str, usize>,
+    last_drawn: Option<Instant>,
+}
+
+impl Progress {
+    fn new(total_bytes: usize) -> Self {
+        Self {
+            total_bytes,
+            statements: 0,
+            by_kind: BTreeMap::new(),
+            last_drawn: None,
+        }
+    }
+
+    fn draw(&mut self, bytes: usize, force: bool) {
+        let now = Instant::now();
+        if !force && self.last_drawn.is_some_and(|last| now - last < PROGRESS_INTERVAL) {
+            return;
+        }
+        self.last_drawn = Some(now);
+
+        let fraction = if self.total_bytes == 0 { 1.0 } else { bytes as f64 / self.total_bytes as f64 };
+        let filled = (fraction * PROGRESS_BAR_WIDTH as f64) as usize;
+        eprint!(
+            "\r[{}{}] {:>3}% {}/{} bytes, {} statements",
+            "#".repeat(filled),
+            " ".repeat(PROGRESS_BAR_WIDTH - filled),
+            (fraction * 100.0) as usize,
+            bytes,
+            self.total_bytes,
+            self.statements,
+        );
+        io::stderr().flush().unwrap();
+    }
+}
+
+impl ParseObserver for Progress {
+    fn on_statement(&mut self, statement: &Statement, span: Span) {
+        self.statements += 1;
+        *self.by_kind.entry(statement.kind()).or_insert(0) += 1;
+        self.draw(span.end, false);
+    }
+}
+
+// A short description of a statement for the session commands, like `CREATE TABLE users`
+fn describe(statement: &Statement) -> String {
+    match statement {
+        Statement::CreateTable { table_name, .. } => format!("{} {}", statement.kind(), table_name),
+        Statement::CreateView { name, .. } => format!("{} {}", statement.kind(), name),
+        Statement::Insert { table_name, .. } | Statement::DropTable { table_name, .. } => {
+            format!("{} {}", statement.kind(), table_name)
+        },
+        Statement::Select { .. } | Statement::Comment { .. } => statement.kind().to_string(),
+        Statement::Custom { keyword, .. } => keyword.clone(),
+    }
+}
+
+// Handles a `:command` of the interactive mode. `last` is the statement that
+// was parsed last, which `:query` searches.
+fn run_command(session: &mut Session, last: Option<&Statement>, command: &str) {
+    if let Some(path) = command.strip_prefix(":query") {
+        match last.map(|statement| statement.query(path.trim())) {
+            None => println!("No statement was parsed yet"),
+            Some(Err(e)) => println!("Error: {}", e),
+            Some(Ok(nodes)) if nodes.is_empty() => println!("No nodes match {}", path.trim()),
+            Some(Ok(nodes)) => {
+                for node in nodes {
+                    println!("{:?}", node);
+                }
+            },
+        }
+        return;
+    }
+    match command {
+        ":undo" => match session.undo() {
+            Some(statement) => println!("Undone: {}", describe(statement)),
+            None => println!("Nothing to undo"),
+        },
+        ":redo" => match session.redo() {
+            Some(statement) => println!("Redone: {}", describe(statement)),
+            None => println!("Nothing to redo"),
+        },
+        ":log" => {
+            if session.log().is_empty() {
+                println!("No statements changed the schema yet");
+            }
+            for (i, statement) in session.log().iter().enumerate() {
+                println!("{:>3}. {}", i + 1, describe(statement));
+            }
+        },
+        _ => println!("Unknown command {}. Available commands: :undo, :redo, :log, :query <path>", command),
+    }
+}
+
+// The line number of a byte offset of the input
+fn line_of(input: &str, offset: usize) -> usize {
+    input[..offset].matches('\n').count() + 1
+}
+
+// Parses every statement of a file, reporting the errors and a summary at the end.
+// Returns whether the whole file was parsed without errors.
+fn parse_file(path: &str) -> bool {
+    let input = match std::fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: can not read {}: {}", path, e);
+            return false;
+        },
+    };
+
+    let start = Instant::now();
+    // Dumps often come from other tools, so they are cleaned up first. Errors
+    // are still reported at the lines of the file as it is.
+    let filtered = FilteredInput::standard(&input);
+    let mut progress = Progress::new(filtered.text().len());
+    let mut errors = Vec::new();
+    {
+        let mut parser = match Parser::new(Tokenizer::new(filtered.text())) {
+            Ok(parser) => parser,
+            Err(e) => {
+                eprintln!("Error at line 1: {}", e);
+                return false;
+            },
+        };
+        parser.set_observer(&mut progress);
+
+        while !parser.is_at_end() {
+            if let Err(e) = parser.parse_statement() {
+                // The parser stops at the token it could not make sense of
+                let error = parser.locate(e);
+                errors.push(ParseError { span: filtered.original_span(error.span), ..error });
+                parser.skip_statement();
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    progress.draw(filtered.text().len(), true);
+    eprintln!();
+
+    for error in &errors {
+        println!("{}\n", error.render(&input));
+    }
+    println!("\nParsed {} statements in {:.3} s", progress.statements, elapsed.as_secs_f64());
+    for (kind, count) in &progress.by_kind {
+        println!("  {:<14}{}", kind, count);
+    }
+    println!("Errors: {}", errors.len());
+    let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "Throughput: {:.2} MB/s, {:.0} statements/s",
+        input.len() as f64 / 1_000_000.0 / seconds,
+        progress.statements as f64 / seconds,
+    );
+    errors.is_empty()
+}
+
+// Prints a summary of the schema defined by a dump: the tables with their
+// columns and constraints, and the views. Statements that can not be parsed or
+// do not fit the schema are skipped with a warning on stderr, so that one
+// unsupported statement does not hide the rest of the dump.
+// Returns whether the summary was printed.
+fn print_schema(path: &str, format: &str) -> bool {
+    if format != "markdown" && (format != "json" || cfg!(not(feature = "serde"))) {
+        let formats = if cfg!(feature = "serde") { "json, markdown" } else { "markdown" };
+        eprintln!("Error: unknown format {}. Available formats: {}", format, formats);
+        return false;
+    }
+    let input = match std::fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: can not read {}: {}", path, e);
+            return false;
+        },
+    };
+
+    let filtered = FilteredInput::standard(&input);
+    let mut catalog = Catalog::new();
+    let mut parser = match Parser::new(Tokenizer::new(filtered.text())) {
+        Ok(parser) => parser,
+        Err(e) => {
+            eprintln!("Error at line 1: {}", e);
+            return false;
+        },
+    };
+    while !parser.is_at_end() {
+        let line = line_of(&input, filtered.original_offset(parser.offset()));
+        match parser.parse_statement() {
+            Ok(statement) => {
+                if let Err(e) = catalog.add_statement(&statement) {
+                    eprintln!("Warning: skipped the statement at line {}: {}", line, e);
+                }
+            },
+            Err(e) => {
+                eprintln!("Warning: skipped the statement at line {}: {}", line, e);
+                parser.skip_statement();
+            },
+        }
+    }
+    for warning in parser.warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+
+    #[cfg(feature = "serde")]
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&docs::json(&catalog)).unwrap());
+        return true;
+    }
+    print!("{}", docs::markdown(&catalog));
+    true
+}
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // `schema <file> [--format json|markdown]` summarizes the schema of a dump
+    if args.first().map(String::as_str) == Some("schema") {
+        let (path, format) = match &args[1..] {
+            [path] => (path, "markdown"),
+            [path, flag, format] if flag == "--format" => (path, format.as_str()),
+            _ => {
+                eprintln!("Usage: schema <file> [--format json|markdown]");
+                std::process::exit(2);
+            },
+        };
+        std::process::exit(if print_schema(path, format) { 0 } else { 1 });
+    }
+    // Parse a whole file when a path is given, otherwise start the interactive mode
+    if let Some(path) = args.first() {
+        let success = parse_file(path);
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
     println!("SQL Parser CLI");
     println!("Type SQL queries to parse or 'exit' to quit.");
+    println!("Use :undo and :redo to step through the schema changes, :log to list them.");
+    println!("Use :query <path> to find nodes of the last statement, like :query select.where.**.Identifier");
     println!("-------------------------------------------");
-    
+
+    let mut session = Session::new();
+    let mut last = None;
+
     loop {
         print!("> ");
         io::stdout().flush().unwrap();
-        
+
         let mut input = String::new();
         io::stdin().read_line(&mut input).unwrap();
-        
+
         let input = input.trim();
-        
+
         if input.eq_ignore_ascii_case("exit") || input.eq_ignore_ascii_case("quit") {
             println!("Exiting...");
             break;
         }
-        
+
         if input.is_empty() {
             continue;
         }
+        if input.starts_with(':') {
+            run_command(&mut session, last.as_ref(), input);
+            continue;
+        }
         println!("\nParsed Statement:");
-        match build_statement(input) {
-            Ok(statement) => println!("{:#?}", statement),
-            Err(e) => println!("Error: {}", e),
+        match build_located_statement(input) {
+            Ok(statement) => {
+                println!("{:#?}", statement);
+                if let Err(e) = session.execute(statement.clone()) {
+                    println!("Error: {}", e);
+                }
+                last = Some(statement);
+            },
+            Err(e) => println!("{}", e.render(input)),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,255 @@
+use std::fmt::{Display, Formatter};
+
+/// The kinds of names the parser expects in various places, used by `Message::ExpectedName`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NameKind {
+    Table,
+    View,
+    Column,
+    CommonTableExpression,
+    Alias,
+    TableOption,
+    Constraint,
+}
+
+/// Every diagnostic that the tokenizer, the parser and the analysis passes can report to the user. A message only carries the data that differs between occurrences (SQL keywords, names, offending tokens), while the actual text is produced by a message catalog (see `Messages`). The `Display` implementation of this enum is the built-in English catalog.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Message {
+    // Tokenizer
+    MismatchedQuotes { opening: char, found: char },
+    UnterminatedString { quote: char },
+    UnterminatedQuotedIdentifier,
+    InvalidBitString { value: String },
+    UnreadableInput { reason: String },
+    InvalidToken { found: String },
+    UnsupportedSyntax { syntax: String, dialect: String },
+    // Parser
+    EmptyInput,
+    UnexpectedEndOfInput,
+    UnexpectedEndOfColumnDefinition,
+    UnexpectedEndOfTypeDefinition,
+    DidYouMean { word: String },
+    UnexpectedPrefixToken { found: String },
+    UnexpectedInfixToken { found: String },
+    UnexpectedColumnDefinitionToken { found: String },
+    ExpectedDataType { found: String },
+    ExpectedClosingParenthesis,
+    ExpectedOneOf { expected: Vec<String>, found: String },
+    ExpectedFrom,
+    ExpectedAfter { expected: Vec<String>, after: String },
+    ExpectedName { kind: NameKind, after: Option<String> },
+    ExpectedQueryParenthesis { name: String },
+    UnclosedQuery { name: String },
+    UnclosedColumnDefinitions,
+    UnclosedCheck,
+    UnclosedCast,
+    ExpectedVarcharLength,
+    UnclosedVarcharLength,
+    UnclosedSubquery,
+    MissingDerivedTableAlias,
+    UnclosedJoin,
+    WriteRejected { statement: String },
+    UnclosedFunctionArguments { name: String },
+    UnclosedWindowSpecification,
+    UnclosedKeyColumns,
+    UnclosedTableOptions,
+    UnclosedPartitionKey,
+    DuplicateLimit,
+    ExpectedCommentText,
+    DistinctWildcard { name: String },
+    InvalidEscapeCharacter,
+    NumberOutOfRange { number: u64 },
+    NestingTooDeep { limit: usize },
+    InvalidPlaceholder { placeholder: String },
+    WrongArgumentCount { name: String, expected: String, found: usize },
+    InvalidTemporalLiteral { kind: String, value: String },
+    InvalidDecimalScale { precision: usize, scale: usize },
+    UnclosedInList,
+    UnclosedInsertColumns,
+    UnclosedValues,
+    ReplaceWithIfNotExists,
+    // Warnings about the parts of MySQL dumps that are skipped
+    IgnoredUnsigned { column: String },
+    AutoIncrementNotInteger { column: String },
+    ConflictingNullability { column: String },
+    IgnoredIndex { name: Option<String>, table: String },
+    // Analysis passes
+    TableAlreadyExists { name: String },
+    ViewAlreadyExists { name: String },
+    UnknownTable { name: String },
+    UnknownView { name: String },
+    UnknownColumn { name: String },
+    AmbiguousColumn { name: String, tables: Vec<String> },
+    DuplicateTableInFrom { name: String },
+    ExpectedQuery,
+    RecursiveView { name: String },
+    InvalidPath { path: String },
+    InsertValueCount { expected: usize, found: usize },
+    // Migration checks
+    MissingIfNotExists { statement: String, name: String },
+    MissingIfExists { statement: String, name: String },
+    DuplicateDefinition { kind: NameKind, name: String },
+    ConflictingDefinition { kind: NameKind, name: String },
+    // Serialized statements
+    MissingSchemaVersion,
+    UnsupportedSchemaVersion { version: u64 },
+    InvalidAstJson { reason: String },
+}
+
+/// A message catalog turns messages into the text shown to the user. Embedders implement this trait to localize or rebrand the diagnostics, typically by matching on the messages they want to change and falling back to the default (English) text for the rest:
+/// ```rust
+/// struct Lithuanian;
+///
+/// impl Messages for Lithuanian {
+///     fn render(&self, message: &Message) -> String {
+///         match message {
+///             Message::EmptyInput => "Tuščia užklausa".to_string(),
+///             _ => message.to_string(),
+///         }
+///     }
+/// }
+/// ```
+/// The catalog is given to the parser with `Parser::with_messages`, and to the tokenizer with `Tokenizer::with_messages`. Analysis passes return `Message` values directly, so they can be rendered with any catalog.
+pub trait Messages {
+    fn render(&self, message: &Message) -> String {
+        message.to_string()
+    }
+}
+
+/// The default message catalog.
+pub struct EnglishMessages;
+
+impl Messages for EnglishMessages {}
+
+// Joins alternatives as "A", "A or B", "A, B or C"
+fn alternatives(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} or {}", init.join(", "), last),
+    }
+}
+
+impl Display for NameKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameKind::Table => write!(f, "table"),
+            NameKind::View => write!(f, "view"),
+            NameKind::Column => write!(f, "column"),
+            NameKind::CommonTableExpression => write!(f, "common table expression"),
+            NameKind::Alias => write!(f, "alias"),
+            NameKind::TableOption => write!(f, "table option"),
+            NameKind::Constraint => write!(f, "constraint"),
+        }
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::MismatchedQuotes { opening, found } => {
+                write!(f, "Mismatched quotes: string started with {} but found {}", opening, found)
+            }
+            Message::UnterminatedString { quote } => write!(f, "Unterminated string starting with {}", quote),
+            Message::UnterminatedQuotedIdentifier => write!(f, "Unterminated identifier starting with `"),
+            Message::InvalidBitString { value } => write!(f, "Invalid bit string B'{}', it can only contain 0 and 1", value),
+            Message::UnreadableInput { reason } => write!(f, "Can not read the input: {}", reason),
+            Message::InvalidToken { found } => write!(f, "Invalid token {}", found),
+            Message::UnsupportedSyntax { syntax, dialect } => write!(f, "{} is not supported in {}", syntax, dialect),
+            Message::EmptyInput => write!(f, "Empty input"),
+            Message::UnexpectedEndOfInput => write!(f, "Unexpected end of input"),
+            Message::UnexpectedEndOfColumnDefinition => write!(f, "Unexpected end of input in column definition"),
+            Message::UnexpectedEndOfTypeDefinition => write!(f, "Unexpected end of input in type definition"),
+            Message::DidYouMean { word } => write!(f, "Did you mean {}?", word),
+            Message::UnexpectedPrefixToken { found } => write!(f, "Unexpected token in prefix position: {}", found),
+            Message::UnexpectedInfixToken { found } => write!(f, "Unexpected token in infix position: {}", found),
+            Message::UnexpectedColumnDefinitionToken { found } => {
+                write!(f, "Unexpected token in column definition: {}", found)
+            }
+            Message::ExpectedDataType { found } => write!(f, "Expected data type, got {}", found),
+            Message::ExpectedClosingParenthesis => write!(f, "Expected closing parenthesis"),
+            Message::ExpectedOneOf { expected, found } => write!(f, "Expected {}, found {}", alternatives(expected), found),
+            Message::ExpectedFrom => write!(f, "Expected FROM clause in SELECT statement"),
+            Message::ExpectedAfter { expected, after } => {
+                write!(f, "Expected {} after {}", alternatives(expected), after)
+            }
+            Message::ExpectedName { kind, after: Some(after) } => write!(f, "Expected {} name after {}", kind, after),
+            Message::ExpectedName { kind, after: None } => write!(f, "Expected {} name", kind),
+            Message::ExpectedQueryParenthesis { name } => write!(f, "Expected ( before the query of {}", name),
+            Message::UnclosedQuery { name } => write!(f, "Expected ) after the query of {}", name),
+            Message::UnclosedColumnDefinitions => write!(f, "Expected ) after column definitions"),
+            Message::UnclosedCheck => write!(f, "Expected ) after CHECK expression"),
+            Message::UnclosedCast => write!(f, "Expected ) after the type of CAST"),
+            Message::ExpectedVarcharLength => write!(f, "Expected number for VARCHAR length"),
+            Message::UnclosedVarcharLength => write!(f, "Expected ) after VARCHAR length"),
+            Message::UnclosedSubquery => write!(f, "Expected ) after subquery"),
+            Message::MissingDerivedTableAlias => write!(f, "Expected alias after derived table"),
+            Message::UnclosedJoin => write!(f, "Expected ) after the joined tables"),
+            Message::UnclosedFunctionArguments { name } => write!(f, "Expected ) after the arguments of {}", name),
+            Message::UnclosedWindowSpecification => write!(f, "Expected ) after window specification"),
+            Message::UnclosedKeyColumns => write!(f, "Expected ) after the columns of the key"),
+            Message::UnclosedTableOptions => write!(f, "Expected ) after table options"),
+            Message::UnclosedPartitionKey => write!(f, "Expected ) after the partition key"),
+            Message::ExpectedCommentText => write!(f, "Expected string or NULL as the text of the comment"),
+            Message::DistinctWildcard { name } => write!(f, "{} can not count DISTINCT *, name the columns instead", name),
+            Message::InvalidEscapeCharacter => write!(f, "Expected a string of a single character after ESCAPE"),
+            Message::InvalidPlaceholder { placeholder } => {
+                write!(f, "Invalid placeholder {}, numbered placeholders start at $1", placeholder)
+            },
+            Message::WrongArgumentCount { name, expected, found } => {
+                write!(f, "{} takes {}, got {}", name, expected, found)
+            },
+            Message::InvalidTemporalLiteral { kind, value } => write!(f, "'{}' is not a valid {} literal", value, kind),
+            Message::InvalidDecimalScale { precision, scale } => write!(f, "The scale {} of DECIMAL is bigger than its precision {}", scale, precision),
+            Message::NestingTooDeep { limit } => write!(f, "Expressions and queries can not be nested more than {} levels deep", limit),
+            Message::NumberOutOfRange { number } => write!(f, "The number {} is too big, the largest supported number is {}", number, i64::MAX),
+            Message::UnclosedInList => write!(f, "Expected ) after the values of IN"),
+            Message::DuplicateLimit => write!(f, "A query can not have both TOP and LIMIT"),
+            Message::ReplaceWithIfNotExists => write!(f, "A view can not be created with both OR REPLACE and IF NOT EXISTS"),
+            Message::UnclosedInsertColumns => write!(f, "Expected ) after the columns of INSERT"),
+            Message::UnclosedValues => write!(f, "Expected ) after the values of a row"),
+            Message::IgnoredUnsigned { column } => write!(f, "Ignored UNSIGNED modifier of column {}", column),
+            Message::AutoIncrementNotInteger { column } => write!(f, "Only integer columns can be AUTO_INCREMENT, but column {} is not one", column),
+            Message::ConflictingNullability { column } => write!(f, "Column {} is declared both NULL and NOT NULL", column),
+            Message::IgnoredIndex { name: Some(name), table } => write!(f, "Ignored index {} of table {}", name, table),
+            Message::IgnoredIndex { name: None, table } => write!(f, "Ignored an index of table {}", table),
+            Message::WriteRejected { statement } => write!(f, "{} statements are not allowed in read-only mode", statement),
+            Message::TableAlreadyExists { name } => write!(f, "Table {} already exists", name),
+            Message::ViewAlreadyExists { name } => write!(f, "View {} already exists", name),
+            Message::UnknownTable { name } => write!(f, "Unknown table {}", name),
+            Message::UnknownView { name } => write!(f, "Unknown view {}", name),
+            Message::UnknownColumn { name } => write!(f, "Unknown column {}", name),
+            Message::AmbiguousColumn { name, tables } => {
+                write!(f, "Ambiguous column reference {}: present in {}", name, tables.join(", "))
+            }
+            Message::DuplicateTableInFrom { name } => write!(f, "Table {} appears more than once in FROM", name),
+            Message::ExpectedQuery => write!(f, "Expected a SELECT query"),
+            Message::RecursiveView { name } => write!(f, "View {} is defined recursively", name),
+            Message::InvalidPath { path } => write!(f, "Invalid path {}: every step between the dots needs a name, * or **", path),
+            Message::InsertValueCount { expected, found } => {
+                write!(f, "INSERT has {} columns but a row of {} values", expected, found)
+            }
+            Message::MissingIfNotExists { statement, name } => {
+                write!(f, "{} {} has no IF NOT EXISTS guard, so running it again fails", statement, name)
+            }
+            Message::MissingIfExists { statement, name } => {
+                write!(f, "{} {} has no IF EXISTS guard, so running it again fails", statement, name)
+            }
+            Message::DuplicateDefinition { kind, name } => write!(f, "The {} {} is created again with the same definition", kind, name),
+            Message::ConflictingDefinition { kind, name } => {
+                write!(f, "The {} {} is created again with a different definition", kind, name)
+            }
+            Message::MissingSchemaVersion => write!(f, "Serialized statement has no schema version"),
+            Message::UnsupportedSchemaVersion { version } => write!(f, "Unsupported schema version {} of a serialized statement", version),
+            Message::InvalidAstJson { reason } => write!(f, "Invalid serialized statement: {}", reason),
+        }
+    }
+}
+
+// Renders the message with the default catalog, which lets `?` mix analysis
+// passes with the parser in functions returning `Result<_, String>`
+impl From<Message> for String {
+    fn from(message: Message) -> Self {
+        message.to_string()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::messages::{Message, NameKind};
+use crate::statement::{ObjectName, Statement};
+
+// The kind and the name of the object a statement creates, and its definition
+// without the parts that do not change the created object: the case of the
+// name and the IF NOT EXISTS and OR REPLACE guards
+fn definition(statement: &Statement) -> Option<(NameKind, String, Statement)> {
+    let mut definition = statement.clone();
+    match &mut definition {
+        Statement::CreateTable { table_name, if_not_exists, .. } => {
+            *if_not_exists = false;
+            let name = table_name.to_string();
+            *table_name = lowercase(table_name);
+            Some((NameKind::Table, name, definition))
+        },
+        Statement::CreateView { name, or_replace, if_not_exists, .. } => {
+            *or_replace = false;
+            *if_not_exists = false;
+            let original = name.to_string();
+            *name = lowercase(name);
+            Some((NameKind::View, original, definition))
+        },
+        _ => None,
+    }
+}
+
+fn lowercase(name: &ObjectName) -> ObjectName {
+    ObjectName(name.0.iter().map(|part| part.to_lowercase()).collect())
+}
+
+// Checks that a migration script can be run more than once, on top of the
+// scripts that were already applied to the database. Reports every `CREATE`
+// of the migration that is not guarded with IF NOT EXISTS, and every object
+// that the migration creates again after the applied scripts or the migration
+// itself already created it, telling whether the definitions are the same
+// (the statement is redundant) or different (with IF NOT EXISTS, the new
+// definition is silently ignored). A view created with OR REPLACE needs no
+// guard, and its definition replaces the earlier one. Likewise every `DROP TABLE` without IF
+// EXISTS is reported, and a dropped table can be created again without a
+// finding. Indexes are not part of the schema model, so only tables and views
+// are checked.
+pub fn check_migration(applied: &[Statement], migration: &[Statement]) -> Vec<Message> {
+    // The first definition of an object is the one that stays in effect
+    let mut defined: Vec<Statement> = Vec::new();
+    for statement in applied {
+        if let Statement::DropTable { table_name, .. } = statement {
+            forget(&mut defined, table_name);
+        }
+        if let Some((_, _, definition)) = definition(statement) {
+            if matches!(statement, Statement::CreateView { or_replace: true, .. }) {
+                replace(&mut defined, definition);
+            } else if !defined.iter().any(|known| same_name(known, &definition)) {
+                defined.push(definition);
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    for statement in migration {
+        if let Statement::DropTable { table_name, if_exists } = statement {
+            if !if_exists {
+                findings.push(Message::MissingIfExists { statement: statement.kind().to_string(), name: table_name.to_string() });
+            }
+            forget(&mut defined, table_name);
+            continue;
+        }
+        let Some((kind, name, definition)) = definition(statement) else {
+            continue;
+        };
+        if matches!(statement, Statement::CreateView { or_replace: true, .. }) {
+            replace(&mut defined, definition);
+            continue;
+        }
+        if matches!(
+            statement,
+            Statement::CreateTable { if_not_exists: false, .. } | Statement::CreateView { if_not_exists: false, .. }
+        ) {
+            findings.push(Message::MissingIfNotExists { statement: statement.kind().to_string(), name: name.clone() });
+        }
+        match defined.iter().find(|known| same_name(known, &definition)) {
+            Some(known) if *known == definition => findings.push(Message::DuplicateDefinition { kind, name }),
+            Some(_) => findings.push(Message::ConflictingDefinition { kind, name }),
+            None => defined.push(definition),
+        }
+    }
+    findings
+}
+
+// Checks a single script on its own, see `check_migration`
+pub fn check_script(statements: &[Statement]) -> Vec<Message> {
+    check_migration(&[], statements)
+}
+
+// Puts the definition of a replaced view in place of the earlier definition
+// with the same name, if there is one
+fn replace(defined: &mut Vec<Statement>, definition: Statement) {
+    match defined.iter_mut().find(|known| same_name(known, &definition)) {
+        Some(known) => *known = definition,
+        None => defined.push(definition),
+    }
+}
+
+// Removes the definition of a dropped table. Only tables can be dropped, so a
+// view of the same name stays.
+fn forget(defined: &mut Vec<Statement>, table: &ObjectName) {
+    let table = lowercase(table);
+    defined.retain(|known| !matches!(known, Statement::CreateTable { table_name, .. } if *table_name == table));
+}
+
+// Tables and views share one namespace, so a view can clash with a table
+fn same_name(left: &Statement, right: &Statement) -> bool {
+    name(left) == name(right)
+}
+
+fn name(definition: &Statement) -> Option<&ObjectName> {
+    match definition {
+        Statement::CreateTable { table_name, .. } => Some(table_name),
+        Statement::CreateView { name, .. } => Some(name),
+        _ => None,
+    }
+}
@@ -0,0 +1,33 @@
+use crate::statement::{Statement, TableColumn};
+use crate::token::Span;
+
+/// The clauses of a `SELECT` query that are reported to a `ParseObserver` once they are parsed.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Clause {
+    With,
+    Select,
+    From,
+    Where,
+    OrderBy,
+}
+
+/// Receives events from the parser while it works through the input, so that a
+/// consumer can report progress or handle statements one by one instead of
+/// waiting for the whole script to be parsed. Every event carries the span of
+/// the input it covers. All methods do nothing by default.
+///
+/// Clauses are reported for nested queries (common table expressions and views)
+/// as well, while statement events are only reported for the top-level statements.
+pub trait ParseObserver {
+    // Called when the parser starts parsing a statement at the given byte offset
+    fn on_statement_start(&mut self, _start: usize) {}
+
+    // Called after a whole statement, including its semicolon, was parsed
+    fn on_statement(&mut self, _statement: &Statement, _span: Span) {}
+
+    // Called after a clause of a query was parsed
+    fn on_clause(&mut self, _clause: Clause, _span: Span) {}
+
+    // Called after a column definition of CREATE TABLE was parsed
+    fn on_column_definition(&mut self, _column: &TableColumn, _span: Span) {}
+}
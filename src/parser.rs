@@ -1,35 +1,280 @@
-use crate::statement::{BinaryOperator, Constraint, DBType, Expression, Statement, TableColumn, UnaryOperator};
-use crate::token::{Keyword, Token};
+use crate::statement::{
+    BinaryOperator, CommonTableExpression, Constraint, DBType, Expression, JoinType, Statement,
+    TableColumn, TableConstraint, TableReference, UnaryOperator, WindowSpecification, LockingClause, LockStrength,
+    WaitPolicy, Partitioning, PartitionMethod, CommentTarget, InsertSource, ReturningItem, ObjectName, Placeholder, TemporalKind, Quantifier, DateField,
+};
+use crate::dialects::Dialect;
+use crate::messages::{EnglishMessages, Message, Messages, NameKind};
+use crate::extension::StatementHandler;
+use crate::observer::{Clause, ParseObserver};
+use crate::token::{Keyword, Position, Span, Token};
 use crate::tokenizer::Tokenizer;
-use std::iter::Peekable;
+
+/// Settings that change which statements the parser accepts.
+/// * `reject_writes` – Refuse every statement that would change the database (like `CREATE TABLE`), for building query sandboxes that should only ever read.
+/// * `dialect` – The flavor of SQL to accept besides the generic one, see `Dialect`.
+/// * `optional_final_semicolon` – Let the last statement of the input go without its semicolon, like `SELECT 1`, for queries that come one at a time from a program rather than from a script. Every other statement still has to end with one.
+/// * `max_depth` – How deep expressions can be nested (in parentheses, subqueries, operands of operators) before the parser gives up with an error, so that input like a hundred thousand opening parentheses can not overflow the stack. Operators and parentheses are nested without using up the call stack of the parser, so the limit can be raised a lot for them, as far as the code that walks the statements can go; subqueries still take the call stack. `DEFAULT_MAX_DEPTH` by default.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParserOptions {
+    pub reject_writes: bool,
+    pub dialect: Dialect,
+    pub optional_final_semicolon: bool,
+    pub max_depth: usize,
+}
+
+// Deeper than any query written by hand, while a parser in a debug build still
+// fits into the 2 MB stack of a spawned thread
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            reject_writes: false,
+            dialect: Dialect::default(),
+            optional_final_semicolon: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+/// An error of the parser together with the span of the input it was found at,
+/// see `Parser::locate`. `render` shows the error the way a compiler does, with
+/// the line of the input it is on and the span underlined.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |newline| start + newline);
+        let line = source[..start].matches('\n').count() + 1;
+        let line_text = source[line_start..line_end].trim_end_matches('\r');
+        let position = Position {
+            offset: start,
+            line,
+            column: source[line_start..start].chars().count() + 1,
+        };
+        
+        // Tabs are kept in front of the underline, so that it lines up with the
+        // text however wide the tabs are shown
+        let padding: String = source[line_start..start].chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+        let end = self.span.end.clamp(start, line_start + line_text.len());
+        let width = source[start..end].chars().count().max(1);
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "Error at {}: {}\n{} |\n{} | {}\n{} | {}{}",
+            position, self.message, gutter, line, line_text, gutter, padding, "^".repeat(width)
+        )
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
 
 pub struct Parser<'a> {
-    tokenizer: Peekable<Tokenizer<'a>>,
+    tokenizer: Tokenizer<'a>,
     current_token: Option<Token>,
+    current_span: Span,
+    previous_end: usize, // Byte offset where the previous token ended
+    messages: &'a dyn Messages,
+    observer: Option<&'a mut dyn ParseObserver>,
+    handler: Option<&'a mut dyn StatementHandler>,
+    options: ParserOptions,
+    warnings: Vec<String>,
+    depth: usize, // How many expressions are being parsed, one inside of another
+    recovering: bool, // Whether errors in lists are recorded and skipped, see `parse_all_with_errors`
+    errors: Vec<ParseError>,
+    expected: Vec<String>, // What the parser looked for at the current token, see `unexpected`
+}
+
+// An operator of `parse_operations` that waits for its operand, with the
+// precedence of the expression it is an operand of itself
+enum Pending {
+    Unary { operator: UnaryOperator, precedence: u8 },
+    Binary { left: Expression, operator: BinaryOperator, precedence: u8 },
+    // The elements before the operand are only there once a comma made the
+    // parentheses a tuple
+    Parentheses { elements: Vec<Expression>, precedence: u8 },
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokenizer: Tokenizer<'a>) -> Result<Self, String> {
-        let mut tokenizer = tokenizer.peekable();
-        let current_token = match tokenizer.next() {
-            Some(Ok(token)) => Some(token),
-            Some(Err(e)) => return Err(e),
-            None => None,
-        };
+        Self::with_messages(tokenizer, &EnglishMessages)
+    }
+    
+    // Creates a parser with the given options. The dialect is handed to the
+    // tokenizer before the first token is read, so that the input can start
+    // with what only the dialect reads, like a `#` comment of MySQL.
+    pub fn with_options(mut tokenizer: Tokenizer<'a>, options: ParserOptions) -> Result<Self, String> {
+        tokenizer.set_dialect(options.dialect);
+        let mut parser = Self::new(tokenizer)?;
+        parser.options = options;
+        Ok(parser)
+    }
+    
+    // Creates a parser that reports errors using the given message catalog.
+    // The catalog is used by the tokenizer as well.
+    pub fn with_messages(mut tokenizer: Tokenizer<'a>, messages: &'a dyn Messages) -> Result<Self, String> {
+        tokenizer.set_messages(messages);
+        let current_token = Self::next_token(&mut tokenizer, messages)?;
         
         Ok(Self {
+            current_span: tokenizer.span(),
             tokenizer,
             current_token,
+            previous_end: 0,
+            messages,
+            observer: None,
+            handler: None,
+            options: ParserOptions::default(),
+            warnings: Vec::new(),
+            depth: 0,
+            recovering: false,
+            errors: Vec::new(),
+            expected: Vec::new(),
         })
     }
     
+    // Changes the options from the next token on. The token the parser is at
+    // was read when it was created, so an input that the dialect reads
+    // differently from its very start is parsed with `with_options` instead.
+    pub fn set_options(&mut self, options: ParserOptions) {
+        self.options = options;
+        self.tokenizer.set_dialect(options.dialect);
+    }
+    
+    // Registers an observer that is notified about the statements, clauses and
+    // column definitions as soon as they are parsed
+    pub fn set_observer(&mut self, observer: &'a mut dyn ParseObserver) {
+        self.observer = Some(observer);
+    }
+    
+    // Registers a handler for the statements that start with words the parser
+    // does not know, see `StatementHandler`
+    pub fn set_statement_handler(&mut self, handler: &'a mut dyn StatementHandler) {
+        self.handler = Some(handler);
+    }
+    
+    fn notify(&mut self, event: impl FnOnce(&mut dyn ParseObserver)) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            event(observer);
+        }
+    }
+    
+    // The span from the given offset up to the end of the last consumed token
+    fn span_from(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.previous_end,
+        }
+    }
+    
+    // Renders an error message with the message catalog of the parser
+    fn error(&self, message: Message) -> String {
+        let error = self.messages.render(&message);
+        match self.suggest_keyword(&message) {
+            Some(word) => format!("{}. {}", error, self.messages.render(&Message::DidYouMean { word })),
+            None => error,
+        }
+    }
+    
+    // Records what the parser looked for at the current token and did not
+    // find, like the optional clauses after the columns of a query, so that
+    // the parser can list all of them when nothing it knows follows
+    fn expect(&mut self, alternatives: &[&str]) {
+        for alternative in alternatives {
+            if !self.expected.iter().any(|expected| expected == alternative) {
+                self.expected.push(alternative.to_string());
+            }
+        }
+    }
+    
+    // The error for a token that none of the expected alternatives start
+    fn unexpected(&self) -> String {
+        let found = match &self.current_token {
+            Some(Token::Eof) | None => "end of input".to_string(),
+            Some(token) => format!("{:?}", token),
+        };
+        self.error(Message::ExpectedOneOf { expected: self.expected.clone(), found })
+    }
+    
+    // A keyword that the identifier the parser failed at is likely a typo of,
+    // like SELECT for `SELEC`. The keywords that the error expects are tried,
+    // or all of them when the error does not say.
+    fn suggest_keyword(&self, message: &Message) -> Option<String> {
+        let Some(Token::Identifier(identifier)) = &self.current_token else {
+            return None;
+        };
+        let identifier = identifier.to_uppercase();
+        let candidates: Vec<&str> = match message {
+            Message::ExpectedFrom => vec!["FROM"],
+            Message::ExpectedAfter { expected, .. } | Message::ExpectedOneOf { expected, .. } => expected
+                .iter()
+                .filter_map(|expected| expected.split(' ').next())
+                .filter(|word| word.chars().all(|c| c.is_ascii_uppercase()))
+                .collect(),
+            _ => self.tokenizer.keywords().words().collect(),
+        };
+        // One typo in every three letters is still recognizable. Of the words
+        // that are as close, the one of the same length is likely the meant one.
+        let length = identifier.chars().count();
+        let allowed = (length / 3).max(1);
+        candidates
+            .into_iter()
+            .map(|word| (edit_distance(&identifier, word), word.len().abs_diff(length), word))
+            .filter(|(distance, _, _)| *distance <= allowed)
+            .min()
+            .map(|(_, _, word)| word.to_string())
+    }
+    
+    // Records a warning about a part of the input that was skipped
+    fn warn(&mut self, message: Message) {
+        let warning = self.messages.render(&message);
+        self.warnings.push(warning);
+    }
+    
+    // The warnings about the parts of the input that were parsed, but do not
+    // show up in the statements, in the order they were found
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+    
+    // Whether the current token is the given word, which is only a keyword in
+    // some dialects, so the tokenizer leaves it as an identifier
+    fn at_word(&self, word: &str) -> bool {
+        matches!(&self.current_token, Some(Token::Identifier(identifier)) if identifier.eq_ignore_ascii_case(word))
+    }
+    
     fn advance_token(&mut self) -> Result<(), String> {
-        self.current_token = match self.tokenizer.next() {
+        self.previous_end = self.current_span.end;
+        self.expected.clear();
+        self.current_token = Self::next_token(&mut self.tokenizer, self.messages)?;
+        self.current_span = self.tokenizer.span();
+        Ok(())
+    }
+    
+    // Reads the next token. Invalid text can not be a part of any statement,
+    // so it is reported as soon as it is found.
+    fn next_token(tokenizer: &mut Tokenizer<'a>, messages: &dyn Messages) -> Result<Option<Token>, String> {
+        let token = match tokenizer.next() {
+            Some(Ok(Token::Invalid { text, .. })) => return Err(messages.render(&Message::InvalidToken { found: text })),
             Some(Ok(token)) => Some(token),
             Some(Err(e)) => return Err(e),
             None => None,
         };
-        Ok(())
+        #[cfg(feature = "trace")]
+        if let Some(token) = &token {
+            crate::trace::record_token(token, tokenizer.span());
+        }
+        Ok(token)
     }
     
     // Gets the precedence of the current token if it's a binary operator
@@ -44,10 +289,31 @@ impl<'a> Parser<'a> {
                 // Comparisons
                 Token::Equal | Token::NotEqual |
                 Token::GreaterThan | Token::GreaterThanOrEqual |
-                Token::LessThan | Token::LessThanOrEqual => 4,
+                Token::LessThan | Token::LessThanOrEqual |
+                Token::Keyword(Keyword::In) | Token::Keyword(Keyword::Like) |
+                Token::Keyword(Keyword::Is) => 4,
+                // NOT in the middle of an expression can only start NOT LIKE,
+                // NOT IN, NOT SIMILAR TO or NOT REGEXP
+                Token::Keyword(Keyword::Not) => 4,
+                // Pattern matching operators, which depend on the dialect
+                Token::Identifier(word) if word.eq_ignore_ascii_case("SIMILAR") => 4,
+                Token::Identifier(word) if self.options.dialect == Dialect::MySql && word.eq_ignore_ascii_case("REGEXP") => 4,
+                Token::Tilde | Token::NotTilde if self.options.dialect == Dialect::Postgres => 4,
+                // Bitwise operators bind looser than arithmetic, so that
+                // `flags & 4 != 0` compares the result of the mask. They come
+                // in the order of C: |, then ^, then &, then the shifts.
+                // In PostgreSQL ^ is the power instead, which binds tighter than
+                // any other arithmetic.
+                Token::Pipe => 5,
+                Token::Caret if self.options.dialect == Dialect::Postgres => 11,
+                Token::Caret => 6,
+                Token::Ampersand => 7,
+                Token::ShiftLeft | Token::ShiftRight => 8,
                 // Arithmetic
-                Token::Plus | Token::Minus => 5,
-                Token::Star | Token::Divide => 6,
+                Token::Plus | Token::Minus => 9,
+                Token::Star | Token::Divide | Token::Percent => 10,
+                // The Postgres-style cast binds tighter than any other operator
+                Token::DoubleColon => 12,
                 _ => 0, // Default: not an infix operator or end of expression group
             }
         } else {
@@ -55,16 +321,69 @@ impl<'a> Parser<'a> {
         }
     }
     
-    // Parses a prefix expression (unary operations or primary expressions)
+    // Whether the operator at the current token groups from the right, like
+    // the power in `2 ^ 3 ^ 2`, which is `2 ^ (3 ^ 2)`. Every other operator
+    // groups from the left.
+    fn is_right_associative(&self) -> bool {
+        self.options.dialect == Dialect::Postgres && matches!(self.current_token, Some(Token::Caret))
+    }
+    
+    // Parses a primary expression, like a literal, a column or a function
+    // call. The unary operators and parentheses around it are parsed by
+    // `parse_operations`.
     fn parse_prefix(&mut self) -> Result<Expression, String> {
         if let Some(token) = &self.current_token {
             match token {
                 Token::Number(n) => {
+                    cover!("prefix.number");
+                    let number = self.number_literal(*n)?;
+                    self.advance_token()?;
+                    Ok(number)
+                },
+                Token::Float(n) => {
+                    cover!("prefix.float");
                     let value = *n;
                     self.advance_token()?;
-                    Ok(Expression::Number(value))
+                    Ok(Expression::Float(value))
+                },
+                Token::BitString(bits) => {
+                    cover!("prefix.bit_string");
+                    let bits = bits.clone();
+                    self.advance_token()?;
+                    Ok(Expression::BitString(bits))
+                },
+                Token::Placeholder(text) => {
+                    let placeholder = match text.strip_prefix('$') {
+                        None if text == "?" => {
+                            cover!("prefix.placeholder");
+                            Placeholder::Anonymous
+                        },
+                        // The prefix of a named parameter, : or @, is one character
+                        None => {
+                            cover!("prefix.named_placeholder");
+                            Placeholder::Named(text[1..].to_string())
+                        },
+                        Some(number) => {
+                            cover!("prefix.numbered_placeholder");
+                            match number.parse::<u64>() {
+                                Ok(number) if number > 0 => Placeholder::Numbered(number),
+                                _ => return Err(self.error(Message::InvalidPlaceholder { placeholder: text.clone() })),
+                            }
+                        },
+                    };
+                    self.advance_token()?;
+                    Ok(Expression::Placeholder(placeholder))
                 },
                 Token::String(s) => {
+                    cover!("prefix.string");
+                    let value = s.clone();
+                    self.advance_token()?;
+                    Ok(Expression::String(value))
+                },
+                // The national character set is the only one strings have here,
+                // and the escapes of E'...' were already read by the tokenizer
+                Token::NationalString(s) | Token::EscapeString(s) => {
+                    cover!("prefix.prefixed_string");
                     let value = s.clone();
                     self.advance_token()?;
                     Ok(Expression::String(value))
@@ -72,100 +391,101 @@ impl<'a> Parser<'a> {
                 Token::Identifier(ident) => {
                     let value = ident.clone();
                     self.advance_token()?;
-                    Ok(Expression::Identifier(value))
+                    // DATE, TIME and TIMESTAMP are not reserved, as they are
+                    // common column names, so a typed literal is told apart by
+                    // the string after the word
+                    if let Some(Token::String(text)) = &self.current_token {
+                        let kind = match value.to_ascii_uppercase().as_str() {
+                            "DATE" => Some(TemporalKind::Date),
+                            "TIME" => Some(TemporalKind::Time),
+                            "TIMESTAMP" => Some(TemporalKind::Timestamp),
+                            _ => None,
+                        };
+                        if let Some(kind) = kind {
+                            cover!("prefix.temporal");
+                            if !is_valid_temporal(&kind, text) {
+                                return Err(self.error(Message::InvalidTemporalLiteral { kind: kind.to_string(), value: text.clone() }));
+                            }
+                            let value = text.clone();
+                            self.advance_token()?;
+                            return Ok(Expression::Temporal { kind, value });
+                        }
+                    }
+                    if let Some(Token::Dot) = &self.current_token {
+                        cover!("prefix.compound_identifier");
+                        self.parse_compound_identifier(value)
+                    } else if let Some(Token::LeftParentheses) = &self.current_token {
+                        if value.eq_ignore_ascii_case("EXTRACT") {
+                            cover!("prefix.extract");
+                            self.advance_token()?;
+                            return self.parse_extract();
+                        }
+                        cover!("prefix.function");
+                        self.parse_function_call(value)
+                    } else {
+                        cover!("prefix.identifier");
+                        Ok(Expression::Identifier(value))
+                    }
                 },
                 Token::Keyword(Keyword::True) => {
+                    cover!("prefix.true");
                     self.advance_token()?;
                     Ok(Expression::Bool(true))
                 },
                 Token::Keyword(Keyword::False) => {
+                    cover!("prefix.false");
                     self.advance_token()?;
                     Ok(Expression::Bool(false))
                 },
-                Token::Keyword(Keyword::Not) => {
+                Token::Keyword(Keyword::Null) => {
+                    cover!("prefix.null");
                     self.advance_token()?;
-                    let operand = self.parse_expression(6)?; // NOT has high precedence
-                    Ok(Expression::UnaryOperation {
-                        operand: Box::new(operand),
-                        operator: UnaryOperator::Not,
-                    })
+                    Ok(Expression::Null)
                 },
-                Token::Plus => {
+                Token::Keyword(Keyword::Exists) => {
+                    cover!("prefix.exists");
                     self.advance_token()?;
-                    let operand = self.parse_expression(6)?;
-                    Ok(Expression::UnaryOperation {
-                        operand: Box::new(operand),
-                        operator: UnaryOperator::Plus,
-                    })
+                    self.parse_exists(false)
                 },
-                Token::Minus => {
+                Token::Keyword(Keyword::Cast) => {
+                    cover!("prefix.cast");
                     self.advance_token()?;
-                    let operand = self.parse_expression(6)?;
-                    Ok(Expression::UnaryOperation {
-                        operand: Box::new(operand),
-                        operator: UnaryOperator::Minus,
-                    })
+                    self.parse_cast()
                 },
-                Token::LeftParentheses => {
+                // The LEFT and RIGHT of joins are also string functions in
+                // MySQL and SQL Server, like `LEFT(name, 3)`
+                Token::Keyword(keyword @ (Keyword::Left | Keyword::Right)) => {
+                    let name = if *keyword == Keyword::Left { "LEFT" } else { "RIGHT" };
                     self.advance_token()?;
-                    let expr = self.parse_expression(0)?;
-                    if let Some(Token::RightParentheses) = &self.current_token {
-                        self.advance_token()?;
-                        Ok(expr)
-                    } else {
-                        Err("Expected closing parenthesis".to_string())
+                    if !matches!(self.current_token, Some(Token::LeftParentheses)) {
+                        return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: name.to_string() }));
                     }
+                    cover!("prefix.join_keyword_function");
+                    self.parse_function_call(name.to_string())
                 },
-                _ => Err(format!("Unexpected token in prefix position: {:?}", token)),
+                _ => Err(self.error(Message::UnexpectedPrefixToken { found: format!("{:?}", token) })),
             }
         } else {
-            Err("Unexpected end of input".to_string())
+            Err(self.error(Message::UnexpectedEndOfInput))
         }
     }
     
     // Parses an infix expression (binary operations)
     fn parse_infix(&mut self, left: Expression) -> Result<Expression, String> {
+        if let Some((operator, right_precedence)) = self.binary_operator()? {
+            let right = self.parse_expression(right_precedence)?;
+            return Ok(Expression::BinaryOperation {
+                left_operand: Box::new(left),
+                operator,
+                right_operand: Box::new(right),
+            });
+        }
         if let Some(token) = &self.current_token {
             match token {
-                Token::Plus => {
-                    self.advance_token()?;
-                    let right = self.parse_expression(5)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Plus,
-                        right_operand: Box::new(right),
-                    })
-                },
-                Token::Minus => {
-                    self.advance_token()?;
-                    let right = self.parse_expression(5)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Minus,
-                        right_operand: Box::new(right),
-                    })
-                },
-                Token::Star => {
-                    self.advance_token()?;
-                    let right = self.parse_expression(6)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Multiply,
-                        right_operand: Box::new(right),
-                    })
-                },
-                Token::Divide => {
-                    self.advance_token()?;
-                    let right = self.parse_expression(6)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Divide,
-                        right_operand: Box::new(right),
-                    })
-                },
                 Token::Equal => {
+                    cover!("infix.equal");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::Equal,
@@ -173,8 +493,9 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::NotEqual => {
+                    cover!("infix.not_equal");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::NotEqual,
@@ -182,8 +503,9 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::GreaterThan => {
+                    cover!("infix.greater_than");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::GreaterThan,
@@ -191,8 +513,9 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::GreaterThanOrEqual => {
+                    cover!("infix.greater_than_or_equal");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::GreaterThanOrEqual,
@@ -200,8 +523,9 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::LessThan => {
+                    cover!("infix.less_than");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::LessThan,
@@ -209,33 +533,134 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::LessThanOrEqual => {
+                    cover!("infix.less_than_or_equal");
                     self.advance_token()?;
-                    let right = self.parse_expression(4)?;
+                    let right = self.parse_comparison_operand()?;
                     Ok(Expression::BinaryOperation {
                         left_operand: Box::new(left),
                         operator: BinaryOperator::LessThanOrEqual,
                         right_operand: Box::new(right),
                     })
                 },
-                Token::Keyword(Keyword::And) => {
+                Token::Keyword(Keyword::In) => {
                     self.advance_token()?;
-                    let right = self.parse_expression(3)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::And,
-                        right_operand: Box::new(right),
-                    })
+                    self.parse_in(left, false)
                 },
-                Token::Keyword(Keyword::Or) => {
+                Token::Keyword(Keyword::Like) => {
+                    cover!("infix.like");
                     self.advance_token()?;
-                    let right = self.parse_expression(2)?;
-                    Ok(Expression::BinaryOperation {
-                        left_operand: Box::new(left),
-                        operator: BinaryOperator::Or,
-                        right_operand: Box::new(right),
-                    })
+                    self.parse_like(left, false)
+                },
+                Token::Keyword(Keyword::Is) => {
+                    self.advance_token()?;
+                    let negated = matches!(self.current_token, Some(Token::Keyword(Keyword::Not)));
+                    if negated {
+                        self.advance_token()?;
+                    }
+                    let after = if negated { "IS NOT" } else { "IS" };
+                    match &self.current_token {
+                        Some(Token::Keyword(Keyword::Null)) => self.advance_token()?,
+                        Some(Token::Keyword(Keyword::Distinct)) => {
+                            self.advance_token()?;
+                            if let Some(Token::Keyword(Keyword::From)) = &self.current_token {
+                                self.advance_token()?;
+                            } else {
+                                return Err(self.error(Message::ExpectedAfter {
+                                    expected: vec!["FROM".to_string()],
+                                    after: format!("{} DISTINCT", after),
+                                }));
+                            }
+                            let operator = if negated {
+                                cover!("infix.is_not_distinct_from");
+                                BinaryOperator::IsNotDistinctFrom
+                            } else {
+                                cover!("infix.is_distinct_from");
+                                BinaryOperator::IsDistinctFrom
+                            };
+                            let right = self.parse_expression(4)?;
+                            return Ok(Expression::BinaryOperation {
+                                left_operand: Box::new(left),
+                                operator,
+                                right_operand: Box::new(right),
+                            });
+                        },
+                        _ => {
+                            return Err(self.error(Message::ExpectedAfter {
+                                expected: vec!["NULL".to_string(), "DISTINCT FROM".to_string()],
+                                after: after.to_string(),
+                            }));
+                        },
+                    }
+                    if negated {
+                        cover!("postfix.is_not_null");
+                    } else {
+                        cover!("postfix.is_null");
+                    }
+                    Ok(Expression::IsNull { expression: Box::new(left), negated })
+                },
+                Token::Keyword(Keyword::Not) => {
+                    self.advance_token()?;
+                    match &self.current_token {
+                        Some(Token::Keyword(Keyword::Like)) => {
+                            cover!("infix.not_like");
+                            self.advance_token()?;
+                            self.parse_like(left, true)
+                        },
+                        Some(Token::Keyword(Keyword::In)) => {
+                            self.advance_token()?;
+                            self.parse_in(left, true)
+                        },
+                        _ if self.at_word("SIMILAR") => {
+                            cover!("infix.not_similar_to");
+                            self.advance_token()?;
+                            self.parse_similar_to(left, BinaryOperator::NotSimilarTo)
+                        },
+                        _ if self.options.dialect == Dialect::MySql && self.at_word("REGEXP") => {
+                            cover!("infix.not_regexp");
+                            self.advance_token()?;
+                            self.parse_pattern_match(left, BinaryOperator::NotRegexMatch)
+                        },
+                        _ => {
+                            let mut expected = vec!["LIKE".to_string(), "IN".to_string(), "SIMILAR TO".to_string()];
+                            if self.options.dialect == Dialect::MySql {
+                                expected.push("REGEXP".to_string());
+                            }
+                            Err(self.error(Message::ExpectedAfter { expected, after: "NOT".to_string() }))
+                        },
+                    }
+                },
+                Token::Identifier(word) if word.eq_ignore_ascii_case("SIMILAR") => {
+                    cover!("infix.similar_to");
+                    self.advance_token()?;
+                    self.parse_similar_to(left, BinaryOperator::SimilarTo)
+                },
+                Token::Identifier(word) if word.eq_ignore_ascii_case("REGEXP") => {
+                    cover!("infix.regexp");
+                    self.advance_token()?;
+                    self.parse_pattern_match(left, BinaryOperator::RegexMatch)
+                },
+                Token::Tilde => {
+                    cover!("infix.regex_match");
+                    self.advance_token()?;
+                    self.parse_pattern_match(left, BinaryOperator::RegexMatch)
+                },
+                Token::NotTilde => {
+                    cover!("infix.not_regex_match");
+                    self.advance_token()?;
+                    self.parse_pattern_match(left, BinaryOperator::NotRegexMatch)
+                },
+                Token::DoubleColon => {
+                    if !self.options.dialect.has_double_colon_casts() {
+                        let dialect = self.options.dialect.to_string();
+                        return Err(self.error(Message::UnsupportedSyntax { syntax: "The :: cast".to_string(), dialect }));
+                    }
+                    cover!("postfix.cast");
+                    self.advance_token()?;
+                    let target_type = self.parse_db_type()?;
+                    Ok(Expression::Cast { expression: Box::new(left), target_type })
                 },
                 Token::Keyword(Keyword::Asc) => {
+                    cover!("postfix.asc");
                     self.advance_token()?;
                     Ok(Expression::UnaryOperation {
                         operand: Box::new(left),
@@ -243,205 +668,1703 @@ impl<'a> Parser<'a> {
                     })
                 },
                 Token::Keyword(Keyword::Desc) => {
+                    cover!("postfix.desc");
                     self.advance_token()?;
                     Ok(Expression::UnaryOperation {
                         operand: Box::new(left),
                         operator: UnaryOperator::Desc,
                     })
                 },
-                _ => Err(format!("Unexpected token in infix position: {:?}", token)),
+                _ => Err(self.error(Message::UnexpectedInfixToken { found: format!("{:?}", token) })),
             }
         } else {
-            Err("Unexpected end of input".to_string())
+            Err(self.error(Message::UnexpectedEndOfInput))
         }
     }
     
-    // The main entry point for the Pratt parser
-    pub fn parse_expression(&mut self, precedence: u8) -> Result<Expression, String> {
-        // First, parse a prefix expression
-        let mut left = self.parse_prefix()?;
-        
-        // Then, as long as the next operator has a higher precedence than the current one,
-        // parse the infix expression and update the left-hand side
-        while precedence < self.get_precedence() {
-            left = self.parse_infix(left)?;
+    // Numbers are stored signed, so that negative values can be represented
+    // too. A literal too big for that is an error rather than wrapping around.
+    fn number_literal(&self, value: u64) -> Result<Expression, String> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Expression::Number(value)),
+            Err(_) => Err(self.error(Message::NumberOutOfRange { number: value })),
         }
-        
-        Ok(left)
     }
     
-    // Parse the entire SQL query and return a Statement
-    pub fn parse_statement(&mut self) -> Result<Statement, String> {
-        if let Some(token) = &self.current_token {
-            match token {
-                Token::Keyword(Keyword::Select) => self.parse_select_statement(),
-                Token::Keyword(Keyword::Create) => self.parse_create_table_statement(),
-                _ => Err(format!("Expected SELECT or CREATE, got {:?}", token)),
-            }
-        } else {
-            Err("Empty input".to_string())
+    // Parse the right operand of a comparison, which can also be a subquery
+    // quantified with ANY (or SOME) or ALL, like `salary > ALL (SELECT ...)`.
+    // The quantifiers are not reserved, so that they can still name columns.
+    fn parse_comparison_operand(&mut self) -> Result<Expression, String> {
+        let word = match &self.current_token {
+            Some(Token::Identifier(word)) if ["ANY", "SOME", "ALL"].iter().any(|q| word.eq_ignore_ascii_case(q)) => word.clone(),
+            _ => return self.parse_expression(4),
+        };
+        let quantifier = if word.eq_ignore_ascii_case("ALL") { Quantifier::All } else { Quantifier::Any };
+        self.advance_token()?;
+        
+        match &self.current_token {
+            Some(Token::LeftParentheses) => {
+                cover!("comparison.quantified");
+                self.advance_token()?;
+                if !matches!(self.current_token, Some(Token::Keyword(Keyword::Select) | Token::Keyword(Keyword::With))) {
+                    return Err(self.error(Message::ExpectedAfter {
+                        expected: vec!["SELECT".to_string()],
+                        after: format!("{} (", word.to_ascii_uppercase()),
+                    }));
+                }
+                let subquery = self.parse_subquery()?;
+                Ok(Expression::Quantified { quantifier, subquery: Box::new(subquery) })
+            },
+            // Only a column that happens to be named like a quantifier
+            _ => {
+                let mut operand = match &self.current_token {
+                    Some(Token::Dot) => self.parse_compound_identifier(word)?,
+                    _ => Expression::Identifier(word),
+                };
+                while 4 < self.get_precedence() {
+                    operand = self.parse_infix(operand)?;
+                }
+                Ok(operand)
+            },
         }
     }
     
-    // Parse a SELECT statement
-    fn parse_select_statement(&mut self) -> Result<Statement, String> {
-        // Consume the SELECT keyword
+    // Parse `field FROM expression)` after `EXTRACT(`
+    fn parse_extract(&mut self) -> Result<Expression, String> {
+        let field = match &self.current_token {
+            Some(Token::Identifier(word)) => DateField::ALL.into_iter().find(|field| word.eq_ignore_ascii_case(&field.to_string())),
+            _ => None,
+        };
+        let Some(field) = field else {
+            return Err(self.error(Message::ExpectedAfter {
+                expected: DateField::ALL.iter().map(DateField::to_string).collect(),
+                after: "EXTRACT(".to_string(),
+            }));
+        };
         self.advance_token()?;
-        
-        // Parse columns (selection expressions)
-        let mut columns = Vec::new();
-        
-        // Special handling for SELECT *
-        if let Some(Token::Star) = &self.current_token {
+        if let Some(Token::Keyword(Keyword::From)) = &self.current_token {
             self.advance_token()?;
-            columns.push(Expression::Wildcard);
         } else {
-            // Parse first column
-            columns.push(self.parse_expression(0)?);
-            
-            // Parse additional columns separated by commas
-            while let Some(Token::Comma) = &self.current_token {
-                self.advance_token()?; // Consume comma
-                columns.push(self.parse_expression(0)?);
-            }
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["FROM".to_string()], after: field.to_string() }));
         }
-        
-        // Check for FROM clause
-        if let Some(Token::Keyword(Keyword::From)) = &self.current_token {
-            self.advance_token()?; // Consume FROM
+        let expression = self.parse_expression(0)?;
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
         } else {
-            return Err("Expected FROM clause in SELECT statement".to_string());
+            return Err(self.error(Message::UnclosedFunctionArguments { name: "EXTRACT".to_string() }));
         }
-        
-        // Parse table name
-        let from = if let Some(Token::Identifier(table_name)) = &self.current_token {
-            let table = table_name.clone();
+        Ok(Expression::Extract { field, expression: Box::new(expression) })
+    }
+    
+    // Parse `(expression AS type)` after the CAST keyword
+    fn parse_cast(&mut self) -> Result<Expression, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
             self.advance_token()?;
-            table
         } else {
-            return Err("Expected table name after FROM".to_string());
-        };
-        
-        // Parse optional WHERE clause
-        let r#where = if let Some(Token::Keyword(Keyword::Where)) = &self.current_token {
-            self.advance_token()?; // Consume WHERE
-            Some(self.parse_expression(0)?)
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "CAST".to_string() }));
+        }
+        let expression = self.parse_expression(0)?;
+        if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+            self.advance_token()?;
         } else {
-            None
-        };
-        
-        // Parse optional ORDER BY clause
-        let mut orderby = Vec::new();
-        if let Some(Token::Keyword(Keyword::Order)) = &self.current_token {
-            self.advance_token()?; // Consume ORDER
-            
-            // Check for BY
-            if let Some(Token::Keyword(Keyword::By)) = &self.current_token {
-                self.advance_token()?; // Consume BY
-                
-                // Parse first ORDER BY expression
-                let expr = self.parse_expression(0)?;
-                orderby.push(expr);
-                
-                // Parse additional ORDER BY expressions separated by commas
-                while let Some(Token::Comma) = &self.current_token {
-                    self.advance_token()?; // Consume comma
-                    let expr = self.parse_expression(0)?;
-                    orderby.push(expr);
-                }
-            } else {
-                return Err("Expected BY after ORDER".to_string());
-            }
+            return Err(self.error(Message::ExpectedAfter {
+                expected: vec!["AS".to_string()],
+                after: "the expression of CAST".to_string(),
+            }));
         }
-        
-        // Check for semicolon
-        if let Some(Token::Semicolon) = &self.current_token {
+        let target_type = self.parse_db_type()?;
+        if let Some(Token::RightParentheses) = &self.current_token {
             self.advance_token()?;
         } else {
-            return Err("Expected semicolon at the end of the SELECT statement".to_string());
+            return Err(self.error(Message::UnclosedCast));
         }
-        
-        Ok(Statement::Select {
-            columns,
-            from,
-            r#where,
-            orderby,
-        })
+        Ok(Expression::Cast { expression: Box::new(expression), target_type })
     }
     
-    // Parse a CREATE TABLE statement
-    fn parse_create_table_statement(&mut self) -> Result<Statement, String> {
-        // Consume the CREATE keyword
+    // Consumes the binary operator at the current token if both of its
+    // operands are plain expressions, like + or AND, unlike a comparison,
+    // whose right operand can be a quantified subquery. Returns the operator
+    // with the precedence its right operand is parsed at, which stops before
+    // the operators that bind as tight as this one or looser, so `a - b - c`
+    // is `(a - b) - c`, except for a right-associative operator, which takes
+    // in the operators of its own precedence.
+    fn binary_operator(&mut self) -> Result<Option<(BinaryOperator, u8)>, String> {
+        let operator = match &self.current_token {
+            Some(Token::Plus) => {
+                cover!("infix.plus");
+                BinaryOperator::Plus
+            },
+            Some(Token::Minus) => {
+                cover!("infix.minus");
+                BinaryOperator::Minus
+            },
+            Some(Token::Star) => {
+                cover!("infix.multiply");
+                BinaryOperator::Multiply
+            },
+            Some(Token::Divide) => {
+                cover!("infix.divide");
+                BinaryOperator::Divide
+            },
+            Some(Token::Percent) => {
+                cover!("infix.modulo");
+                BinaryOperator::Modulo
+            },
+            Some(Token::Pipe) => {
+                cover!("infix.bitwise_or");
+                BinaryOperator::BitwiseOr
+            },
+            Some(Token::Caret) if self.options.dialect == Dialect::Postgres => {
+                cover!("infix.power");
+                BinaryOperator::Power
+            },
+            Some(Token::Caret) => {
+                cover!("infix.bitwise_xor");
+                BinaryOperator::BitwiseXor
+            },
+            Some(Token::Ampersand) => {
+                cover!("infix.bitwise_and");
+                BinaryOperator::BitwiseAnd
+            },
+            Some(Token::ShiftLeft) => {
+                cover!("infix.shift_left");
+                BinaryOperator::ShiftLeft
+            },
+            Some(Token::ShiftRight) => {
+                cover!("infix.shift_right");
+                BinaryOperator::ShiftRight
+            },
+            Some(Token::Keyword(Keyword::And)) => {
+                cover!("infix.and");
+                BinaryOperator::And
+            },
+            Some(Token::Keyword(Keyword::Or)) => {
+                cover!("infix.or");
+                BinaryOperator::Or
+            },
+            _ => return Ok(None),
+        };
+        let precedence = self.get_precedence();
+        let right_precedence = if self.is_right_associative() { precedence - 1 } else { precedence };
         self.advance_token()?;
-        
-        // Check for TABLE keyword
-        if let Some(Token::Keyword(Keyword::Table)) = &self.current_token {
+        Ok(Some((operator, right_precedence)))
+    }
+    
+    // Parse the pattern of a SIMILAR TO or regular expression match, after
+    // its operator
+    fn parse_pattern_match(&mut self, left: Expression, operator: BinaryOperator) -> Result<Expression, String> {
+        let right = self.parse_expression(4)?;
+        Ok(Expression::BinaryOperation {
+            left_operand: Box::new(left),
+            operator,
+            right_operand: Box::new(right),
+        })
+    }
+    
+    // Parse the pattern of `SIMILAR TO`, after the SIMILAR word
+    fn parse_similar_to(&mut self, left: Expression, operator: BinaryOperator) -> Result<Expression, String> {
+        if self.at_word("TO") {
             self.advance_token()?;
         } else {
-            return Err("Expected TABLE after CREATE".to_string());
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["TO".to_string()], after: "SIMILAR".to_string() }));
         }
-        
-        // Parse table name
-        let table_name = if let Some(Token::Identifier(name)) = &self.current_token {
-            let table = name.clone();
+        self.parse_pattern_match(left, operator)
+    }
+    
+    // Parse the parenthesized subquery or list of values of an IN predicate,
+    // after the IN keyword
+    fn parse_in(&mut self, expression: Expression, negated: bool) -> Result<Expression, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
             self.advance_token()?;
-            table
         } else {
-            return Err("Expected table name after CREATE TABLE".to_string());
-        };
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "IN".to_string() }));
+        }
+        if let Some(Token::Keyword(Keyword::Select) | Token::Keyword(Keyword::With)) = &self.current_token {
+            cover!("infix.in");
+            let predicate = Expression::InSubquery {
+                expression: Box::new(expression),
+                subquery: Box::new(self.parse_subquery()?),
+            };
+            if !negated {
+                return Ok(predicate);
+            }
+            cover!("infix.not_in");
+            return Ok(Expression::UnaryOperation { operand: Box::new(predicate), operator: UnaryOperator::Not });
+        }
+        
+        cover!("infix.in_list");
+        let list = self.parse_expression_list()?;
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedInList));
+        }
+        Ok(Expression::InList { expression: Box::new(expression), list, negated })
+    }
+    
+    // Parse the pattern of a LIKE predicate after the LIKE keyword, with its
+    // optional ESCAPE character
+    fn parse_like(&mut self, expression: Expression, negated: bool) -> Result<Expression, String> {
+        let pattern = self.parse_expression(4)?;
+        let mut escape = None;
+        if self.at_word("ESCAPE") {
+            cover!("like.escape");
+            self.advance_token()?;
+            let mut characters = match &self.current_token {
+                Some(Token::String(text)) => text.chars(),
+                _ => "".chars(),
+            };
+            match (characters.next(), characters.next()) {
+                (Some(character), None) => escape = Some(character),
+                _ => return Err(self.error(Message::InvalidEscapeCharacter)),
+            }
+            self.advance_token()?;
+        }
+        Ok(Expression::Like {
+            expression: Box::new(expression),
+            pattern: Box::new(pattern),
+            negated,
+            escape,
+        })
+    }
+    
+    // The main entry point for the Pratt parser
+    pub fn parse_expression(&mut self, precedence: u8) -> Result<Expression, String> {
+        self.nested(|parser| parser.parse_operations(precedence))
+    }
+    
+    // Parses something that can be nested inside of itself, like expressions
+    // and queries, one level deeper, as long as `max_depth` allows
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        let depth = self.depth;
+        self.deeper()?;
+        let result = parse(self);
+        self.depth = depth;
+        result
+    }
+    
+    // Goes one level deeper into the nesting, as long as `max_depth` allows
+    fn deeper(&mut self) -> Result<(), String> {
+        if self.depth >= self.options.max_depth {
+            return Err(self.error(Message::NestingTooDeep { limit: self.options.max_depth }));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    
+    // Puts an operator that waits for its operand onto the stack of
+    // `parse_operations`, which is one level of nesting
+    fn push_pending(&mut self, pending: &mut Vec<Pending>, operator: Pending) -> Result<(), String> {
+        self.deeper()?;
+        pending.push(operator);
+        Ok(())
+    }
+    
+    // Parses the operands of an expression one after another, left to right.
+    // The operators and parentheses that wait for an operand to be parsed are
+    // kept on a stack, rather than in calls of this function into itself, so
+    // that chains like `- - - 1` or `((((1))))` do not use up the call stack.
+    fn parse_operations(&mut self, precedence: u8) -> Result<Expression, String> {
+        let mut precedence = precedence;
+        let mut pending = Vec::new();
+        'operand: loop {
+            // First, the unary operators and parentheses in front of the operand
+            let mut operand = match &self.current_token {
+                Some(Token::Keyword(Keyword::Not)) => {
+                    self.advance_token()?;
+                    if let Some(Token::Keyword(Keyword::Exists)) = &self.current_token {
+                        cover!("prefix.not_exists");
+                        self.advance_token()?;
+                        self.parse_exists(true)?
+                    } else {
+                        cover!("prefix.not");
+                        self.push_pending(&mut pending, Pending::Unary { operator: UnaryOperator::Not, precedence })?;
+                        precedence = 10; // NOT has high precedence
+                        continue 'operand;
+                    }
+                },
+                Some(Token::Plus) => {
+                    cover!("prefix.plus");
+                    self.advance_token()?;
+                    self.push_pending(&mut pending, Pending::Unary { operator: UnaryOperator::Plus, precedence })?;
+                    precedence = 10;
+                    continue 'operand;
+                },
+                Some(Token::Minus) => {
+                    self.advance_token()?;
+                    // The smallest number, -9223372036854775808, is read as a
+                    // literal, because 9223372036854775808 alone does not fit.
+                    // The minus takes it only when no operator binds tighter.
+                    let smallest = i64::MIN.unsigned_abs();
+                    if matches!(self.current_token, Some(Token::Number(n)) if n == smallest) {
+                        self.advance_token()?;
+                        if self.get_precedence() > 10 {
+                            return Err(self.error(Message::NumberOutOfRange { number: smallest }));
+                        }
+                        cover!("prefix.smallest_number");
+                        Expression::Number(i64::MIN)
+                    } else {
+                        cover!("prefix.minus");
+                        self.push_pending(&mut pending, Pending::Unary { operator: UnaryOperator::Minus, precedence })?;
+                        precedence = 10;
+                        continue 'operand;
+                    }
+                },
+                Some(Token::Tilde) => {
+                    cover!("prefix.bitwise_not");
+                    self.advance_token()?;
+                    self.push_pending(&mut pending, Pending::Unary { operator: UnaryOperator::BitwiseNot, precedence })?;
+                    precedence = 11; // Binds tighter than any binary operator except ::
+                    continue 'operand;
+                },
+                Some(Token::LeftParentheses) => {
+                    self.advance_token()?;
+                    if let Some(Token::Keyword(Keyword::Select) | Token::Keyword(Keyword::With)) = &self.current_token {
+                        cover!("prefix.subquery");
+                        Expression::Subquery(Box::new(self.parse_subquery()?))
+                    } else {
+                        self.push_pending(&mut pending, Pending::Parentheses { elements: Vec::new(), precedence })?;
+                        precedence = 0;
+                        continue 'operand;
+                    }
+                },
+                _ => self.parse_prefix()?,
+            };
+            
+            loop {
+                // Then, as long as the next operator has a higher precedence
+                // than the current one, the operators after the operand. Those
+                // with a plain right operand wait for it on the stack.
+                while precedence < self.get_precedence() {
+                    match self.binary_operator()? {
+                        Some((operator, right_precedence)) => {
+                            self.push_pending(&mut pending, Pending::Binary { left: operand, operator, precedence })?;
+                            precedence = right_precedence;
+                            continue 'operand;
+                        },
+                        None => operand = self.parse_infix(operand)?,
+                    }
+                }
+                if self.get_precedence() == 0 {
+                    self.expect(&["operator"]);
+                }
+                
+                // The operand is complete, and with it the operand of the
+                // operator that waited for it
+                match pending.pop() {
+                    None => return Ok(operand),
+                    Some(Pending::Unary { operator, precedence: outer }) => {
+                        operand = Expression::UnaryOperation { operand: Box::new(operand), operator };
+                        precedence = outer;
+                    },
+                    Some(Pending::Binary { left, operator, precedence: outer }) => {
+                        operand = Expression::BinaryOperation {
+                            left_operand: Box::new(left),
+                            operator,
+                            right_operand: Box::new(operand),
+                        };
+                        precedence = outer;
+                    },
+                    Some(Pending::Parentheses { mut elements, precedence: outer }) => {
+                        match &self.current_token {
+                            // A comma turns the parentheses from a grouping into a tuple
+                            Some(Token::Comma) => {
+                                self.advance_token()?;
+                                elements.push(operand);
+                                pending.push(Pending::Parentheses { elements, precedence: outer });
+                                precedence = 0;
+                                continue 'operand;
+                            },
+                            Some(Token::RightParentheses) if elements.is_empty() => {
+                                cover!("prefix.parentheses");
+                                self.advance_token()?;
+                            },
+                            Some(Token::RightParentheses) => {
+                                cover!("prefix.tuple");
+                                self.advance_token()?;
+                                elements.push(operand);
+                                operand = Expression::Tuple(elements);
+                            },
+                            _ => return Err(self.error(Message::ExpectedClosingParenthesis)),
+                        }
+                        precedence = outer;
+                    },
+                }
+                self.depth -= 1;
+            }
+        }
+    }
+    
+    // Parses a single operand with the unary operators in front of it, but
+    // none of the binary operators after it
+    fn parse_operand(&mut self) -> Result<Expression, String> {
+        self.parse_expression(u8::MAX)
+    }
+    
+    // Parse the entire SQL query and return a Statement
+    pub fn parse_statement(&mut self) -> Result<Statement, String> {
+        let start = self.current_span.start;
+        let statement = if let Some(token) = &self.current_token {
+            match token {
+                Token::Keyword(Keyword::Select) | Token::Keyword(Keyword::With) => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_select_statement()?
+                },
+                Token::Keyword(Keyword::Create) => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_create_statement()?
+                },
+                Token::Keyword(Keyword::Insert) => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_insert_statement()?
+                },
+                Token::Keyword(Keyword::Drop) => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_drop_statement()?
+                },
+                Token::Identifier(word) if word.eq_ignore_ascii_case("COMMENT") => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_comment_statement()?
+                },
+                Token::Identifier(word) | Token::Keyword(Keyword::Custom(word)) if self.handles(word) => {
+                    self.notify(|observer| observer.on_statement_start(start));
+                    self.parse_custom_statement()?
+                },
+                _ => {
+                    self.expect(&["SELECT", "WITH", "CREATE", "INSERT", "DROP", "COMMENT"]);
+                    return Err(self.unexpected());
+                },
+            }
+        } else {
+            return Err(self.error(Message::EmptyInput));
+        };
+        
+        if self.options.reject_writes && statement.is_write() {
+            return Err(self.error(Message::WriteRejected { statement: statement.kind().to_string() }));
+        }
+        
+        let span = self.span_from(start);
+        self.notify(|observer| observer.on_statement(&statement, span));
+        Ok(statement)
+    }
+    
+    // Parses the next statement, or returns None at the end of the input. The
+    // parser stops right after the semicolon of the statement, at `offset`,
+    // so that the caller can take over there: handle a statement the parser
+    // does not know on its own and `skip_statement` it, or read the rest of
+    // the input as tokens with `into_tokens`.
+    pub fn parse_next_statement(&mut self) -> Option<Result<Statement, String>> {
+        if self.is_at_end() {
+            return None;
+        }
+        Some(self.parse_statement())
+    }
+    
+    // Turns the parser into the tokens it has not consumed yet, starting with
+    // the one it is looking at
+    pub fn into_tokens(self) -> impl Iterator<Item = Result<Token, String>> + 'a {
+        self.current_token.map(Ok).into_iter().chain(self.tokenizer)
+    }
+    
+    // Parse every statement until the end of the input. An observer sees each
+    // statement as soon as it is parsed, before the rest of the input is read.
+    pub fn parse_statements(&mut self) -> Result<Vec<Statement>, String> {
+        let mut statements = Vec::new();
+        while !self.is_at_end() {
+            statements.push(self.parse_statement()?);
+        }
+        Ok(statements)
+    }
+    
+    // Parse every statement until the end of the input, carrying on after
+    // errors, for editors that show all the problems of the input at once.
+    // After an error in an item of a list (a column of SELECT or a column
+    // definition) the rest of the item is skipped up to the next comma, and
+    // after any other error the rest of the statement is skipped up to its
+    // semicolon. The statements that had errors in their lists are returned
+    // without the items that failed.
+    pub fn parse_all_with_errors(&mut self) -> (Vec<Statement>, Vec<ParseError>) {
+        self.recovering = true;
+        let mut statements = Vec::new();
+        while !self.is_at_end() {
+            match self.parse_statement() {
+                Ok(statement) => statements.push(statement),
+                Err(message) => {
+                    let error = self.locate(message);
+                    self.errors.push(error);
+                    self.skip_statement();
+                },
+            }
+        }
+        self.recovering = false;
+        (statements, std::mem::take(&mut self.errors))
+    }
+    
+    // Passes on the result of parsing an item of a comma separated list. When
+    // recovering from errors, an error is recorded instead, and the rest of
+    // the item is skipped up to the comma, the closing parenthesis or the end
+    // of the statement.
+    fn recover_item<T>(&mut self, item: Result<T, String>) -> Result<Option<T>, String> {
+        let message = match item {
+            Ok(item) => return Ok(Some(item)),
+            Err(message) if self.recovering => message,
+            Err(message) => return Err(message),
+        };
+        let error = self.locate(message);
+        self.errors.push(error);
+        let mut depth = 0;
+        loop {
+            match self.current_token {
+                None | Some(Token::Eof | Token::Semicolon) => break,
+                Some(Token::Comma | Token::RightParentheses | Token::Keyword(Keyword::From)) if depth == 0 => break,
+                Some(Token::LeftParentheses) => depth += 1,
+                Some(Token::RightParentheses) => depth -= 1,
+                _ => {},
+            }
+            let _ = self.advance_token();
+        }
+        Ok(None)
+    }
+    
+    // Whether the whole input has been consumed
+    pub fn is_at_end(&self) -> bool {
+        matches!(self.current_token, Some(Token::Eof) | None)
+    }
+    
+    // The byte offset of the token the parser is looking at
+    pub fn offset(&self) -> usize {
+        self.current_span.start
+    }
+    
+    // Where the token the parser is looking at starts. After an error of the
+    // tokenizer, this is where the tokenizer found it.
+    pub fn position(&self) -> Position {
+        self.tokenizer.position()
+    }
+    
+    // Attaches the span of the input to an error that the parser has just
+    // returned: the token it could not make sense of, or the text that the
+    // tokenizer failed to read
+    pub fn locate(&self, message: String) -> ParseError {
+        ParseError { message, span: self.tokenizer.span() }
+    }
+    
+    // Skips the rest of a statement that failed to parse, up to and including
+    // its semicolon, so that parsing can continue with the next statement.
+    // Tokenizer errors are skipped along with the tokens.
+    pub fn skip_statement(&mut self) {
+        while !self.is_at_end() {
+            let at_semicolon = matches!(self.current_token, Some(Token::Semicolon));
+            let _ = self.advance_token();
+            if at_semicolon {
+                break;
+            }
+        }
+    }
+    
+    // Whether the statement handler parses the statements starting with the word
+    fn handles(&self, word: &str) -> bool {
+        self.handler.as_deref().is_some_and(|handler| handler.handles(&word.to_uppercase()))
+    }
+    
+    // Parse a statement of the statement handler, starting at its first word
+    fn parse_custom_statement(&mut self) -> Result<Statement, String> {
+        cover!("statement.custom");
+        let keyword = self.current_token.as_ref().map(|token| match token {
+            Token::Keyword(Keyword::Custom(word)) | Token::Identifier(word) => word.to_uppercase(),
+            token => token.to_string(),
+        }).unwrap_or_default();
+        self.advance_token()?;
+        
+        let mut tokens = Vec::new();
+        while let Some(token) = &self.current_token {
+            if matches!(token, Token::Semicolon | Token::Eof) {
+                break;
+            }
+            tokens.push((token.clone(), self.current_span));
+            self.advance_token()?;
+        }
+        self.expect_semicolon()?;
+        
+        // The handler is there, as it claimed the keyword in `handles`
+        let payload = match self.handler.as_deref_mut() {
+            Some(handler) => handler.parse(&keyword, &tokens)?,
+            None => String::new(),
+        };
+        Ok(Statement::Custom { keyword, payload })
+    }
+    
+    // Consumes the semicolon at the end of a statement. The last statement of
+    // the input can go without it when `optional_final_semicolon` is set.
+    fn expect_semicolon(&mut self) -> Result<(), String> {
+        match self.current_token {
+            Some(Token::Semicolon) => self.advance_token(),
+            Some(Token::Eof) | None if self.options.optional_final_semicolon => Ok(()),
+            _ => {
+                self.expect(&["';'"]);
+                Err(self.unexpected())
+            },
+        }
+    }
+    
+    // Parse a SELECT statement
+    fn parse_select_statement(&mut self) -> Result<Statement, String> {
+        let query = self.parse_query()?;
+        
+        self.expect_semicolon()?;
+        
+        Ok(query)
+    }
+    
+    // Parse a SELECT query without the terminating semicolon, so that it can
+    // be embedded into other statements
+    fn parse_query(&mut self) -> Result<Statement, String> {
+        self.nested(Self::parse_query_body)
+    }
+    
+    fn parse_query_body(&mut self) -> Result<Statement, String> {
+        // Parse optional WITH clause
+        let mut with = Vec::new();
+        if let Some(Token::Keyword(Keyword::With)) = &self.current_token {
+            cover!("select.with");
+            let start = self.current_span.start;
+            self.advance_token()?; // Consume WITH
+            with.push(self.parse_common_table_expression()?);
+            
+            // Parse additional common table expressions separated by commas
+            while let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?; // Consume comma
+                with.push(self.parse_common_table_expression()?);
+            }
+            self.expect(&["','"]);
+            let span = self.span_from(start);
+            self.notify(|observer| observer.on_clause(Clause::With, span));
+        }
+        
+        if !matches!(self.current_token, Some(Token::Keyword(Keyword::Select))) {
+            self.expect(&["SELECT"]);
+            return Err(self.unexpected());
+        }
+        cover!("statement.select");
+        let start = self.current_span.start;
+        // Consume the SELECT keyword
+        self.advance_token()?;
+        
+        // Parse optional TOP of SQL Server, which is either a number or a
+        // parenthesized expression
+        let mut limit = None;
+        if self.options.dialect == Dialect::MsSql && self.at_word("TOP") {
+            cover!("select.top");
+            self.advance_token()?; // Consume TOP
+            let expression = match &self.current_token {
+                Some(Token::Number(n)) => {
+                    let number = self.number_literal(*n)?;
+                    self.advance_token()?;
+                    number
+                },
+                Some(Token::LeftParentheses) => self.parse_operand()?,
+                _ => return Err(self.error(Message::ExpectedAfter { expected: vec!["number".to_string(), "(".to_string()], after: "TOP".to_string() })),
+            };
+            limit = Some(expression);
+        }
+        
+        // Parse columns (selection expressions)
+        let mut columns = Vec::new();
+        
+        // Special handling for SELECT *
+        if let Some(Token::Star) = &self.current_token {
+            cover!("select.wildcard");
+            self.advance_token()?;
+            columns.push(Expression::Wildcard);
+        } else {
+            cover!("select.columns");
+            // Parse columns separated by commas
+            loop {
+                let column = self.parse_expression(0);
+                if let Some(column) = self.recover_item(column)? {
+                    columns.push(column);
+                }
+                if let Some(Token::Comma) = &self.current_token {
+                    self.advance_token()?; // Consume comma
+                } else {
+                    self.expect(&["','"]);
+                    break;
+                }
+            }
+        }
+        let span = self.span_from(start);
+        self.notify(|observer| observer.on_clause(Clause::Select, span));
+        
+        // Parse optional FROM clause. Without it, the query computes a single
+        // row from its expressions, like `SELECT 1 + 2;`, so there is nothing
+        // for a wildcard to expand to or for a WHERE clause to filter.
+        let mut from = Vec::new();
+        if let Some(Token::Keyword(Keyword::From)) = &self.current_token {
+            let start = self.current_span.start;
+            self.advance_token()?; // Consume FROM
+            
+            // Parse the table references (tables or trees of joins) separated by commas
+            from.push(self.parse_table_reference()?);
+            while let Some(Token::Comma) = &self.current_token {
+                cover!("from.comma");
+                self.advance_token()?; // Consume comma
+                from.push(self.parse_table_reference()?);
+            }
+            self.expect(&["','"]);
+            let span = self.span_from(start);
+            self.notify(|observer| observer.on_clause(Clause::From, span));
+        } else if columns.iter().any(|column| matches!(column, Expression::Wildcard | Expression::QualifiedWildcard(_)))
+            || matches!(self.current_token, Some(Token::Keyword(Keyword::Where))) {
+            return Err(self.error(Message::ExpectedFrom));
+        } else {
+            cover!("select.without_from");
+            self.expect(&["FROM"]);
+        }
+        
+        // Parse optional WHERE clause
+        let r#where = if let Some(Token::Keyword(Keyword::Where)) = &self.current_token {
+            cover!("select.where");
+            let start = self.current_span.start;
+            self.advance_token()?; // Consume WHERE
+            let expression = self.parse_expression(0)?;
+            let span = self.span_from(start);
+            self.notify(|observer| observer.on_clause(Clause::Where, span));
+            Some(expression)
+        } else {
+            // Without a FROM clause there is nothing to filter
+            if !from.is_empty() {
+                self.expect(&["WHERE"]);
+            }
+            None
+        };
+        
+        // Parse optional ORDER BY clause
+        let mut orderby = Vec::new();
+        if let Some(Token::Keyword(Keyword::Order)) = &self.current_token {
+            let start = self.current_span.start;
+            self.advance_token()?; // Consume ORDER
+            
+            // Check for BY
+            if let Some(Token::Keyword(Keyword::By)) = &self.current_token {
+                self.advance_token()?; // Consume BY
+                cover!("select.order_by");
+                
+                // Parse first ORDER BY expression
+                let expr = self.parse_expression(0)?;
+                orderby.push(expr);
+                
+                // Parse additional ORDER BY expressions separated by commas
+                while let Some(Token::Comma) = &self.current_token {
+                    self.advance_token()?; // Consume comma
+                    let expr = self.parse_expression(0)?;
+                    orderby.push(expr);
+                }
+                let span = self.span_from(start);
+                self.notify(|observer| observer.on_clause(Clause::OrderBy, span));
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["BY".to_string()], after: "ORDER".to_string() }));
+            }
+        } else {
+            self.expect(&["ORDER"]);
+        }
+        
+        // Parse optional LIMIT clause
+        if let Some(Token::Keyword(Keyword::Limit)) = &self.current_token {
+            cover!("select.limit");
+            if limit.is_some() {
+                return Err(self.error(Message::DuplicateLimit));
+            }
+            self.advance_token()?; // Consume LIMIT
+            limit = Some(self.parse_expression(0)?);
+        } else {
+            self.expect(&["LIMIT"]);
+        }
+        
+        // Parse optional locking clause
+        let lock = if let Some(Token::Keyword(Keyword::For)) = &self.current_token {
+            self.advance_token()?; // Consume FOR
+            Some(self.parse_locking_clause()?)
+        } else {
+            self.expect(&["FOR"]);
+            None
+        };
+        
+        Ok(Statement::Select {
+            with,
+            columns,
+            from,
+            r#where,
+            orderby,
+            limit,
+            lock,
+        })
+    }
+    
+    // Parse the locking clause of a query after its FOR keyword
+    fn parse_locking_clause(&mut self) -> Result<LockingClause, String> {
+        // Only UPDATE is reserved, the other words are common column names
+        let strength = if let Some(Token::Keyword(Keyword::Update)) = &self.current_token {
+            cover!("lock.for_update");
+            LockStrength::Update
+        } else if self.at_word("SHARE") {
+            cover!("lock.for_share");
+            LockStrength::Share
+        } else {
+            return Err(self.error(Message::ExpectedAfter {
+                expected: vec!["UPDATE".to_string(), "SHARE".to_string()],
+                after: "FOR".to_string(),
+            }));
+        };
+        self.advance_token()?;
+        
+        let wait_policy = if self.at_word("NOWAIT") {
+            cover!("lock.nowait");
+            self.advance_token()?;
+            WaitPolicy::NoWait
+        } else if self.at_word("SKIP") {
+            cover!("lock.skip_locked");
+            self.advance_token()?; // Consume SKIP
+            if self.at_word("LOCKED") {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["LOCKED".to_string()], after: "SKIP".to_string() }));
+            }
+            WaitPolicy::SkipLocked
+        } else {
+            WaitPolicy::Wait
+        };
+        Ok(LockingClause { strength, wait_policy })
+    }
+    
+    // Parse the parenthesized subquery of an EXISTS predicate
+    fn parse_exists(&mut self, negated: bool) -> Result<Expression, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "EXISTS".to_string() }));
+        }
+        let subquery = self.parse_subquery()?;
+        Ok(Expression::Exists {
+            subquery: Box::new(subquery),
+            negated,
+        })
+    }
+    
+    // Parse a query embedded into an expression, after its opening parenthesis
+    fn parse_subquery(&mut self) -> Result<Statement, String> {
+        let subquery = self.parse_query()?;
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+            Ok(subquery)
+        } else {
+            Err(self.error(Message::UnclosedSubquery))
+        }
+    }
+    
+    // Parse a single `name AS (query)` definition of the WITH clause
+    fn parse_common_table_expression(&mut self) -> Result<CommonTableExpression, String> {
+        let name = if let Some(Token::Identifier(name)) = &self.current_token {
+            let name = name.clone();
+            self.advance_token()?;
+            name
+        } else {
+            return Err(self.error(Message::ExpectedName { kind: NameKind::CommonTableExpression, after: Some("WITH".to_string()) }));
+        };
+        
+        // Check for AS keyword
+        if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["AS".to_string()], after: name }));
+        }
+        
+        // Check for opening parenthesis
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedQueryParenthesis { name }));
+        }
+        
+        let query = self.parse_query()?;
+        
+        // Check for closing parenthesis
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedQuery { name }));
+        }
+        
+        Ok(CommonTableExpression { name, query })
+    }
+    
+    // Parse the FROM clause: a table followed by any number of joins
+    fn parse_table_reference(&mut self) -> Result<TableReference, String> {
+        let mut left = self.parse_table_factor()?;
+        
+        while let Some(join_type) = self.parse_join_type()? {
+            let right = self.parse_table_factor()?;
+            
+            // Every join must have a condition
+            if let Some(Token::Keyword(Keyword::On)) = &self.current_token {
+                self.advance_token()?; // Consume ON
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["ON".to_string()], after: join_type.to_string() }));
+            }
+            let on = self.parse_expression(0)?;
+            
+            left = TableReference::Join {
+                left: Box::new(left),
+                join_type,
+                right: Box::new(right),
+                on,
+            };
+        }
+        
+        Ok(left)
+    }
+    
+    // Parse a single table name inside the FROM clause, a derived table, or
+    // joins in parentheses
+    fn parse_table_factor(&mut self) -> Result<TableReference, String> {
+        if let Some(Token::Identifier(_)) = &self.current_token {
+            cover!("from.table");
+            let name = self.parse_object_name(NameKind::Table, "FROM")?;
+            let alias = self.parse_alias()?;
+            Ok(TableReference::Table { name, alias })
+        } else if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?; // Consume (
+            // Joins are grouped with parentheses when one has to be the right
+            // side of another, like `a JOIN (b JOIN c ON ...) ON ...`
+            if matches!(self.current_token, Some(Token::Identifier(_) | Token::LeftParentheses)) {
+                cover!("from.parenthesized_join");
+                let reference = self.nested(Self::parse_table_reference)?;
+                if let Some(Token::RightParentheses) = &self.current_token {
+                    self.advance_token()?;
+                    return Ok(reference);
+                }
+                return Err(self.error(Message::UnclosedJoin));
+            }
+            cover!("from.derived");
+            let subquery = self.parse_subquery()?;
+            // A derived table has no name of its own, so it must be given one
+            match self.parse_alias()? {
+                Some(alias) => Ok(TableReference::Derived { subquery: Box::new(subquery), alias }),
+                None => Err(self.error(Message::MissingDerivedTableAlias)),
+            }
+        } else {
+            Err(self.error(Message::ExpectedName { kind: NameKind::Table, after: Some("FROM".to_string()) }))
+        }
+    }
+    
+    // Parse the rest of a dotted name, such as `users.name`, after its first part
+    fn parse_compound_identifier(&mut self, first: String) -> Result<Expression, String> {
+        let mut parts = vec![first];
+        while let Some(Token::Dot) = &self.current_token {
+            self.advance_token()?; // Consume the dot
+            if let Some(Token::Identifier(part)) = &self.current_token {
+                parts.push(part.clone());
+                self.advance_token()?;
+            } else if let (Some(Token::Star), [table]) = (&self.current_token, parts.as_slice()) {
+                cover!("prefix.qualified_wildcard");
+                let table = table.clone();
+                self.advance_token()?;
+                return Ok(Expression::QualifiedWildcard(table));
+            } else {
+                return Err(self.error(Message::ExpectedName { kind: NameKind::Column, after: Some(".".to_string()) }));
+            }
+        }
+        Ok(Expression::CompoundIdentifier(parts))
+    }
+    
+    // Parse the arguments and the optional OVER clause of a function call,
+    // starting at the opening parenthesis after the name of the function
+    fn parse_function_call(&mut self, name: String) -> Result<Expression, String> {
+        self.advance_token()?; // Consume (
+        let mut arguments = Vec::new();
+        // An aggregate over the distinct values only, like COUNT(DISTINCT name)
+        let distinct = matches!(self.current_token, Some(Token::Keyword(Keyword::Distinct)));
+        if distinct {
+            cover!("function.distinct");
+            self.advance_token()?;
+        }
+        if let Some(Token::Star) = &self.current_token {
+            // Only used by COUNT(*), meaning all rows
+            if distinct {
+                return Err(self.error(Message::DistinctWildcard { name }));
+            }
+            cover!("function.star");
+            self.advance_token()?;
+            arguments.push(Expression::Wildcard);
+        } else if distinct || !matches!(self.current_token, Some(Token::RightParentheses)) {
+            arguments.push(self.parse_expression(0)?);
+            while let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?; // Consume comma
+                arguments.push(self.parse_expression(0)?);
+            }
+        }
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedFunctionArguments { name }));
+        }
+        
+        // COALESCE and NULLIF are ordinary calls, except that they are a part
+        // of the SQL standard and so their arguments are known
+        let expected = match name.to_ascii_uppercase().as_str() {
+            "COALESCE" if arguments.is_empty() => Some("at least one argument"),
+            "NULLIF" if arguments.len() != 2 => Some("two arguments"),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            cover!("function.argument_count");
+            return Err(self.error(Message::WrongArgumentCount { name, expected: expected.to_string(), found: arguments.len() }));
+        }
+        
+        let over = if self.at_word("OVER") {
+            cover!("function.over");
+            self.advance_token()?; // Consume OVER
+            Some(Box::new(self.parse_window_specification()?))
+        } else {
+            None
+        };
+        
+        Ok(Expression::Function { name, distinct, arguments, over })
+    }
+    
+    // Parse the parenthesized window of the OVER clause
+    fn parse_window_specification(&mut self) -> Result<WindowSpecification, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "OVER".to_string() }));
+        }
+        
+        let mut partition_by = Vec::new();
+        if self.at_word("PARTITION") {
+            cover!("over.partition_by");
+            self.advance_token()?; // Consume PARTITION
+            self.expect_by("PARTITION")?;
+            partition_by = self.parse_expression_list()?;
+        }
+        
+        let mut order_by = Vec::new();
+        if let Some(Token::Keyword(Keyword::Order)) = &self.current_token {
+            cover!("over.order_by");
+            self.advance_token()?; // Consume ORDER
+            self.expect_by("ORDER")?;
+            order_by = self.parse_expression_list()?;
+        }
+        
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedWindowSpecification));
+        }
+        Ok(WindowSpecification { partition_by, order_by })
+    }
+    
+    // Consume the BY keyword that must follow the given keyword
+    fn expect_by(&mut self, after: &str) -> Result<(), String> {
+        if let Some(Token::Keyword(Keyword::By)) = &self.current_token {
+            self.advance_token()
+        } else {
+            Err(self.error(Message::ExpectedAfter { expected: vec!["BY".to_string()], after: after.to_string() }))
+        }
+    }
+    
+    // Parse one or more expressions separated by commas
+    fn parse_expression_list(&mut self) -> Result<Vec<Expression>, String> {
+        let mut expressions = vec![self.parse_expression(0)?];
+        while let Some(Token::Comma) = &self.current_token {
+            self.advance_token()?; // Consume comma
+            expressions.push(self.parse_expression(0)?);
+        }
+        Ok(expressions)
+    }
+    
+    // Parse an optional alias, written either as `AS alias` or just `alias`
+    fn parse_alias(&mut self) -> Result<Option<String>, String> {
+        if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+            self.advance_token()?; // Consume AS
+            if let Some(Token::Identifier(alias)) = &self.current_token {
+                cover!("from.alias");
+                let alias = alias.clone();
+                self.advance_token()?;
+                Ok(Some(alias))
+            } else {
+                Err(self.error(Message::ExpectedName { kind: NameKind::Alias, after: Some("AS".to_string()) }))
+            }
+        } else if let Some(Token::Identifier(alias)) = self.current_token.as_ref().filter(|_| !self.at_word("RETURNING")) {
+            // RETURNING ends an `INSERT ... SELECT`, so it is not taken for
+            // the alias of the last table of the query
+            cover!("from.alias");
+            let alias = alias.clone();
+            self.advance_token()?;
+            Ok(Some(alias))
+        } else {
+            self.expect(&["AS", "alias"]);
+            Ok(None)
+        }
+    }
+    
+    // Parse the join keywords: JOIN, INNER JOIN or LEFT/RIGHT/FULL [OUTER] JOIN.
+    // Returns None if the current token does not start a join
+    fn parse_join_type(&mut self) -> Result<Option<JoinType>, String> {
+        let join_type = match &self.current_token {
+            Some(Token::Keyword(Keyword::Join)) => {
+                cover!("join.inner");
+                self.advance_token()?; // Consume JOIN
+                return Ok(Some(JoinType::Inner));
+            },
+            Some(Token::Keyword(Keyword::Inner)) => {
+                cover!("join.inner");
+                self.advance_token()?; // Consume INNER
+                JoinType::Inner
+            },
+            Some(Token::Keyword(Keyword::Left)) => {
+                cover!("join.left");
+                self.advance_token()?; // Consume LEFT
+                JoinType::Left
+            },
+            Some(Token::Keyword(Keyword::Right)) => {
+                cover!("join.right");
+                self.advance_token()?; // Consume RIGHT
+                JoinType::Right
+            },
+            Some(Token::Keyword(Keyword::Full)) => {
+                cover!("join.full");
+                self.advance_token()?; // Consume FULL
+                JoinType::Full
+            },
+            _ => {
+                self.expect(&["JOIN", "INNER", "LEFT", "RIGHT", "FULL"]);
+                return Ok(None);
+            },
+        };
+        
+        // OUTER is optional and only allowed for LEFT, RIGHT and FULL joins
+        if join_type != JoinType::Inner {
+            if let Some(Token::Keyword(Keyword::Outer)) = &self.current_token {
+                self.advance_token()?; // Consume OUTER
+            }
+        }
+        
+        if let Some(Token::Keyword(Keyword::Join)) = &self.current_token {
+            self.advance_token()?; // Consume JOIN
+            Ok(Some(join_type))
+        } else {
+            let keyword = match join_type {
+                JoinType::Inner => "INNER",
+                JoinType::Left => "LEFT",
+                JoinType::Right => "RIGHT",
+                JoinType::Full => "FULL",
+            };
+            Err(self.error(Message::ExpectedAfter { expected: vec!["JOIN".to_string()], after: keyword.to_string() }))
+        }
+    }
+    
+    // Parse a CREATE statement, dispatching on the kind of object being created.
+    // OR REPLACE is only allowed before VIEW, and TEMPORARY only before TABLE.
+    fn parse_create_statement(&mut self) -> Result<Statement, String> {
+        // Consume the CREATE keyword
+        self.advance_token()?;
+        let mut after = "CREATE".to_string();
+        
+        let or_replace = if let Some(Token::Keyword(Keyword::Or)) = &self.current_token {
+            self.advance_token()?;
+            if !self.at_word("REPLACE") {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["REPLACE".to_string()], after: "CREATE OR".to_string() }));
+            }
+            cover!("create.or_replace");
+            self.advance_token()?;
+            after.push_str(" OR REPLACE");
+            true
+        } else {
+            false
+        };
+        let temporary = !or_replace && (self.at_word("TEMPORARY") || self.at_word("TEMP"));
+        if temporary {
+            cover!("create.temporary");
+            self.advance_token()?;
+            after.push_str(" TEMPORARY");
+        }
+        
+        match &self.current_token {
+            Some(Token::Keyword(Keyword::Table)) if !or_replace => self.parse_create_table_statement(temporary),
+            Some(Token::Keyword(Keyword::View)) if !temporary => self.parse_create_view_statement(or_replace),
+            _ => {
+                let mut expected = Vec::new();
+                if !or_replace {
+                    expected.push("TABLE".to_string());
+                }
+                if !temporary {
+                    expected.push("VIEW".to_string());
+                }
+                Err(self.error(Message::ExpectedAfter { expected, after }))
+            },
+        }
+    }
+    
+    // Parse the optional IF NOT EXISTS guard after the given words, which
+    // makes creating an object that already exists do nothing
+    fn parse_if_not_exists(&mut self, after: &str) -> Result<bool, String> {
+        if !self.at_word("IF") {
+            return Ok(false);
+        }
+        cover!("create.if_not_exists");
+        self.advance_token()?;
+        for keyword in [Keyword::Not, Keyword::Exists] {
+            if self.current_token.as_ref() == Some(&Token::Keyword(keyword.clone())) {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter {
+                    expected: vec!["NOT EXISTS".to_string()],
+                    after: format!("{} IF", after),
+                }));
+            }
+        }
+        Ok(true)
+    }
+    
+    // Parses the IF EXISTS guard of a DROP statement, if there is one
+    fn parse_if_exists(&mut self, after: &str) -> Result<bool, String> {
+        if !self.at_word("IF") {
+            return Ok(false);
+        }
+        cover!("drop.if_exists");
+        self.advance_token()?;
+        if let Some(Token::Keyword(Keyword::Exists)) = &self.current_token {
+            self.advance_token()?;
+            Ok(true)
+        } else {
+            Err(self.error(Message::ExpectedAfter { expected: vec!["EXISTS".to_string()], after: format!("{} IF", after) }))
+        }
+    }
+    
+    // Parse a DROP TABLE statement
+    fn parse_drop_statement(&mut self) -> Result<Statement, String> {
+        // Consume the DROP keyword
+        self.advance_token()?;
+        if let Some(Token::Keyword(Keyword::Table)) = &self.current_token {
+            cover!("statement.drop_table");
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["TABLE".to_string()], after: "DROP".to_string() }));
+        }
+        let if_exists = self.parse_if_exists("DROP TABLE")?;
+        let table_name = self.parse_object_name(NameKind::Table, "DROP TABLE")?;
+        
+        self.expect_semicolon()?;
+        Ok(Statement::DropTable { table_name, if_exists })
+    }
+    
+    // Parse a CREATE VIEW statement, after CREATE and its OR REPLACE
+    fn parse_create_view_statement(&mut self, or_replace: bool) -> Result<Statement, String> {
+        cover!("statement.create_view");
+        // Consume the VIEW keyword
+        self.advance_token()?;
+        let if_not_exists = self.parse_if_not_exists("CREATE VIEW")?;
+        // Both decide what happens to an existing view, in opposite ways
+        if or_replace && if_not_exists {
+            return Err(self.error(Message::ReplaceWithIfNotExists));
+        }
+        
+        let name = self.parse_object_name(NameKind::View, "CREATE VIEW")?;
+        
+        // Check for AS keyword
+        if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["AS".to_string()], after: name.to_string() }));
+        }
+        
+        let query = self.parse_query()?;
+        
+        self.expect_semicolon()?;
+        
+        Ok(Statement::CreateView {
+            name,
+            or_replace,
+            if_not_exists,
+            query: Box::new(query),
+        })
+    }
+    
+    // Parse a COMMENT ON statement, like `COMMENT ON COLUMN users.email IS '...';`
+    fn parse_comment_statement(&mut self) -> Result<Statement, String> {
+        cover!("statement.comment");
+        // Consume the COMMENT word
+        self.advance_token()?;
+        if let Some(Token::Keyword(Keyword::On)) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["ON".to_string()], after: "COMMENT".to_string() }));
+        }
+        
+        let target = match &self.current_token {
+            Some(Token::Keyword(Keyword::Table)) => {
+                self.advance_token()?;
+                CommentTarget::Table(self.parse_object_name(NameKind::Table, "COMMENT ON TABLE")?)
+            },
+            Some(Token::Keyword(Keyword::View)) => {
+                self.advance_token()?;
+                CommentTarget::View(self.parse_object_name(NameKind::View, "COMMENT ON VIEW")?)
+            },
+            _ if self.at_word("COLUMN") => {
+                self.advance_token()?;
+                // The column is the last part of the dotted name, after its table
+                let mut table = self.parse_object_name(NameKind::Table, "COMMENT ON COLUMN")?;
+                if !table.is_qualified() {
+                    return Err(self.error(Message::ExpectedAfter { expected: vec![".".to_string()], after: table.to_string() }));
+                }
+                let column = table.0.pop().unwrap_or_default();
+                CommentTarget::Column { table, column }
+            },
+            _ => return Err(self.error(Message::ExpectedAfter {
+                expected: vec!["TABLE".to_string(), "VIEW".to_string(), "COLUMN".to_string()],
+                after: "COMMENT ON".to_string(),
+            })),
+        };
+        
+        if let Some(Token::Keyword(Keyword::Is)) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["IS".to_string()], after: "COMMENT ON".to_string() }));
+        }
+        let text = match &self.current_token {
+            Some(Token::String(text)) => Some(text.clone()),
+            Some(Token::Keyword(Keyword::Null)) => None,
+            _ => return Err(self.error(Message::ExpectedCommentText)),
+        };
+        self.advance_token()?;
+        
+        self.expect_semicolon()?;
+        Ok(Statement::Comment { target, text })
+    }
+    
+    // Parse an INSERT statement, whose rows come either from a VALUES list or
+    // from a query: `INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b');`
+    fn parse_insert_statement(&mut self) -> Result<Statement, String> {
+        cover!("statement.insert");
+        // Consume the INSERT keyword
+        self.advance_token()?;
+        if let Some(Token::Keyword(Keyword::Into)) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["INTO".to_string()], after: "INSERT".to_string() }));
+        }
+        let table_name = self.parse_object_name(NameKind::Table, "INSERT INTO")?;
+        
+        // Parse the optional list of the columns that get the values
+        let mut columns = Vec::new();
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            cover!("insert.columns");
+            self.advance_token()?;
+            columns.push(self.parse_name(NameKind::Column, "(")?);
+            while let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?;
+                columns.push(self.parse_name(NameKind::Column, ",")?);
+            }
+            if let Some(Token::RightParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::UnclosedInsertColumns));
+            }
+        }
+        
+        let source = match &self.current_token {
+            Some(Token::Keyword(Keyword::Values)) => {
+                cover!("insert.values");
+                self.advance_token()?;
+                let mut rows = vec![self.parse_values_row()?];
+                while let Some(Token::Comma) = &self.current_token {
+                    cover!("insert.multiple_rows");
+                    self.advance_token()?;
+                    rows.push(self.parse_values_row()?);
+                }
+                self.expect(&["','"]);
+                InsertSource::Values(rows)
+            },
+            Some(Token::Keyword(Keyword::Select)) | Some(Token::Keyword(Keyword::With)) => {
+                cover!("insert.query");
+                InsertSource::Query(Box::new(self.parse_query()?))
+            },
+            _ => return Err(self.error(Message::ExpectedAfter {
+                expected: vec!["VALUES".to_string(), "SELECT".to_string()],
+                after: table_name.to_string(),
+            })),
+        };
+        
+        // RETURNING is not reserved, so that it can still name a column
+        let mut returning = Vec::new();
+        if self.at_word("RETURNING") {
+            cover!("insert.returning");
+            self.advance_token()?;
+            returning.push(self.parse_returning_item()?);
+            while let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?;
+                returning.push(self.parse_returning_item()?);
+            }
+            self.expect(&["','"]);
+        } else {
+            self.expect(&["RETURNING"]);
+        }
+        
+        self.expect_semicolon()?;
+        Ok(Statement::Insert { table_name, columns, source, returning })
+    }
+    
+    // Parse one value of the RETURNING clause with its optional `AS alias`,
+    // or a `*` for all the columns of the row
+    fn parse_returning_item(&mut self) -> Result<ReturningItem, String> {
+        if let Some(Token::Star) = &self.current_token {
+            self.advance_token()?;
+            return Ok(ReturningItem { expression: Expression::Wildcard, alias: None });
+        }
+        let expression = self.parse_expression(0)?;
+        let alias = if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+            self.advance_token()?;
+            Some(self.parse_name(NameKind::Alias, "AS")?)
+        } else {
+            None
+        };
+        Ok(ReturningItem { expression, alias })
+    }
+    
+    // Parse one parenthesized row of a VALUES list
+    fn parse_values_row(&mut self) -> Result<Vec<Expression>, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "VALUES".to_string() }));
+        }
+        let row = self.parse_expression_list()?;
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedValues));
+        }
+        Ok(row)
+    }
+    
+    // Parse the name of a schema object that must follow the given words
+    fn parse_name(&mut self, kind: NameKind, after: &str) -> Result<String, String> {
+        if let Some(Token::Identifier(name)) = &self.current_token {
+            let name = name.clone();
+            self.advance_token()?;
+            Ok(name)
+        } else {
+            Err(self.error(Message::ExpectedName { kind, after: Some(after.to_string()) }))
+        }
+    }
+    
+    // Parse the name of a table or a view, which can be qualified with its
+    // schema and database, like `mydb.public.users`
+    fn parse_object_name(&mut self, kind: NameKind, after: &str) -> Result<ObjectName, String> {
+        let mut parts = vec![self.parse_name(kind, after)?];
+        while let Some(Token::Dot) = &self.current_token {
+            cover!("name.qualified");
+            self.advance_token()?; // Consume the dot
+            parts.push(self.parse_name(kind, ".")?);
+        }
+        Ok(ObjectName(parts))
+    }
+    
+    // Parse a CREATE TABLE statement, after CREATE and its TEMPORARY
+    fn parse_create_table_statement(&mut self, temporary: bool) -> Result<Statement, String> {
+        cover!("statement.create_table");
+        // Consume the TABLE keyword
+        self.advance_token()?;
+        let if_not_exists = self.parse_if_not_exists("CREATE TABLE")?;
+        
+        let table_name = self.parse_object_name(NameKind::Table, "CREATE TABLE")?;
         
         // Check for opening parenthesis
         if let Some(Token::LeftParentheses) = &self.current_token {
             self.advance_token()?;
         } else {
-            return Err("Expected ( after table name".to_string());
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: table_name.to_string() }));
         }
         
-        // Parse column definitions
+        // Parse column definitions separated by commas
         let mut column_list = Vec::new();
-        
-        // Parse first column
-        column_list.push(self.parse_column_definition()?);
-        
-        // Parse additional columns separated by commas
-        while let Some(Token::Comma) = &self.current_token {
-            self.advance_token()?; // Consume comma
-            column_list.push(self.parse_column_definition()?);
+        let mut constraints = Vec::new();
+        cover!("create_table.columns");
+        loop {
+            let entry = if self.options.dialect == Dialect::MySql && self.at_key_definition() {
+                self.parse_key_definition(&table_name.to_string(), &mut column_list, &mut constraints)
+            } else if self.at_table_constraint() {
+                self.parse_table_constraint().map(|constraint| constraints.push(constraint))
+            } else {
+                self.parse_column_definition().map(|column| column_list.push(column))
+            };
+            self.recover_item(entry)?;
+            
+            if let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?; // Consume comma
+            } else {
+                break;
+            }
         }
         
         // Check for closing parenthesis
         if let Some(Token::RightParentheses) = &self.current_token {
             self.advance_token()?;
         } else {
-            return Err("Expected ) after column definitions".to_string());
+            return Err(self.error(Message::UnclosedColumnDefinitions));
         }
         
-        // Check for semicolon
-        if let Some(Token::Semicolon) = &self.current_token {
-            self.advance_token()?;
+        // Postgres puts the partitioning before the table options and MySQL
+        // after them, so the options can be on both sides
+        let mut options = self.parse_table_options()?;
+        let partitioning = if self.at_word("PARTITION") {
+            self.advance_token()?; // Consume PARTITION
+            let partitioning = self.parse_partitioning()?;
+            options.extend(self.parse_table_options()?);
+            Some(partitioning)
         } else {
-            return Err("Expected semicolon at the end of the CREATE TABLE statement".to_string());
-        }
+            None
+        };
+        
+        self.expect_semicolon()?;
         
         Ok(Statement::CreateTable {
             table_name,
+            temporary,
+            if_not_exists,
             column_list,
+            constraints,
+            options,
+            partitioning,
         })
     }
     
+    // Parse the partitioning of a table after its PARTITION keyword, like
+    // `PARTITION BY HASH (id)`
+    fn parse_partitioning(&mut self) -> Result<Partitioning, String> {
+        self.expect_by("PARTITION")?;
+        let method = if self.at_word("RANGE") {
+            cover!("partition.range");
+            PartitionMethod::Range
+        } else if self.at_word("LIST") {
+            cover!("partition.list");
+            PartitionMethod::List
+        } else if self.at_word("HASH") {
+            cover!("partition.hash");
+            PartitionMethod::Hash
+        } else if let Some(Token::Keyword(Keyword::Key)) = &self.current_token {
+            cover!("partition.key");
+            PartitionMethod::Key
+        } else {
+            return Err(self.error(Message::ExpectedAfter {
+                expected: vec!["RANGE".to_string(), "LIST".to_string(), "HASH".to_string(), "KEY".to_string()],
+                after: "PARTITION BY".to_string(),
+            }));
+        };
+        self.advance_token()?;
+        
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "PARTITION BY".to_string() }));
+        }
+        let expressions = self.parse_expression_list()?;
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedPartitionKey));
+        }
+        Ok(Partitioning { method, expressions })
+    }
+    
+    // Parse the optional table options after the column definitions, either
+    // `WITH (name = value, ...)` or `name = value ...` (optionally separated by
+    // commas), where a name can be made of several words, like `DEFAULT CHARSET`
+    fn parse_table_options(&mut self) -> Result<Vec<(String, Expression)>, String> {
+        let mut options = Vec::new();
+        if let Some(Token::Keyword(Keyword::With)) = &self.current_token {
+            cover!("table_options.with");
+            self.advance_token()?; // Consume WITH
+            if let Some(Token::LeftParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "WITH".to_string() }));
+            }
+            options.push(self.parse_table_option()?);
+            while let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?; // Consume comma
+                options.push(self.parse_table_option()?);
+            }
+            if let Some(Token::RightParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::UnclosedTableOptions));
+            }
+        } else {
+            // PARTITION is not reserved, so it is told apart from the name of
+            // an option by the word itself
+            while matches!(self.current_token, Some(Token::Identifier(_))) && !self.at_word("PARTITION") {
+                cover!("table_options.list");
+                options.push(self.parse_table_option()?);
+                if let Some(Token::Comma) = &self.current_token {
+                    self.advance_token()?; // Consume comma
+                }
+            }
+        }
+        Ok(options)
+    }
+    
+    // Parse a single table option: its name, an equals sign and its value
+    fn parse_table_option(&mut self) -> Result<(String, Expression), String> {
+        let mut words = Vec::new();
+        while let Some(Token::Identifier(word)) = &self.current_token {
+            words.push(word.clone());
+            self.advance_token()?;
+        }
+        if words.is_empty() {
+            return Err(self.error(Message::ExpectedName { kind: NameKind::TableOption, after: None }));
+        }
+        let name = words.join(" ");
+        
+        if let Some(Token::Equal) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["=".to_string()], after: name }));
+        }
+        // The value can not be a comparison, so that `=` always starts the value
+        let value = self.parse_expression(4)?;
+        Ok((name, value))
+    }
+    
     // Parse a column definition
     fn parse_column_definition(&mut self) -> Result<TableColumn, String> {
+        let start = self.current_span.start;
         // Parse column name
         let column_name = if let Some(Token::Identifier(name)) = &self.current_token {
             let column = name.clone();
             self.advance_token()?;
             column
         } else {
-            return Err("Expected column name".to_string());
+            return Err(self.error(Message::ExpectedName { kind: NameKind::Column, after: None }));
         };
         
         // Parse column type
-        let column_type = self.parse_db_type()?;
+        let mut column_type = self.parse_db_type()?;
+        if self.options.dialect == Dialect::MySql && self.at_word("UNSIGNED") {
+            self.advance_token()?;
+            match &mut column_type {
+                DBType::Int { unsigned } | DBType::SmallInt { unsigned } | DBType::BigInt { unsigned } | DBType::TinyInt { unsigned } => {
+                    cover!("mysql.unsigned");
+                    *unsigned = true;
+                },
+                // MySQL deprecated UNSIGNED for the other numbers, and it only
+                // forbids negative values there without widening the range
+                _ => {
+                    cover!("mysql.unsigned_ignored");
+                    self.warn(Message::IgnoredUnsigned { column: column_name.clone() });
+                },
+            }
+        }
         
         // Parse optional constraints
         let mut constraints = Vec::new();
@@ -453,9 +2376,10 @@ impl<'a> Parser<'a> {
                         // Check for KEY
                         if let Some(Token::Keyword(Keyword::Key)) = &self.current_token {
                             self.advance_token()?;
+                            cover!("column.primary_key");
                             constraints.push(Constraint::PrimaryKey);
                         } else {
-                            return Err("Expected KEY after PRIMARY".to_string());
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["KEY".to_string()], after: "PRIMARY".to_string() }));
                         }
                     },
                     Token::Keyword(Keyword::Not) => {
@@ -463,10 +2387,22 @@ impl<'a> Parser<'a> {
                         // Check for NULL
                         if let Some(Token::Keyword(Keyword::Null)) = &self.current_token {
                             self.advance_token()?;
+                            cover!("column.not_null");
+                            if constraints.contains(&Constraint::Null) {
+                                return Err(self.error(Message::ConflictingNullability { column: column_name }));
+                            }
                             constraints.push(Constraint::NotNull);
                         } else {
-                            return Err("Expected NULL after NOT".to_string());
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["NULL".to_string()], after: "NOT".to_string() }));
+                        }
+                    },
+                    Token::Keyword(Keyword::Null) => {
+                        cover!("column.null");
+                        if constraints.contains(&Constraint::NotNull) {
+                            return Err(self.error(Message::ConflictingNullability { column: column_name }));
                         }
+                        self.advance_token()?;
+                        constraints.push(Constraint::Null);
                     },
                     Token::Keyword(Keyword::Check) => {
                         self.advance_token()?;
@@ -478,45 +2414,302 @@ impl<'a> Parser<'a> {
                             // Check for closing parenthesis
                             if let Some(Token::RightParentheses) = &self.current_token {
                                 self.advance_token()?;
+                                cover!("column.check");
                                 constraints.push(Constraint::Check(expr));
                             } else {
-                                return Err("Expected ) after CHECK expression".to_string());
+                                return Err(self.error(Message::UnclosedCheck));
+                            }
+                        } else {
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "CHECK".to_string() }));
+                        }
+                    },
+                    Token::Identifier(word) if word.eq_ignore_ascii_case("COMMENT") => {
+                        self.advance_token()?;
+                        if let Some(Token::String(text)) = &self.current_token {
+                            cover!("column.comment");
+                            constraints.push(Constraint::Comment(text.clone()));
+                            self.advance_token()?;
+                        } else {
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["string".to_string()], after: "COMMENT".to_string() }));
+                        }
+                    },
+                    Token::Identifier(word) if word.eq_ignore_ascii_case("DEFAULT") => {
+                        cover!("column.default");
+                        self.advance_token()?;
+                        // Comparisons and the logical operators are left out, so
+                        // that the NOT of a following NOT NULL is not taken for an
+                        // operator. They can still be used in parentheses.
+                        let value = self.parse_expression(4)?;
+                        constraints.push(Constraint::Default(value));
+                    },
+                    Token::Identifier(word) if word.eq_ignore_ascii_case("AUTO_INCREMENT") || word.eq_ignore_ascii_case("AUTOINCREMENT") => {
+                        cover!("column.auto_increment");
+                        if !column_type.is_integer() {
+                            return Err(self.error(Message::AutoIncrementNotInteger { column: column_name }));
+                        }
+                        self.advance_token()?;
+                        constraints.push(Constraint::AutoIncrement);
+                    },
+                    Token::Identifier(word) if word.eq_ignore_ascii_case("GENERATED") => {
+                        cover!("column.identity");
+                        self.advance_token()?;
+                        let always = if self.at_word("ALWAYS") {
+                            self.advance_token()?;
+                            true
+                        } else if let Some(Token::Keyword(Keyword::By)) = &self.current_token {
+                            self.advance_token()?;
+                            if !self.at_word("DEFAULT") {
+                                return Err(self.error(Message::ExpectedAfter { expected: vec!["DEFAULT".to_string()], after: "GENERATED BY".to_string() }));
                             }
+                            self.advance_token()?;
+                            false
                         } else {
-                            return Err("Expected ( after CHECK".to_string());
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["ALWAYS".to_string(), "BY DEFAULT".to_string()], after: "GENERATED".to_string() }));
+                        };
+                        if let Some(Token::Keyword(Keyword::As)) = &self.current_token {
+                            self.advance_token()?;
+                        } else {
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["AS IDENTITY".to_string()], after: "GENERATED".to_string() }));
                         }
+                        if !self.at_word("IDENTITY") {
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["IDENTITY".to_string()], after: "GENERATED AS".to_string() }));
+                        }
+                        self.advance_token()?;
+                        constraints.push(Constraint::Identity { always });
                     },
                     Token::Comma | Token::RightParentheses => {
                         // End of column definition
                         break;
                     },
-                    _ => return Err(format!("Unexpected token in column definition: {:?}", token)),
+                    _ => return Err(self.error(Message::UnexpectedColumnDefinitionToken { found: format!("{:?}", token) })),
                 }
             } else {
-                return Err("Unexpected end of input in column definition".to_string());
+                return Err(self.error(Message::UnexpectedEndOfColumnDefinition));
             }
         }
         
-        Ok(TableColumn {
+        let column = TableColumn {
             column_name,
             column_type,
             constraints,
-        })
+        };
+        let span = self.span_from(start);
+        self.notify(|observer| observer.on_column_definition(&column, span));
+        Ok(column)
+    }
+    
+    // Whether a constraint of the whole table starts at the current token, like
+    // `PRIMARY KEY (a, b)`, `CHECK (a < b)` or `CONSTRAINT name UNIQUE (x)`
+    fn at_table_constraint(&self) -> bool {
+        matches!(self.current_token, Some(Token::Keyword(Keyword::Primary) | Token::Keyword(Keyword::Check)))
+            || self.at_word("UNIQUE")
+            || self.at_word("CONSTRAINT")
+    }
+    
+    fn parse_table_constraint(&mut self) -> Result<TableConstraint, String> {
+        let name = if self.at_word("CONSTRAINT") {
+            cover!("create_table.named_constraint");
+            self.advance_token()?;
+            Some(self.parse_name(NameKind::Constraint, "CONSTRAINT")?)
+        } else {
+            None
+        };
+        
+        if let Some(Token::Keyword(Keyword::Primary)) = &self.current_token {
+            cover!("create_table.primary_key");
+            self.advance_token()?;
+            if let Some(Token::Keyword(Keyword::Key)) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["KEY".to_string()], after: "PRIMARY".to_string() }));
+            }
+            let columns = self.parse_constraint_columns("PRIMARY KEY")?;
+            Ok(TableConstraint::PrimaryKey { name, columns })
+        } else if self.at_word("UNIQUE") {
+            cover!("create_table.unique");
+            self.advance_token()?;
+            let columns = self.parse_constraint_columns("UNIQUE")?;
+            Ok(TableConstraint::Unique { name, columns })
+        } else if let Some(Token::Keyword(Keyword::Check)) = &self.current_token {
+            cover!("create_table.check");
+            self.advance_token()?;
+            if let Some(Token::LeftParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "CHECK".to_string() }));
+            }
+            let expression = self.parse_expression(0)?;
+            if let Some(Token::RightParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::UnclosedCheck));
+            }
+            Ok(TableConstraint::Check { name, expression })
+        } else {
+            Err(self.error(Message::ExpectedAfter {
+                expected: vec!["PRIMARY KEY".to_string(), "UNIQUE".to_string(), "CHECK".to_string()],
+                after: format!("CONSTRAINT {}", name.unwrap_or_default()),
+            }))
+        }
+    }
+    
+    // Parses the parenthesized columns of a table constraint, like `(a, b)`
+    fn parse_constraint_columns(&mut self, constraint: &str) -> Result<Vec<String>, String> {
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: constraint.to_string() }));
+        }
+        let mut columns = vec![self.parse_name(NameKind::Column, "(")?];
+        while let Some(Token::Comma) = &self.current_token {
+            self.advance_token()?;
+            columns.push(self.parse_name(NameKind::Column, ",")?);
+        }
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+            Ok(columns)
+        } else {
+            Err(self.error(Message::ExpectedAfter { expected: vec![")".to_string()], after: format!("the columns of {}", constraint) }))
+        }
+    }
+    
+    // Whether a key definition of MySQL starts at the current token, like
+    // `PRIMARY KEY (id)`, `KEY idx_name (name)` or `UNIQUE INDEX (email)`
+    fn at_key_definition(&self) -> bool {
+        matches!(self.current_token, Some(Token::Keyword(Keyword::Primary)) | Some(Token::Keyword(Keyword::Key)))
+            || self.at_word("INDEX")
+            || self.at_word("UNIQUE")
+    }
+    
+    // Parse a key definition of MySQL among the columns of a table. A primary
+    // key of a single column becomes a constraint of that column, while the
+    // other keys only define indexes, so they are skipped with a warning.
+    fn parse_key_definition(&mut self, table_name: &str, column_list: &mut [TableColumn], constraints: &mut Vec<TableConstraint>) -> Result<(), String> {
+        let primary = matches!(self.current_token, Some(Token::Keyword(Keyword::Primary)));
+        // Skip the keywords before the name of the key
+        while matches!(self.current_token, Some(Token::Keyword(Keyword::Primary)) | Some(Token::Keyword(Keyword::Key)))
+            || self.at_word("INDEX")
+            || self.at_word("UNIQUE")
+        {
+            self.advance_token()?;
+        }
+        let name = if let Some(Token::Identifier(name)) = &self.current_token {
+            let name = name.clone();
+            self.advance_token()?;
+            Some(name)
+        } else {
+            None
+        };
+        
+        if let Some(Token::LeftParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::ExpectedAfter { expected: vec!["(".to_string()], after: "KEY".to_string() }));
+        }
+        let mut columns = Vec::new();
+        loop {
+            if let Some(Token::Identifier(column)) = &self.current_token {
+                columns.push(column.clone());
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedName { kind: NameKind::Column, after: None }));
+            }
+            // Skip the length of an indexed prefix, like `name(10)`
+            if let Some(Token::LeftParentheses) = &self.current_token {
+                self.advance_token()?;
+                if let Some(Token::Number(_)) = &self.current_token {
+                    self.advance_token()?;
+                }
+                if let Some(Token::RightParentheses) = &self.current_token {
+                    self.advance_token()?;
+                } else {
+                    return Err(self.error(Message::UnclosedKeyColumns));
+                }
+            }
+            if let Some(Token::Comma) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                break;
+            }
+        }
+        if let Some(Token::RightParentheses) = &self.current_token {
+            self.advance_token()?;
+        } else {
+            return Err(self.error(Message::UnclosedKeyColumns));
+        }
+        // Skip the index options, like `USING BTREE`
+        while !self.is_at_end() && !matches!(self.current_token, Some(Token::Comma) | Some(Token::RightParentheses)) {
+            self.advance_token()?;
+        }
+        
+        match columns.as_slice() {
+            [column] if primary => {
+                cover!("mysql.primary_key");
+                let Some(definition) = column_list.iter_mut().find(|definition| &definition.column_name == column) else {
+                    return Err(self.error(Message::UnknownColumn { name: column.clone() }));
+                };
+                if !definition.constraints.contains(&Constraint::PrimaryKey) {
+                    definition.constraints.push(Constraint::PrimaryKey);
+                }
+            },
+            _ if primary => {
+                cover!("mysql.composite_primary_key");
+                constraints.push(TableConstraint::PrimaryKey { name: None, columns });
+            },
+            _ => {
+                cover!("mysql.index");
+                self.warn(Message::IgnoredIndex { name, table: table_name.to_string() });
+            },
+        }
+        Ok(())
     }
     
+    // Parses the rest of an integer type after its name
+    fn parse_integer_type(&mut self, integer: DBType, name: &str) -> Result<DBType, String> {
+        self.advance_token()?;
+        // The display width of MySQL, like `INT(11)`, does not change the type
+        if self.options.dialect == Dialect::MySql && matches!(self.current_token, Some(Token::LeftParentheses)) {
+            cover!("mysql.display_width");
+            self.advance_token()?;
+            if let Some(Token::Number(_)) = &self.current_token {
+                self.advance_token()?;
+            }
+            if let Some(Token::RightParentheses) = &self.current_token {
+                self.advance_token()?;
+            } else {
+                return Err(self.error(Message::ExpectedAfter { expected: vec![")".to_string()], after: format!("{}(", name) }));
+            }
+        }
+        Ok(integer)
+    }
+
     // Parse a database type
     fn parse_db_type(&mut self) -> Result<DBType, String> {
         if let Some(token) = &self.current_token {
             match token {
                 Token::Keyword(Keyword::Int) => {
-                    self.advance_token()?;
-                    Ok(DBType::Int)
+                    cover!("type.int");
+                    self.parse_integer_type(DBType::Int { unsigned: false }, "INT")
+                },
+                Token::Keyword(Keyword::SmallInt) => {
+                    cover!("type.smallint");
+                    self.parse_integer_type(DBType::SmallInt { unsigned: false }, "SMALLINT")
+                },
+                Token::Keyword(Keyword::BigInt) => {
+                    cover!("type.bigint");
+                    self.parse_integer_type(DBType::BigInt { unsigned: false }, "BIGINT")
+                },
+                Token::Keyword(Keyword::TinyInt) => {
+                    cover!("type.tinyint");
+                    self.parse_integer_type(DBType::TinyInt { unsigned: false }, "TINYINT")
                 },
                 Token::Keyword(Keyword::Bool) => {
+                    cover!("type.bool");
                     self.advance_token()?;
                     Ok(DBType::Bool)
                 },
                 Token::Keyword(Keyword::Varchar) => {
+                    cover!("type.varchar");
                     self.advance_token()?;
                     // Check for opening parenthesis
                     if let Some(Token::LeftParentheses) = &self.current_token {
@@ -528,23 +2721,196 @@ impl<'a> Parser<'a> {
                             // Check for closing parenthesis
                             if let Some(Token::RightParentheses) = &self.current_token {
                                 self.advance_token()?;
-                                Ok(DBType::Varchar(length))
+                                Ok(DBType::Varchar(Some(length)))
                             } else {
-                                Err("Expected ) after VARCHAR length".to_string())
+                                Err(self.error(Message::UnclosedVarcharLength))
                             }
                         } else {
-                            Err("Expected number for VARCHAR length".to_string())
+                            Err(self.error(Message::ExpectedVarcharLength))
                         }
                     } else {
-                        Err("Expected ( after VARCHAR".to_string())
+                        cover!("type.varchar_without_length");
+                        Ok(DBType::Varchar(None))
                     }
                 },
-                _ => Err(format!("Expected data type, got {:?}", token)),
+                Token::Keyword(Keyword::Decimal | Keyword::Numeric) => {
+                    cover!("type.decimal");
+                    let name = token.to_string().to_uppercase();
+                    self.advance_token()?;
+                    if !matches!(self.current_token, Some(Token::LeftParentheses)) {
+                        return Ok(DBType::Decimal { precision: None, scale: None });
+                    }
+                    self.advance_token()?;
+                    let Some(Token::Number(precision)) = self.current_token else {
+                        return Err(self.error(Message::ExpectedAfter { expected: vec!["precision".to_string()], after: format!("{}(", name) }));
+                    };
+                    self.advance_token()?;
+                    let mut scale = None;
+                    if let Some(Token::Comma) = &self.current_token {
+                        self.advance_token()?;
+                        let Some(Token::Number(number)) = self.current_token else {
+                            return Err(self.error(Message::ExpectedAfter { expected: vec!["scale".to_string()], after: format!("{}({},", name, precision) }));
+                        };
+                        self.advance_token()?;
+                        scale = Some(number as usize);
+                    }
+                    if !matches!(self.current_token, Some(Token::RightParentheses)) {
+                        return Err(self.error(Message::ExpectedAfter { expected: vec![")".to_string()], after: format!("the precision of {}", name) }));
+                    }
+                    self.advance_token()?;
+                    let precision = precision as usize;
+                    if let Some(scale) = scale.filter(|scale| *scale > precision) {
+                        return Err(self.error(Message::InvalidDecimalScale { precision, scale }));
+                    }
+                    Ok(DBType::Decimal { precision: Some(precision), scale })
+                },
+                Token::Keyword(Keyword::Float) => {
+                    cover!("type.float");
+                    self.advance_token()?;
+                    Ok(DBType::Float)
+                },
+                Token::Keyword(Keyword::Real) => {
+                    cover!("type.real");
+                    self.advance_token()?;
+                    Ok(DBType::Real)
+                },
+                Token::Keyword(Keyword::Double) => {
+                    cover!("type.double");
+                    self.advance_token()?;
+                    if self.at_word("PRECISION") {
+                        self.advance_token()?;
+                    }
+                    Ok(DBType::Double)
+                },
+                Token::Keyword(Keyword::Blob | Keyword::Bytea) => {
+                    cover!("type.blob");
+                    self.advance_token()?;
+                    Ok(DBType::Blob)
+                },
+                // The names of the date and time types and of UUID are not
+                // reserved, as they are common names of columns
+                Token::Identifier(name) if name.eq_ignore_ascii_case("SERIAL") => {
+                    cover!("type.serial");
+                    self.advance_token()?;
+                    Ok(DBType::Serial)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("BIGSERIAL") => {
+                    cover!("type.bigserial");
+                    self.advance_token()?;
+                    Ok(DBType::BigSerial)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("UUID") => {
+                    cover!("type.uuid");
+                    self.advance_token()?;
+                    Ok(DBType::Uuid)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("DATE") => {
+                    cover!("type.date");
+                    self.advance_token()?;
+                    Ok(DBType::Date)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("TIME") => {
+                    cover!("type.time");
+                    self.advance_token()?;
+                    Ok(DBType::Time)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("DATETIME") => {
+                    cover!("type.datetime");
+                    self.advance_token()?;
+                    Ok(DBType::DateTime)
+                },
+                Token::Identifier(name) if name.eq_ignore_ascii_case("TIMESTAMP") => {
+                    self.advance_token()?;
+                    if !matches!(self.current_token, Some(Token::Keyword(Keyword::With))) {
+                        cover!("type.timestamp");
+                        return Ok(DBType::Timestamp { with_time_zone: false });
+                    }
+                    cover!("type.timestamp_with_time_zone");
+                    self.advance_token()?;
+                    if !self.at_word("TIME") {
+                        return Err(self.error(Message::ExpectedAfter { expected: vec!["TIME ZONE".to_string()], after: "TIMESTAMP WITH".to_string() }));
+                    }
+                    self.advance_token()?;
+                    if !self.at_word("ZONE") {
+                        return Err(self.error(Message::ExpectedAfter { expected: vec!["ZONE".to_string()], after: "TIMESTAMP WITH TIME".to_string() }));
+                    }
+                    self.advance_token()?;
+                    Ok(DBType::Timestamp { with_time_zone: true })
+                },
+                Token::Keyword(Keyword::Custom(name)) => {
+                    cover!("type.custom");
+                    let name = name.clone();
+                    self.advance_token()?;
+                    Ok(DBType::Custom(name))
+                },
+                _ => Err(self.error(Message::ExpectedDataType { found: format!("{:?}", token) })),
             }
         } else {
-            Err("Unexpected end of input in type definition".to_string())
+            Err(self.error(Message::UnexpectedEndOfTypeDefinition))
+        }
+    }
+}
+
+// Checks the format of a typed literal: `YYYY-MM-DD` for a date, `HH:MM:SS`
+// with an optional fraction of a second for a time, and both separated by a
+// space or a `T` for a timestamp
+fn is_valid_temporal(kind: &TemporalKind, value: &str) -> bool {
+    fn number(part: &str, digits: usize, range: std::ops::RangeInclusive<u32>) -> bool {
+        part.len() == digits && part.bytes().all(|byte| byte.is_ascii_digit()) && part.parse().is_ok_and(|n| range.contains(&n))
+    }
+    fn date(value: &str) -> bool {
+        let parts: Vec<&str> = value.split('-').collect();
+        let [year, month, day] = parts[..] else { return false };
+        if !number(year, 4, 0..=9999) || !number(month, 2, 1..=12) {
+            return false;
+        }
+        let year: u32 = year.parse().unwrap();
+        let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month.parse().unwrap() {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        number(day, 2, 1..=days)
+    }
+    fn time(value: &str) -> bool {
+        let (time, fraction) = value.split_once('.').unwrap_or((value, "0"));
+        let parts: Vec<&str> = time.split(':').collect();
+        !fraction.is_empty()
+            && fraction.bytes().all(|byte| byte.is_ascii_digit())
+            && matches!(parts[..], [hour, minute, second] if number(hour, 2, 0..=23) && number(minute, 2, 0..=59) && number(second, 2, 0..=59))
+    }
+    
+    match kind {
+        TemporalKind::Date => date(value),
+        TemporalKind::Time => time(value),
+        TemporalKind::Timestamp => value.split_once([' ', 'T']).is_some_and(|(day, clock)| date(day) && time(clock)),
+    }
+}
+
+// The number of letters that have to be inserted, deleted, replaced or swapped
+// with their neighbors to turn one word into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `rows[i][j]` is the distance between the first i letters of `a` and the first j letters of `b`
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let replace = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + replace);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
         }
     }
+    rows[a.len()][b.len()]
 }
 
 // Helper function to parse a string into a Statement
@@ -552,4 +2918,23 @@ pub fn build_statement(input: &str) -> Result<Statement, String> {
     let tokenizer = crate::tokenizer::Tokenizer::new(input);
     let mut parser = Parser::new(tokenizer)?;
     parser.parse_statement()
+}
+
+// Like `build_statement`, but the error tells where in the input it was found
+pub fn build_located_statement(input: &str) -> Result<Statement, ParseError> {
+    let mut parser = Parser::new(Tokenizer::new(input)).map_err(|message| {
+        // The parser failed to read its first token, and took the tokenizer
+        // with it, so the token is read again to find where it is
+        let mut tokenizer = Tokenizer::new(input);
+        let _ = tokenizer.next_token();
+        ParseError { message, span: tokenizer.span() }
+    })?;
+    parser.parse_statement().map_err(|message| parser.locate(message))
+}
+
+// Helper function to parse a string with any number of statements
+pub fn build_statements(input: &str) -> Result<Vec<Statement>, String> {
+    let tokenizer = crate::tokenizer::Tokenizer::new(input);
+    let mut parser = Parser::new(tokenizer)?;
+    parser.parse_statements()
 }
\ No newline at end of file
@@ -0,0 +1,235 @@
+use crate::messages::Message;
+use crate::statement::{
+    CommonTableExpression, Constraint, Expression, InsertSource, Placeholder, Statement, TableColumn, TableConstraint, TableReference,
+    WindowSpecification,
+};
+
+/// A node of the syntax tree found by a path query (see `Statement::query`).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Node<'a> {
+    Statement(&'a Statement),
+    Expression(&'a Expression),
+    TableReference(&'a TableReference),
+    CommonTableExpression(&'a CommonTableExpression),
+    TableColumn(&'a TableColumn),
+    WindowSpecification(&'a WindowSpecification),
+}
+
+impl<'a> Node<'a> {
+    // The name of the variant (or the struct) of the node, like `Select` or `Identifier`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Node::Statement(statement) => match statement {
+                Statement::Select { .. } => "Select",
+                Statement::CreateTable { .. } => "CreateTable",
+                Statement::CreateView { .. } => "CreateView",
+                Statement::Comment { .. } => "Comment",
+                Statement::Insert { .. } => "Insert",
+                Statement::DropTable { .. } => "DropTable",
+                Statement::Custom { .. } => "Custom",
+            },
+            Node::Expression(expression) => match expression {
+                Expression::BinaryOperation { .. } => "BinaryOperation",
+                Expression::UnaryOperation { .. } => "UnaryOperation",
+                Expression::Number(_) => "Number",
+                Expression::Float(_) => "Float",
+                Expression::BitString(_) => "BitString",
+                Expression::Placeholder(_) => "Placeholder",
+                Expression::Temporal { .. } => "Temporal",
+                Expression::Bool(_) => "Bool",
+                Expression::Null => "Null",
+                Expression::Identifier(_) => "Identifier",
+                Expression::CompoundIdentifier(_) => "CompoundIdentifier",
+                Expression::String(_) => "String",
+                Expression::Wildcard => "Wildcard",
+                Expression::QualifiedWildcard(_) => "QualifiedWildcard",
+                Expression::Tuple(_) => "Tuple",
+                Expression::Subquery(_) => "Subquery",
+                Expression::InSubquery { .. } => "InSubquery",
+                Expression::InList { .. } => "InList",
+                Expression::Quantified { .. } => "Quantified",
+                Expression::Exists { .. } => "Exists",
+                Expression::Like { .. } => "Like",
+                Expression::IsNull { .. } => "IsNull",
+                Expression::Cast { .. } => "Cast",
+                Expression::Extract { .. } => "Extract",
+                Expression::Function { .. } => "Function",
+            },
+            Node::TableReference(reference) => match reference {
+                TableReference::Table { .. } => "Table",
+                TableReference::Derived { .. } => "Derived",
+                TableReference::Join { .. } => "Join",
+            },
+            Node::CommonTableExpression(_) => "CommonTableExpression",
+            Node::TableColumn(_) => "TableColumn",
+            Node::WindowSpecification(_) => "WindowSpecification",
+        }
+    }
+
+    // The direct children of the node, each with the name of the field it is
+    // stored in. The elements of a list all carry the name of the list.
+    fn children(&self) -> Vec<(&'static str, Node<'a>)> {
+        let mut children = Vec::new();
+        match *self {
+            Node::Statement(statement) => match statement {
+                Statement::Select { with, columns, from, r#where, orderby, limit, .. } => {
+                    children.extend(with.iter().map(|cte| ("with", Node::CommonTableExpression(cte))));
+                    children.extend(columns.iter().map(|column| ("columns", Node::Expression(column))));
+                    children.extend(from.iter().map(|reference| ("from", Node::TableReference(reference))));
+                    children.extend(r#where.iter().map(|condition| ("where", Node::Expression(condition))));
+                    children.extend(orderby.iter().map(|expression| ("orderby", Node::Expression(expression))));
+                    children.extend(limit.iter().map(|limit| ("limit", Node::Expression(limit))));
+                },
+                Statement::CreateTable { column_list, constraints, options, partitioning, .. } => {
+                    children.extend(column_list.iter().map(|column| ("column_list", Node::TableColumn(column))));
+                    for constraint in constraints {
+                        if let TableConstraint::Check { expression, .. } = constraint {
+                            children.push(("check", Node::Expression(expression)));
+                        }
+                    }
+                    children.extend(options.iter().map(|(_, value)| ("options", Node::Expression(value))));
+                    let partition_key = partitioning.iter().flat_map(|partitioning| &partitioning.expressions);
+                    children.extend(partition_key.map(|expression| ("partitioning", Node::Expression(expression))));
+                },
+                Statement::CreateView { query, .. } => children.push(("query", Node::Statement(query))),
+                Statement::Comment { .. } | Statement::DropTable { .. } | Statement::Custom { .. } => {},
+                Statement::Insert { source, returning, .. } => {
+                    match source {
+                        InsertSource::Values(rows) => children.extend(rows.iter().flatten().map(|value| ("values", Node::Expression(value)))),
+                        InsertSource::Query(query) => children.push(("query", Node::Statement(query))),
+                    }
+                    children.extend(returning.iter().map(|item| ("returning", Node::Expression(&item.expression))));
+                },
+            },
+            Node::Expression(expression) => match expression {
+                Expression::BinaryOperation { left_operand, right_operand, .. } => {
+                    children.push(("left_operand", Node::Expression(left_operand)));
+                    children.push(("right_operand", Node::Expression(right_operand)));
+                },
+                Expression::UnaryOperation { operand, .. } => children.push(("operand", Node::Expression(operand))),
+                Expression::Subquery(subquery)
+                | Expression::Exists { subquery, .. }
+                | Expression::Quantified { subquery, .. } => {
+                    children.push(("subquery", Node::Statement(subquery)));
+                },
+                Expression::InSubquery { expression, subquery } => {
+                    children.push(("expression", Node::Expression(expression)));
+                    children.push(("subquery", Node::Statement(subquery)));
+                },
+                Expression::Tuple(elements) => {
+                    children.extend(elements.iter().map(|element| ("elements", Node::Expression(element))));
+                },
+                Expression::InList { expression, list, .. } => {
+                    children.push(("expression", Node::Expression(expression)));
+                    children.extend(list.iter().map(|value| ("list", Node::Expression(value))));
+                },
+                Expression::Like { expression, pattern, .. } => {
+                    children.push(("expression", Node::Expression(expression)));
+                    children.push(("pattern", Node::Expression(pattern)));
+                },
+                Expression::IsNull { expression, .. }
+                | Expression::Cast { expression, .. }
+                | Expression::Extract { expression, .. } => {
+                    children.push(("expression", Node::Expression(expression)));
+                },
+                Expression::Function { arguments, over, .. } => {
+                    children.extend(arguments.iter().map(|argument| ("arguments", Node::Expression(argument))));
+                    children.extend(over.iter().map(|over| ("over", Node::WindowSpecification(over))));
+                },
+                Expression::Number(_) | Expression::Float(_) | Expression::BitString(_) | Expression::Bool(_) | Expression::Null | Expression::String(_)
+                | Expression::Placeholder(_) | Expression::Temporal { .. } | Expression::Identifier(_)
+                | Expression::CompoundIdentifier(_) | Expression::Wildcard | Expression::QualifiedWildcard(_) => {},
+            },
+            Node::TableReference(reference) => match reference {
+                TableReference::Table { .. } => {},
+                TableReference::Derived { subquery, .. } => children.push(("subquery", Node::Statement(subquery))),
+                TableReference::Join { left, right, on, .. } => {
+                    children.push(("left", Node::TableReference(left)));
+                    children.push(("right", Node::TableReference(right)));
+                    children.push(("on", Node::Expression(on)));
+                },
+            },
+            Node::CommonTableExpression(cte) => children.push(("query", Node::Statement(&cte.query))),
+            Node::TableColumn(column) => {
+                for constraint in &column.constraints {
+                    match constraint {
+                        Constraint::Check(check) => children.push(("check", Node::Expression(check))),
+                        Constraint::Default(value) => children.push(("default", Node::Expression(value))),
+                        _ => {},
+                    }
+                }
+            },
+            Node::WindowSpecification(window) => {
+                children.extend(window.partition_by.iter().map(|expression| ("partition_by", Node::Expression(expression))));
+                children.extend(window.order_by.iter().map(|expression| ("order_by", Node::Expression(expression))));
+            },
+        }
+        children
+    }
+}
+
+impl Statement {
+    /// Path queries find nodes of a statement without writing a `Transformer` or a `match` over the whole tree.
+    ///
+    /// Every node is reached through a chain of names: the kind of the statement, then for every step down the name of the field and the kind of the node stored in it. An identifier in the condition `WHERE age > 18` has the chain `Select.where.BinaryOperation.left_operand.Identifier`. A path is a dot-separated pattern for these chains, where `*` stands for any single name and `**` for any number of names (including none). Names are compared case-insensitively. A node matches when the path matches its whole chain, or its chain without the final kind, so a path can also end with a field name:
+    /// * `select.where` – the condition of the query
+    /// * `select.where.**.Identifier` – every column referenced in the condition
+    /// * `**.Function` – every function call, wherever it is
+    /// * `select.columns.*` – every column of the select list
+    ///
+    /// Nodes are returned in the order they appear in the statement, an enclosing node before the nodes inside of it.
+    pub fn query(&self, path: &str) -> Result<Vec<Node<'_>>, Message> {
+        let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(Message::InvalidPath { path: path.to_string() });
+        }
+        let mut found = Vec::new();
+        let mut chain = Vec::new();
+        collect(Node::Statement(self), None, &segments, &mut chain, &mut found);
+        Ok(found)
+    }
+
+    // The parameters of the statement (`?`, `$1`, `:name`), in the order they
+    // appear in. A parameter used several times is listed every time.
+    pub fn parameters(&self) -> Vec<&Placeholder> {
+        let nodes = self.query("**.Placeholder").unwrap_or_default();
+        nodes
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Expression(Expression::Placeholder(placeholder)) => Some(placeholder),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn collect<'a>(
+    node: Node<'a>,
+    field: Option<&'static str>,
+    segments: &[&str],
+    chain: &mut Vec<&'static str>,
+    found: &mut Vec<Node<'a>>,
+) {
+    let depth = chain.len();
+    chain.extend(field);
+    let reached_by_field = field.is_some() && matches(segments, chain);
+    chain.push(node.kind());
+    if reached_by_field || matches(segments, chain) {
+        found.push(node);
+    }
+    for (field, child) in node.children() {
+        collect(child, Some(field), segments, chain, found);
+    }
+    chain.truncate(depth);
+}
+
+fn matches(segments: &[&str], chain: &[&str]) -> bool {
+    match segments.split_first() {
+        None => chain.is_empty(),
+        Some((&"**", rest)) => (0..=chain.len()).any(|skipped| matches(rest, &chain[skipped..])),
+        Some((segment, rest)) => match chain.split_first() {
+            Some((name, chain)) => (*segment == "*" || segment.eq_ignore_ascii_case(name)) && matches(rest, chain),
+            None => false,
+        },
+    }
+}
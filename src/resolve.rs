@@ -0,0 +1,360 @@
+use crate::catalog::Catalog;
+use crate::messages::Message;
+use crate::statement::{CommentTarget, Constraint, Expression, InsertSource, Statement, TableConstraint, TableReference};
+
+/// A column reference that was bound to the table it comes from. `table` is the name under which the table is visible in the statement (its alias, if it has one).
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnBinding {
+    pub table: String,
+    pub column: String,
+}
+
+/// The result of the resolve pass: the original statement annotated with the binding of every column reference in it. Bindings are listed in the order the references are resolved: first the references inside common table expressions and derived tables, then the select list, join conditions, `WHERE` and `ORDER BY` (or the `CHECK` constraints of a `CREATE TABLE`).
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolvedStatement {
+    pub statement: Statement,
+    pub bindings: Vec<ColumnBinding>,
+}
+
+// The tables that are visible while resolving a statement, with the names of
+// their columns. Subqueries in expressions can also see the tables of the
+// queries they are nested in, through the parent scope.
+#[derive(Default)]
+struct Scope<'p> {
+    tables: Vec<(String, Vec<String>)>,
+    parent: Option<&'p Scope<'p>>,
+}
+
+impl<'p> Scope<'p> {
+    fn with_parent(parent: Option<&'p Scope<'p>>) -> Self {
+        Self {
+            tables: Vec::new(),
+            parent,
+        }
+    }
+
+    fn add_table(&mut self, name: &str, columns: Vec<String>) -> Result<(), Message> {
+        if self.tables.iter().any(|(visible, _)| visible.eq_ignore_ascii_case(name)) {
+            return Err(Message::DuplicateTableInFrom { name: name.to_string() });
+        }
+        self.tables.push((name.to_string(), columns));
+        Ok(())
+    }
+
+    fn all_columns(&self) -> impl Iterator<Item = String> + '_ {
+        self.tables.iter().flat_map(|(_, columns)| columns.iter().cloned())
+    }
+
+    // Adds every table of the FROM clause to the scope, collecting the join
+    // conditions so that they can be resolved once all tables are visible
+    fn collect_tables<'a>(
+        &mut self,
+        reference: &'a TableReference,
+        catalog: &Catalog,
+        ctes: &[(String, Vec<String>)],
+        conditions: &mut Vec<&'a Expression>,
+        bindings: &mut Vec<ColumnBinding>,
+    ) -> Result<(), Message> {
+        match reference {
+            TableReference::Table { name, alias } => {
+                let visible_name = alias.as_deref().unwrap_or(name.base_name());
+                let qualified_name = name.to_string();
+                // Common table expressions shadow the tables of the catalog,
+                // and are never qualified with a schema
+                if let Some((_, columns)) = ctes.iter().rev().find(|(cte, _)| !name.is_qualified() && cte.eq_ignore_ascii_case(&qualified_name)) {
+                    self.add_table(visible_name, columns.clone())
+                } else if let Some(columns) = catalog.table(&qualified_name) {
+                    let columns = columns.iter().map(|column| column.column_name.clone()).collect();
+                    self.add_table(visible_name, columns)
+                } else if let Some(query) = catalog.view(&qualified_name) {
+                    // The references inside of a view are not part of this statement
+                    let columns = resolve_query(query, catalog, &[], None, &mut Vec::new())?;
+                    self.add_table(visible_name, columns)
+                } else {
+                    Err(Message::UnknownTable { name: qualified_name })
+                }
+            },
+            TableReference::Derived { subquery, alias } => {
+                // A derived table can not see the other tables of the same FROM clause
+                let columns = resolve_query(subquery, catalog, ctes, self.parent, bindings)?;
+                self.add_table(alias, columns)
+            },
+            TableReference::Join { left, right, on, .. } => {
+                self.collect_tables(left, catalog, ctes, conditions, bindings)?;
+                self.collect_tables(right, catalog, ctes, conditions, bindings)?;
+                conditions.push(on);
+                Ok(())
+            },
+        }
+    }
+
+    fn bind_column(&self, column: &str) -> Result<ColumnBinding, Message> {
+        let candidates: Vec<&str> = self
+            .tables
+            .iter()
+            .filter(|(_, columns)| columns.iter().any(|c| c.eq_ignore_ascii_case(column)))
+            .map(|(table, _)| table.as_str())
+            .collect();
+        
+        match candidates.as_slice() {
+            [] => match self.parent {
+                Some(parent) => parent.bind_column(column),
+                None => Err(Message::UnknownColumn { name: column.to_string() }),
+            },
+            [table] => Ok(ColumnBinding {
+                table: table.to_string(),
+                column: column.to_string(),
+            }),
+            _ => Err(Message::AmbiguousColumn {
+                name: column.to_string(),
+                tables: candidates.iter().map(|table| table.to_string()).collect(),
+            }),
+        }
+    }
+
+    // Binds a column qualified with the name of the table it belongs to
+    fn bind_qualified_column(&self, table: &str, column: &str) -> Result<ColumnBinding, Message> {
+        let Some((table, columns)) = self.tables.iter().find(|(name, _)| name.eq_ignore_ascii_case(table)) else {
+            return match self.parent {
+                Some(parent) => parent.bind_qualified_column(table, column),
+                None => Err(Message::UnknownTable { name: table.to_string() }),
+            };
+        };
+        if !columns.iter().any(|c| c.eq_ignore_ascii_case(column)) {
+            return Err(Message::UnknownColumn { name: format!("{}.{}", table, column) });
+        }
+        Ok(ColumnBinding {
+            table: table.clone(),
+            column: column.to_string(),
+        })
+    }
+
+    // The columns of a table, for expanding its qualified wildcard
+    fn columns_of(&self, table: &str) -> Result<&[String], Message> {
+        match self.tables.iter().find(|(name, _)| name.eq_ignore_ascii_case(table)) {
+            Some((_, columns)) => Ok(columns),
+            None => match self.parent {
+                Some(parent) => parent.columns_of(table),
+                None => Err(Message::UnknownTable { name: table.to_string() }),
+            },
+        }
+    }
+
+    fn resolve_expression(
+        &self,
+        expression: &Expression,
+        catalog: &Catalog,
+        ctes: &[(String, Vec<String>)],
+        bindings: &mut Vec<ColumnBinding>,
+    ) -> Result<(), Message> {
+        match expression {
+            Expression::BinaryOperation { left_operand, right_operand, .. } => {
+                self.resolve_expression(left_operand, catalog, ctes, bindings)?;
+                self.resolve_expression(right_operand, catalog, ctes, bindings)
+            },
+            Expression::UnaryOperation { operand, .. } => self.resolve_expression(operand, catalog, ctes, bindings),
+            Expression::Identifier(column) => {
+                bindings.push(self.bind_column(column)?);
+                Ok(())
+            },
+            Expression::CompoundIdentifier(parts) => match parts.as_slice() {
+                [table, column] => {
+                    bindings.push(self.bind_qualified_column(table, column)?);
+                    Ok(())
+                },
+                // Tables are only visible under the last part of their name, so
+                // a column qualified with a schema can not refer to any of them
+                _ => Err(Message::UnknownColumn { name: parts.join(".") }),
+            },
+            Expression::Subquery(subquery)
+            | Expression::Exists { subquery, .. }
+            | Expression::Quantified { subquery, .. } => {
+                resolve_query(subquery, catalog, ctes, Some(self), bindings)?;
+                Ok(())
+            },
+            Expression::Tuple(elements) => {
+                for element in elements {
+                    self.resolve_expression(element, catalog, ctes, bindings)?;
+                }
+                Ok(())
+            },
+            Expression::InList { expression, list, .. } => {
+                for expression in std::iter::once(&**expression).chain(list) {
+                    self.resolve_expression(expression, catalog, ctes, bindings)?;
+                }
+                Ok(())
+            },
+            Expression::Like { expression, pattern, .. } => {
+                self.resolve_expression(expression, catalog, ctes, bindings)?;
+                self.resolve_expression(pattern, catalog, ctes, bindings)
+            },
+            Expression::IsNull { expression, .. }
+            | Expression::Cast { expression, .. }
+            | Expression::Extract { expression, .. } => {
+                self.resolve_expression(expression, catalog, ctes, bindings)
+            },
+            Expression::InSubquery { expression, subquery } => {
+                self.resolve_expression(expression, catalog, ctes, bindings)?;
+                resolve_query(subquery, catalog, ctes, Some(self), bindings)?;
+                Ok(())
+            },
+            Expression::Function { arguments, over, .. } => {
+                let window = over.iter().flat_map(|over| over.partition_by.iter().chain(&over.order_by));
+                for expression in arguments.iter().chain(window) {
+                    self.resolve_expression(expression, catalog, ctes, bindings)?;
+                }
+                Ok(())
+            },
+            Expression::QualifiedWildcard(table) => self.columns_of(table).map(|_| ()),
+            Expression::Number(_) | Expression::Float(_) | Expression::BitString(_) | Expression::Bool(_) | Expression::Null | Expression::String(_)
+            | Expression::Placeholder(_) | Expression::Temporal { .. } | Expression::Wildcard => Ok(()),
+        }
+    }
+}
+
+// Resolves a SELECT query and returns the names of the columns it produces.
+// Columns that are computed by an expression have no name and can not be
+// referenced from the outside.
+// The common table expressions visible to the query are passed in `ctes`,
+// and the scope of the query it is nested in (if any) in `outer`.
+fn resolve_query(
+    query: &Statement,
+    catalog: &Catalog,
+    ctes: &[(String, Vec<String>)],
+    outer: Option<&Scope>,
+    bindings: &mut Vec<ColumnBinding>,
+) -> Result<Vec<String>, Message> {
+    let Statement::Select { with, columns, from, r#where, orderby, .. } = query else {
+        return Err(Message::ExpectedQuery);
+    };
+    
+    // Every common table expression can use the ones defined before it
+    let mut ctes = ctes.to_vec();
+    for cte in with {
+        let columns = resolve_query(&cte.query, catalog, &ctes, outer, bindings)?;
+        ctes.push((cte.name.clone(), columns));
+    }
+    
+    let mut scope = Scope::with_parent(outer);
+    let mut conditions = Vec::new();
+    for reference in from {
+        scope.collect_tables(reference, catalog, &ctes, &mut conditions, bindings)?;
+    }
+    
+    for expression in columns.iter().chain(conditions).chain(r#where).chain(orderby) {
+        scope.resolve_expression(expression, catalog, &ctes, bindings)?;
+    }
+    
+    let mut output = Vec::new();
+    for column in columns {
+        match column {
+            Expression::Wildcard => output.extend(scope.all_columns()),
+            Expression::QualifiedWildcard(table) => output.extend(scope.columns_of(table)?.iter().cloned()),
+            Expression::Identifier(name) => output.push(name.clone()),
+            Expression::CompoundIdentifier(parts) => output.extend(parts.last().cloned()),
+            _ => {},
+        }
+    }
+    Ok(output)
+}
+
+// Binds every column reference of the statement to a table or view known by
+// the catalog, reporting unknown tables and columns and ambiguous references
+pub fn resolve(statement: Statement, catalog: &Catalog) -> Result<ResolvedStatement, Message> {
+    let mut bindings = Vec::new();
+    
+    match &statement {
+        Statement::Select { .. } => {
+            resolve_query(&statement, catalog, &[], None, &mut bindings)?;
+        },
+        Statement::CreateView { query, .. } => {
+            resolve_query(query, catalog, &[], None, &mut bindings)?;
+        },
+        Statement::CreateTable { table_name, column_list, constraints, partitioning, .. } => {
+            // CHECK constraints and the partition key can only refer to the
+            // columns of the table being created
+            let mut scope = Scope::default();
+            let columns = column_list.iter().map(|column| column.column_name.clone()).collect();
+            scope.add_table(table_name.base_name(), columns)?;
+            for column in column_list {
+                for constraint in &column.constraints {
+                    if let Constraint::Check(expression) = constraint {
+                        scope.resolve_expression(expression, catalog, &[], &mut bindings)?;
+                    }
+                }
+            }
+            for expression in partitioning.iter().flat_map(|partitioning| &partitioning.expressions) {
+                scope.resolve_expression(expression, catalog, &[], &mut bindings)?;
+            }
+            // The keys of the whole table list the columns they span
+            for constraint in constraints {
+                match constraint {
+                    TableConstraint::PrimaryKey { columns, .. } | TableConstraint::Unique { columns, .. } => {
+                        if let Some(unknown) = columns.iter().find(|name| !column_list.iter().any(|column| &column.column_name == *name)) {
+                            return Err(Message::UnknownColumn { name: unknown.clone() });
+                        }
+                    },
+                    TableConstraint::Check { expression, .. } => {
+                        scope.resolve_expression(expression, catalog, &[], &mut bindings)?;
+                    },
+                }
+            }
+        },
+        Statement::DropTable { table_name, if_exists: false } if catalog.table(&table_name.to_string()).is_none() => {
+            return Err(Message::UnknownTable { name: table_name.to_string() });
+        },
+        Statement::DropTable { .. } | Statement::Custom { .. } => {},
+        Statement::Comment { target, .. } => match target {
+            CommentTarget::Table(name) if catalog.table(&name.to_string()).is_none() => {
+                return Err(Message::UnknownTable { name: name.to_string() });
+            },
+            CommentTarget::View(name) if catalog.view(&name.to_string()).is_none() => {
+                return Err(Message::UnknownView { name: name.to_string() });
+            },
+            CommentTarget::Table(_) | CommentTarget::View(_) => {},
+            CommentTarget::Column { table, column } => {
+                let columns = catalog.table(&table.to_string()).ok_or_else(|| Message::UnknownTable { name: table.to_string() })?;
+                let mut scope = Scope::default();
+                scope.add_table(table.base_name(), columns.iter().map(|column| column.column_name.clone()).collect())?;
+                let reference = Expression::CompoundIdentifier(vec![table.base_name().to_string(), column.clone()]);
+                scope.resolve_expression(&reference, catalog, &[], &mut bindings)?;
+            },
+        },
+        Statement::Insert { table_name, columns, source, returning } => {
+            // Rows can only be inserted into tables, not into views
+            let table = catalog.table(&table_name.to_string()).ok_or_else(|| Message::UnknownTable { name: table_name.to_string() })?;
+            let mut scope = Scope::default();
+            scope.add_table(table_name.base_name(), table.iter().map(|column| column.column_name.clone()).collect())?;
+            for column in columns {
+                bindings.push(scope.bind_qualified_column(table_name.base_name(), column)?);
+            }
+            let expected = if columns.is_empty() { table.len() } else { columns.len() };
+            match source {
+                // The values can not refer to the columns of the table, only
+                // subqueries in them can read other tables
+                InsertSource::Values(rows) => {
+                    let values_scope = Scope::default();
+                    for row in rows {
+                        if row.len() != expected {
+                            return Err(Message::InsertValueCount { expected, found: row.len() });
+                        }
+                        for expression in row {
+                            values_scope.resolve_expression(expression, catalog, &[], &mut bindings)?;
+                        }
+                    }
+                },
+                // Computed columns of the query have no name, so the number of
+                // columns it produces is not checked
+                InsertSource::Query(query) => {
+                    resolve_query(query, catalog, &[], None, &mut bindings)?;
+                },
+            }
+            // The returned values are computed from the columns of the new row
+            for item in returning {
+                scope.resolve_expression(&item.expression, catalog, &[], &mut bindings)?;
+            }
+        },
+    }
+    
+    Ok(ResolvedStatement { statement, bindings })
+}
@@ -0,0 +1,157 @@
+use crate::messages::Message;
+use crate::rewrite::{walk_statement, walk_table_reference, Transformer};
+use crate::statement::{CommonTableExpression, ObjectName, Statement, TableReference};
+
+// Counts the table references to the given name. A name qualified with a
+// schema never refers to a common table expression.
+struct ReferenceCounter<'a> {
+    name: &'a str,
+    count: usize,
+}
+
+impl Transformer for ReferenceCounter<'_> {
+    fn transform_table_reference(&mut self, reference: TableReference) -> Result<TableReference, Message> {
+        if let TableReference::Table { name, .. } = &reference {
+            if !name.is_qualified() && name.base_name().eq_ignore_ascii_case(self.name) {
+                self.count += 1;
+            }
+        }
+        walk_table_reference(self, reference)
+    }
+}
+
+// Replaces the table references to the given name with another table
+// reference, built out of the name the replaced reference was visible under
+struct ReferenceReplacer<'a> {
+    name: &'a str,
+    replacement: &'a dyn Fn(String) -> TableReference,
+}
+
+impl Transformer for ReferenceReplacer<'_> {
+    fn transform_table_reference(&mut self, reference: TableReference) -> Result<TableReference, Message> {
+        match reference {
+            TableReference::Table { name, alias } if !name.is_qualified() && name.base_name().eq_ignore_ascii_case(self.name) => {
+                Ok((self.replacement)(alias.unwrap_or_else(|| name.base_name().to_string())))
+            },
+            reference => walk_table_reference(self, reference),
+        }
+    }
+}
+
+fn count_references(statements: &[Statement], name: &str) -> Result<usize, Message> {
+    let mut counter = ReferenceCounter { name, count: 0 };
+    for statement in statements {
+        counter.transform_statement(statement.clone())?;
+    }
+    Ok(counter.count)
+}
+
+fn replace_references(
+    statements: &mut [Statement],
+    name: &str,
+    replacement: &dyn Fn(String) -> TableReference,
+) -> Result<(), Message> {
+    let mut replacer = ReferenceReplacer { name, replacement };
+    for statement in statements.iter_mut() {
+        let replaced = replacer.transform_statement(statement.clone())?;
+        *statement = replaced;
+    }
+    Ok(())
+}
+
+// Simplifies the WITH clause of one query. The common table expressions and
+// the query itself are kept in one vector, so that "the statements after the
+// i-th common table expression" (the only ones that can refer to it) is just
+// the tail of the vector.
+fn inline_query(with: Vec<CommonTableExpression>, body: Statement) -> Result<Statement, Message> {
+    let mut names = Vec::new();
+    let mut parts = Vec::new();
+    for cte in with {
+        names.push(cte.name);
+        parts.push(cte.query);
+    }
+    parts.push(body);
+    let mut kept = vec![true; names.len()];
+    
+    // A common table expression with the same body as an earlier one is
+    // dropped, and its references point to the earlier one instead
+    for i in 0..names.len() {
+        if let Some(j) = (0..i).find(|&j| kept[j] && parts[j] == parts[i]) {
+            kept[i] = false;
+            // The references keep their visible name, so that the columns
+            // qualified with it are still valid
+            let canonical = names[j].clone();
+            let replacement = |visible_name: String| TableReference::Table {
+                name: ObjectName::new(&canonical),
+                alias: Some(visible_name),
+            };
+            replace_references(&mut parts[i + 1..], &names[i], &replacement)?;
+        }
+    }
+    
+    // A common table expression used exactly once is moved into the place
+    // where it is used, as a derived table visible under the same name. Unused
+    // ones are dropped.
+    for i in 0..names.len() {
+        if !kept[i] {
+            continue;
+        }
+        match count_references(&parts[i + 1..], &names[i])? {
+            0 => kept[i] = false,
+            1 => {
+                kept[i] = false;
+                let subquery = parts[i].clone();
+                let replacement = |visible_name: String| TableReference::Derived {
+                    subquery: Box::new(subquery.clone()),
+                    alias: visible_name,
+                };
+                replace_references(&mut parts[i + 1..], &names[i], &replacement)?;
+            },
+            _ => {},
+        }
+    }
+    
+    let body = parts.pop().expect("the query itself is always the last part");
+    let with: Vec<CommonTableExpression> = names
+        .into_iter()
+        .zip(parts)
+        .zip(kept)
+        .filter(|(_, kept)| *kept)
+        .map(|((name, query), _)| CommonTableExpression { name, query })
+        .collect();
+    
+    match body {
+        Statement::Select { columns, from, r#where, orderby, limit, lock, .. } => Ok(Statement::Select {
+            with,
+            columns,
+            from,
+            r#where,
+            orderby,
+            limit,
+            lock,
+        }),
+        _ => Err(Message::ExpectedQuery),
+    }
+}
+
+// Simplifies the WITH clauses of every query, starting from the innermost ones
+struct CteInliner;
+
+impl Transformer for CteInliner {
+    fn transform_statement(&mut self, statement: Statement) -> Result<Statement, Message> {
+        match walk_statement(self, statement)? {
+            Statement::Select { with, columns, from, r#where, orderby, limit, lock } if !with.is_empty() => {
+                let body = Statement::Select { with: Vec::new(), columns, from, r#where, orderby, limit, lock };
+                inline_query(with, body)
+            },
+            statement => Ok(statement),
+        }
+    }
+}
+
+// Inlines common table expressions that are used only once as derived tables,
+// merges common table expressions with identical bodies, and drops the unused
+// ones. Common table expressions used more than once stay in the WITH clause.
+pub fn inline_ctes(statement: Statement) -> Result<Statement, Message> {
+    CteInliner.transform_statement(statement)
+}
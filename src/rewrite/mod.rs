@@ -0,0 +1,199 @@
+pub mod ctes;
+pub mod views;
+
+use crate::messages::Message;
+use crate::statement::{
+    CommonTableExpression, Constraint, Expression, InsertSource, Partitioning, ReturningItem, Statement, TableColumn, TableConstraint, TableReference,
+    WindowSpecification,
+};
+
+/// A rewrite of the syntax tree. Every method takes a node by value and returns the node that replaces it. The default implementations only rebuild the node out of its transformed children (using the `walk_*` functions), so a transformer overrides just the methods for the nodes it is interested in, and calls the matching `walk_*` function when it wants to keep descending.
+pub trait Transformer {
+    fn transform_statement(&mut self, statement: Statement) -> Result<Statement, Message> {
+        walk_statement(self, statement)
+    }
+
+    fn transform_table_reference(&mut self, reference: TableReference) -> Result<TableReference, Message> {
+        walk_table_reference(self, reference)
+    }
+
+    fn transform_expression(&mut self, expression: Expression) -> Result<Expression, Message> {
+        walk_expression(self, expression)
+    }
+}
+
+pub fn walk_statement<T: Transformer + ?Sized>(transformer: &mut T, statement: Statement) -> Result<Statement, Message> {
+    match statement {
+        Statement::Select { with, columns, from, r#where, orderby, limit, lock } => Ok(Statement::Select {
+            with: with
+                .into_iter()
+                .map(|cte| {
+                    Ok(CommonTableExpression {
+                        name: cte.name,
+                        query: transformer.transform_statement(cte.query)?,
+                    })
+                })
+                .collect::<Result<Vec<CommonTableExpression>, Message>>()?,
+            columns: walk_expressions(transformer, columns)?,
+            from: from
+                .into_iter()
+                .map(|reference| transformer.transform_table_reference(reference))
+                .collect::<Result<Vec<TableReference>, Message>>()?,
+            r#where: r#where.map(|expression| transformer.transform_expression(expression)).transpose()?,
+            orderby: walk_expressions(transformer, orderby)?,
+            limit: limit.map(|expression| transformer.transform_expression(expression)).transpose()?,
+            lock,
+        }),
+        Statement::CreateTable { table_name, temporary, if_not_exists, column_list, constraints, options, partitioning } => {
+            let column_list = column_list
+                .into_iter()
+                .map(|column| {
+                    let constraints = column
+                        .constraints
+                        .into_iter()
+                        .map(|constraint| match constraint {
+                            Constraint::Check(expression) => Ok(Constraint::Check(transformer.transform_expression(expression)?)),
+                            Constraint::Default(expression) => Ok(Constraint::Default(transformer.transform_expression(expression)?)),
+                            constraint => Ok(constraint),
+                        })
+                        .collect::<Result<Vec<Constraint>, Message>>()?;
+                    Ok(TableColumn { constraints, ..column })
+                })
+                .collect::<Result<Vec<TableColumn>, Message>>()?;
+            let constraints = constraints
+                .into_iter()
+                .map(|constraint| match constraint {
+                    TableConstraint::Check { name, expression } => {
+                        Ok(TableConstraint::Check { name, expression: transformer.transform_expression(expression)? })
+                    },
+                    constraint => Ok(constraint),
+                })
+                .collect::<Result<Vec<TableConstraint>, Message>>()?;
+            let partitioning = partitioning
+                .map(|partitioning| {
+                    Ok(Partitioning {
+                        expressions: walk_expressions(transformer, partitioning.expressions)?,
+                        ..partitioning
+                    })
+                })
+                .transpose()?;
+            // The values of the table options are not expressions over the rows,
+            // so they are left as they are
+            Ok(Statement::CreateTable { table_name, temporary, if_not_exists, column_list, constraints, options, partitioning })
+        },
+        Statement::CreateView { name, or_replace, if_not_exists, query } => Ok(Statement::CreateView {
+            name,
+            or_replace,
+            if_not_exists,
+            query: Box::new(transformer.transform_statement(*query)?),
+        }),
+        Statement::Comment { target, text } => Ok(Statement::Comment { target, text }),
+        Statement::Insert { table_name, columns, source, returning } => {
+            let source = match source {
+                InsertSource::Values(rows) => InsertSource::Values(
+                    rows.into_iter()
+                        .map(|row| walk_expressions(transformer, row))
+                        .collect::<Result<Vec<Vec<Expression>>, Message>>()?,
+                ),
+                InsertSource::Query(query) => InsertSource::Query(Box::new(transformer.transform_statement(*query)?)),
+            };
+            let returning = returning
+                .into_iter()
+                .map(|item| Ok(ReturningItem { expression: transformer.transform_expression(item.expression)?, alias: item.alias }))
+                .collect::<Result<Vec<ReturningItem>, Message>>()?;
+            Ok(Statement::Insert { table_name, columns, source, returning })
+        },
+        Statement::DropTable { table_name, if_exists } => Ok(Statement::DropTable { table_name, if_exists }),
+        Statement::Custom { keyword, payload } => Ok(Statement::Custom { keyword, payload }),
+    }
+}
+
+pub fn walk_table_reference<T: Transformer + ?Sized>(transformer: &mut T, reference: TableReference) -> Result<TableReference, Message> {
+    match reference {
+        TableReference::Table { name, alias } => Ok(TableReference::Table { name, alias }),
+        TableReference::Derived { subquery, alias } => Ok(TableReference::Derived {
+            subquery: Box::new(transformer.transform_statement(*subquery)?),
+            alias,
+        }),
+        TableReference::Join { left, join_type, right, on } => Ok(TableReference::Join {
+            left: Box::new(transformer.transform_table_reference(*left)?),
+            join_type,
+            right: Box::new(transformer.transform_table_reference(*right)?),
+            on: transformer.transform_expression(on)?,
+        }),
+    }
+}
+
+pub fn walk_expression<T: Transformer + ?Sized>(transformer: &mut T, expression: Expression) -> Result<Expression, Message> {
+    match expression {
+        Expression::BinaryOperation { left_operand, operator, right_operand } => Ok(Expression::BinaryOperation {
+            left_operand: Box::new(transformer.transform_expression(*left_operand)?),
+            operator,
+            right_operand: Box::new(transformer.transform_expression(*right_operand)?),
+        }),
+        Expression::UnaryOperation { operand, operator } => Ok(Expression::UnaryOperation {
+            operand: Box::new(transformer.transform_expression(*operand)?),
+            operator,
+        }),
+        Expression::Subquery(subquery) => Ok(Expression::Subquery(Box::new(transformer.transform_statement(*subquery)?))),
+        Expression::InSubquery { expression, subquery } => Ok(Expression::InSubquery {
+            expression: Box::new(transformer.transform_expression(*expression)?),
+            subquery: Box::new(transformer.transform_statement(*subquery)?),
+        }),
+        Expression::Quantified { quantifier, subquery } => Ok(Expression::Quantified {
+            quantifier,
+            subquery: Box::new(transformer.transform_statement(*subquery)?),
+        }),
+        Expression::Exists { subquery, negated } => Ok(Expression::Exists {
+            subquery: Box::new(transformer.transform_statement(*subquery)?),
+            negated,
+        }),
+        Expression::Tuple(elements) => Ok(Expression::Tuple(walk_expressions(transformer, elements)?)),
+        Expression::InList { expression, list, negated } => Ok(Expression::InList {
+            expression: Box::new(transformer.transform_expression(*expression)?),
+            list: walk_expressions(transformer, list)?,
+            negated,
+        }),
+        Expression::Like { expression, pattern, negated, escape } => Ok(Expression::Like {
+            expression: Box::new(transformer.transform_expression(*expression)?),
+            pattern: Box::new(transformer.transform_expression(*pattern)?),
+            negated,
+            escape,
+        }),
+        Expression::IsNull { expression, negated } => Ok(Expression::IsNull {
+            expression: Box::new(transformer.transform_expression(*expression)?),
+            negated,
+        }),
+        Expression::Cast { expression, target_type } => Ok(Expression::Cast {
+            expression: Box::new(transformer.transform_expression(*expression)?),
+            target_type,
+        }),
+        Expression::Extract { field, expression } => Ok(Expression::Extract {
+            field,
+            expression: Box::new(transformer.transform_expression(*expression)?),
+        }),
+        Expression::Function { name, distinct, arguments, over } => Ok(Expression::Function {
+            name,
+            distinct,
+            arguments: walk_expressions(transformer, arguments)?,
+            over: over
+                .map(|over| {
+                    Ok(Box::new(WindowSpecification {
+                        partition_by: walk_expressions(transformer, over.partition_by)?,
+                        order_by: walk_expressions(transformer, over.order_by)?,
+                    }))
+                })
+                .transpose()?,
+        }),
+        Expression::Number(_) | Expression::Float(_) | Expression::BitString(_) | Expression::Bool(_) | Expression::Null | Expression::String(_)
+        | Expression::Placeholder(_) | Expression::Temporal { .. } | Expression::Identifier(_)
+        | Expression::CompoundIdentifier(_) | Expression::Wildcard | Expression::QualifiedWildcard(_) => Ok(expression),
+    }
+}
+
+fn walk_expressions<T: Transformer + ?Sized>(transformer: &mut T, expressions: Vec<Expression>) -> Result<Vec<Expression>, Message> {
+    expressions
+        .into_iter()
+        .map(|expression| transformer.transform_expression(expression))
+        .collect()
+}
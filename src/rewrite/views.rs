@@ -0,0 +1,47 @@
+use crate::catalog::Catalog;
+use crate::messages::Message;
+use crate::rewrite::{walk_table_reference, Transformer};
+use crate::statement::{Statement, TableReference};
+
+// Replaces references to views with the queries that define them
+struct ViewExpander<'a> {
+    catalog: &'a Catalog,
+    // Views that are currently being expanded, used to detect views that
+    // (indirectly) refer to themselves
+    expanding: Vec<String>,
+}
+
+impl Transformer for ViewExpander<'_> {
+    fn transform_table_reference(&mut self, reference: TableReference) -> Result<TableReference, Message> {
+        let TableReference::Table { name, alias } = reference else {
+            return walk_table_reference(self, reference);
+        };
+        let qualified_name = name.to_string();
+        let Some(query) = self.catalog.view(&qualified_name) else {
+            return Ok(TableReference::Table { name, alias });
+        };
+        
+        if self.expanding.iter().any(|view| view.eq_ignore_ascii_case(&qualified_name)) {
+            return Err(Message::RecursiveView { name: qualified_name });
+        }
+        
+        // Views used by the view definition are expanded as well
+        self.expanding.push(qualified_name);
+        let subquery = self.transform_statement(query.clone())?;
+        self.expanding.pop();
+        
+        // The derived table is visible under the same name as the view was
+        // (its alias, or the name of the view itself), so the rest of the
+        // statement keeps referring to it the same way
+        Ok(TableReference::Derived {
+            subquery: Box::new(subquery),
+            alias: alias.unwrap_or_else(|| name.base_name().to_string()),
+        })
+    }
+}
+
+// Inlines every view that the statement refers to as a derived table, so that
+// the statement only depends on real tables of the catalog
+pub fn expand_views(statement: Statement, catalog: &Catalog) -> Result<Statement, Message> {
+    ViewExpander { catalog, expanding: Vec::new() }.transform_statement(statement)
+}
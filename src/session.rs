@@ -0,0 +1,56 @@
+use crate::catalog::Catalog;
+use crate::messages::Message;
+use crate::statement::Statement;
+
+/// The state of an interactive session: the log of the statements that changed the schema so far, and the catalog they build. Any number of statements can be undone and redone. The catalog is never modified in place when undoing; instead it is derived again by replaying the log, so it is always exactly the state the remaining statements produce.
+#[derive(Debug, Default, Clone)]
+pub struct Session {
+    log: Vec<Statement>,
+    undone: Vec<Statement>,
+    catalog: Catalog,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Applies the statement to the state of the session. Statements that do not
+    // change the schema are not logged. A new change forgets the undone
+    // statements, so they can not be redone anymore.
+    pub fn execute(&mut self, statement: Statement) -> Result<(), Message> {
+        if !statement.changes_schema() {
+            return Ok(());
+        }
+        self.catalog.add_statement(&statement)?;
+        self.log.push(statement);
+        self.undone.clear();
+        Ok(())
+    }
+
+    // Takes back the last change and returns it, if there was one
+    pub fn undo(&mut self) -> Option<&Statement> {
+        let statement = self.log.pop()?;
+        self.undone.push(statement);
+        // Every statement of the log was accepted in this order before
+        self.catalog = Catalog::from_statements(&self.log).expect("the log replays without errors");
+        self.undone.last()
+    }
+
+    // Applies the last undone change again and returns it, if there was one
+    pub fn redo(&mut self) -> Option<&Statement> {
+        let statement = self.undone.pop()?;
+        // The catalog is in the same state as when the statement was first executed
+        self.catalog.add_statement(&statement).expect("an undone statement can be applied again");
+        self.log.push(statement);
+        self.log.last()
+    }
+
+    pub fn log(&self) -> &[Statement] {
+        &self.log
+    }
+
+    pub fn catalog(&self) -> &Catalog {
+        &self.catalog
+    }
+}
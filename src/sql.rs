@@ -0,0 +1,432 @@
+use crate::statement::{
+    BinaryOperator, CommentTarget, Constraint, DBType, Expression, InsertSource, JoinType, ObjectName, PartitionMethod, Placeholder, ReturningItem, Statement, TableColumn,
+    TableConstraint, TableReference, UnaryOperator, WindowSpecification,
+};
+use crate::tokenizer::STANDARD_KEYWORDS;
+
+// Renders a statement back into SQL of the generic dialect, on a single line
+// and with its semicolon, so that parsing the result gives the same statement.
+//
+// Only the parentheses the precedence of the operators needs are written, and
+// identifiers are quoted with backticks only when they are keywords or are not
+// plain words. A few things can not make the trip back unchanged:
+// - `^` is the power in PostgreSQL and the exclusive or everywhere else, and
+//   `REGEXP` and `UNSIGNED` are only read in MySQL;
+// - a custom type is only read with its keyword registered;
+// - a named placeholder is always written as `:name`, and a negative number
+//   is read back as the minus of a positive one;
+// - a string can not hold both kinds of quotes;
+// - the payload of a custom statement is written as its handler made it.
+pub(crate) fn statement(statement: &Statement) -> String {
+    match statement {
+        Statement::Select { .. } => format!("{};", GENERIC.query(statement)),
+        Statement::CreateTable { table_name, temporary, if_not_exists, column_list, constraints, options, partitioning } => {
+            let mut definitions: Vec<String> = column_list.iter().map(column_definition).collect();
+            definitions.extend(constraints.iter().map(|constraint| GENERIC.table_constraint(constraint)));
+            let mut sql = format!(
+                "CREATE {}TABLE {}{} ({})",
+                if *temporary { "TEMPORARY " } else { "" },
+                if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                GENERIC.object_name(table_name),
+                definitions.join(", ")
+            );
+            if !options.is_empty() {
+                let options: Vec<String> = options.iter().map(|(name, value)| format!("{} = {}", name, expression(value, 5))).collect();
+                sql.push_str(&format!(" WITH ({})", options.join(", ")));
+            }
+            if let Some(partitioning) = partitioning {
+                let method = match partitioning.method {
+                    PartitionMethod::Range => "RANGE",
+                    PartitionMethod::List => "LIST",
+                    PartitionMethod::Hash => "HASH",
+                    PartitionMethod::Key => "KEY",
+                };
+                sql.push_str(&format!(" PARTITION BY {} ({})", method, GENERIC.expressions(&partitioning.expressions)));
+            }
+            format!("{};", sql)
+        },
+        Statement::CreateView { name, or_replace, if_not_exists, query: view_query } => format!(
+            "CREATE {}VIEW {}{} AS {};",
+            if *or_replace { "OR REPLACE " } else { "" },
+            if *if_not_exists { "IF NOT EXISTS " } else { "" },
+            GENERIC.object_name(name),
+            GENERIC.query(view_query)
+        ),
+        Statement::Comment { target, text } => {
+            let target = match target {
+                CommentTarget::Table(name) => format!("TABLE {}", GENERIC.object_name(name)),
+                CommentTarget::View(name) => format!("VIEW {}", GENERIC.object_name(name)),
+                CommentTarget::Column { table, column } => format!("COLUMN {}.{}", GENERIC.object_name(table), identifier(column)),
+            };
+            let text = text.as_deref().map(string).unwrap_or_else(|| "NULL".to_string());
+            format!("COMMENT ON {} IS {};", target, text)
+        },
+        Statement::Insert { table_name, columns, source, returning } => {
+            let mut sql = format!("INSERT INTO {}", GENERIC.object_name(table_name));
+            if !columns.is_empty() {
+                sql.push_str(&format!(" ({})", GENERIC.identifiers(columns)));
+            }
+            match source {
+                InsertSource::Values(rows) => {
+                    let rows: Vec<String> = rows.iter().map(|row| format!("({})", GENERIC.expressions(row))).collect();
+                    sql.push_str(&format!(" VALUES {}", rows.join(", ")));
+                },
+                InsertSource::Query(source) => sql.push_str(&format!(" {}", GENERIC.query(source))),
+            }
+            if !returning.is_empty() {
+                sql.push_str(&format!(" RETURNING {}", GENERIC.returning(returning)));
+            }
+            format!("{};", sql)
+        },
+        Statement::DropTable { table_name, if_exists } => {
+            format!("DROP TABLE {}{};", if *if_exists { "IF EXISTS " } else { "" }, GENERIC.object_name(table_name))
+        },
+        Statement::Custom { keyword, payload } if payload.is_empty() => format!("{};", keyword),
+        Statement::Custom { keyword, payload } => format!("{} {};", keyword, payload),
+    }
+}
+
+fn column_definition(column: &TableColumn) -> String {
+    let mut definition = format!("{} {}", identifier(&column.column_name), db_type(&column.column_type));
+    for constraint in &column.constraints {
+        let constraint = match constraint {
+            Constraint::PrimaryKey => "PRIMARY KEY".to_string(),
+            Constraint::NotNull => "NOT NULL".to_string(),
+            Constraint::Null => "NULL".to_string(),
+            Constraint::Check(check) => format!("CHECK ({})", expression(check, 0)),
+            Constraint::Comment(text) => format!("COMMENT {}", string(text)),
+            // The value is read without comparisons, so that the NOT of a
+            // following NOT NULL is not taken for an operator
+            Constraint::Default(value) => format!("DEFAULT {}", expression(value, 5)),
+            Constraint::AutoIncrement => "AUTO_INCREMENT".to_string(),
+            Constraint::Identity { always: true } => "GENERATED ALWAYS AS IDENTITY".to_string(),
+            Constraint::Identity { always: false } => "GENERATED BY DEFAULT AS IDENTITY".to_string(),
+        };
+        definition.push(' ');
+        definition.push_str(&constraint);
+    }
+    definition
+}
+
+pub(crate) fn db_type(column_type: &DBType) -> String {
+    let integer = |name: &str, unsigned: bool| if unsigned { format!("{} UNSIGNED", name) } else { name.to_string() };
+    match column_type {
+        DBType::Int { unsigned } => integer("INT", *unsigned),
+        DBType::SmallInt { unsigned } => integer("SMALLINT", *unsigned),
+        DBType::BigInt { unsigned } => integer("BIGINT", *unsigned),
+        DBType::TinyInt { unsigned } => integer("TINYINT", *unsigned),
+        DBType::Varchar(None) => "VARCHAR".to_string(),
+        DBType::Varchar(Some(length)) => format!("VARCHAR({})", length),
+        DBType::Bool => "BOOL".to_string(),
+        DBType::Decimal { precision: None, .. } => "DECIMAL".to_string(),
+        DBType::Decimal { precision: Some(precision), scale: None } => format!("DECIMAL({})", precision),
+        DBType::Decimal { precision: Some(precision), scale: Some(scale) } => format!("DECIMAL({}, {})", precision, scale),
+        DBType::Float => "FLOAT".to_string(),
+        DBType::Real => "REAL".to_string(),
+        DBType::Double => "DOUBLE PRECISION".to_string(),
+        DBType::Date => "DATE".to_string(),
+        DBType::Time => "TIME".to_string(),
+        DBType::Timestamp { with_time_zone: false } => "TIMESTAMP".to_string(),
+        DBType::Timestamp { with_time_zone: true } => "TIMESTAMP WITH TIME ZONE".to_string(),
+        DBType::DateTime => "DATETIME".to_string(),
+        DBType::Blob => "BLOB".to_string(),
+        DBType::Uuid => "UUID".to_string(),
+        DBType::Serial => "SERIAL".to_string(),
+        DBType::BigSerial => "BIGSERIAL".to_string(),
+        DBType::Custom(name) => name.clone(),
+    }
+}
+
+// The clauses are laid out the same in every dialect this crate writes, the
+// dialects only differ in how they quote names and write expressions
+pub(crate) struct Clauses {
+    pub(crate) identifier: fn(&str) -> String,
+    // Renders an expression that is an operand of an operator with the given
+    // precedence, as `expression` does
+    pub(crate) expression: fn(&Expression, u8) -> String,
+    // Renders a whole statement, for the statements nested where a query goes
+    pub(crate) statement: fn(&Statement) -> String,
+    // Whether the locking clause is written at the end of a query
+    pub(crate) locking: bool,
+}
+
+pub(crate) const GENERIC: Clauses = Clauses { identifier, expression, statement, locking: true };
+
+impl Clauses {
+    // Renders a SELECT query without the terminating semicolon
+    pub(crate) fn query(&self, statement: &Statement) -> String {
+        let Statement::Select { with, columns, from, r#where, orderby, limit, lock } = statement else {
+            // Only queries can be nested into other statements
+            return (self.statement)(statement);
+        };
+
+        let mut sql = String::new();
+        if !with.is_empty() {
+            let ctes: Vec<String> = with
+                .iter()
+                .map(|cte| format!("{} AS ({})", (self.identifier)(&cte.name), self.query(&cte.query)))
+                .collect();
+            sql.push_str(&format!("WITH {} ", ctes.join(", ")));
+        }
+        sql.push_str(&format!("SELECT {}", self.expressions(columns)));
+        if !from.is_empty() {
+            let tables: Vec<String> = from.iter().map(|table| self.table_reference(table)).collect();
+            sql.push_str(&format!(" FROM {}", tables.join(", ")));
+        }
+        if let Some(condition) = r#where {
+            sql.push_str(&format!(" WHERE {}", (self.expression)(condition, 0)));
+        }
+        if !orderby.is_empty() {
+            sql.push_str(&format!(" ORDER BY {}", self.expressions(orderby)));
+        }
+        // The TOP of SQL Server is kept as a LIMIT, which means the same
+        if let Some(limit) = limit {
+            sql.push_str(&format!(" LIMIT {}", (self.expression)(limit, 0)));
+        }
+        if let Some(lock) = lock.as_ref().filter(|_| self.locking) {
+            sql.push_str(&format!(" {}", lock));
+        }
+        sql
+    }
+
+    pub(crate) fn table_reference(&self, reference: &TableReference) -> String {
+        match reference {
+            TableReference::Table { name, alias: None } => self.object_name(name),
+            TableReference::Table { name, alias: Some(alias) } => format!("{} AS {}", self.object_name(name), (self.identifier)(alias)),
+            TableReference::Derived { subquery, alias } => format!("({}) AS {}", self.query(subquery), (self.identifier)(alias)),
+            TableReference::Join { left, join_type, right, on } => {
+                let join = match join_type {
+                    JoinType::Inner => "JOIN",
+                    JoinType::Left => "LEFT JOIN",
+                    JoinType::Right => "RIGHT JOIN",
+                    JoinType::Full => "FULL JOIN",
+                };
+                // Joins are left-associative, so only a join on the right needs parentheses
+                let right = match **right {
+                    TableReference::Join { .. } => format!("({})", self.table_reference(right)),
+                    _ => self.table_reference(right),
+                };
+                format!("{} {} {} ON {}", self.table_reference(left), join, right, (self.expression)(on, 0))
+            },
+        }
+    }
+
+    pub(crate) fn table_constraint(&self, constraint: &TableConstraint) -> String {
+        let (name, definition) = match constraint {
+            TableConstraint::PrimaryKey { name, columns } => (name, format!("PRIMARY KEY ({})", self.identifiers(columns))),
+            TableConstraint::Unique { name, columns } => (name, format!("UNIQUE ({})", self.identifiers(columns))),
+            TableConstraint::Check { name, expression: check } => (name, format!("CHECK ({})", (self.expression)(check, 0))),
+        };
+        match name {
+            Some(name) => format!("CONSTRAINT {} {}", (self.identifier)(name), definition),
+            None => definition,
+        }
+    }
+
+    pub(crate) fn window_specification(&self, window: &WindowSpecification) -> String {
+        let mut parts = Vec::new();
+        if !window.partition_by.is_empty() {
+            parts.push(format!("PARTITION BY {}", self.expressions(&window.partition_by)));
+        }
+        if !window.order_by.is_empty() {
+            parts.push(format!("ORDER BY {}", self.expressions(&window.order_by)));
+        }
+        parts.join(" ")
+    }
+
+    pub(crate) fn identifiers(&self, names: &[String]) -> String {
+        names.iter().map(|name| (self.identifier)(name)).collect::<Vec<String>>().join(", ")
+    }
+
+    // Every part of a qualified name is quoted on its own
+    pub(crate) fn object_name(&self, name: &ObjectName) -> String {
+        name.0.iter().map(|part| (self.identifier)(part)).collect::<Vec<String>>().join(".")
+    }
+
+    pub(crate) fn returning(&self, items: &[ReturningItem]) -> String {
+        let items: Vec<String> = items
+            .iter()
+            .map(|item| match &item.alias {
+                Some(alias) => format!("{} AS {}", (self.expression)(&item.expression, 0), (self.identifier)(alias)),
+                None => (self.expression)(&item.expression, 0),
+            })
+            .collect();
+        items.join(", ")
+    }
+
+    pub(crate) fn expressions(&self, list: &[Expression]) -> String {
+        list.iter().map(|item| (self.expression)(item, 0)).collect::<Vec<String>>().join(", ")
+    }
+}
+
+// Quotes a name that would not be read back as the same identifier, because
+// it is a keyword or has characters other than letters, digits and underscores
+fn identifier(name: &str) -> String {
+    let mut characters = name.chars();
+    let plain = characters.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && characters.all(|c| c.is_alphanumeric() || c == '_')
+        && STANDARD_KEYWORDS.get(name).is_none();
+    if plain {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+// A quote inside of a string is written twice. The single quote is used unless
+// the string has double quotes in it, which can not stand inside of it.
+fn string(text: &str) -> String {
+    if text.contains('"') && !text.contains('\'') {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+}
+
+// The precedence of the binary operators, the same as the parser gives them,
+// higher binds tighter
+fn precedence(operator: &BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::Or => 2,
+        BinaryOperator::And => 3,
+        BinaryOperator::Equal | BinaryOperator::NotEqual |
+        BinaryOperator::GreaterThan | BinaryOperator::GreaterThanOrEqual |
+        BinaryOperator::LessThan | BinaryOperator::LessThanOrEqual |
+        BinaryOperator::IsDistinctFrom | BinaryOperator::IsNotDistinctFrom |
+        BinaryOperator::SimilarTo | BinaryOperator::NotSimilarTo |
+        BinaryOperator::RegexMatch | BinaryOperator::NotRegexMatch => 4,
+        BinaryOperator::BitwiseOr => 5,
+        BinaryOperator::BitwiseXor => 6,
+        BinaryOperator::BitwiseAnd => 7,
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 8,
+        BinaryOperator::Plus | BinaryOperator::Minus => 9,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 10,
+        BinaryOperator::Power => 11,
+    }
+}
+
+// The precedence of NOT and of the unary plus and minus
+const PREFIX_PRECEDENCE: u8 = 10;
+
+// The precedence an expression binds with as an operand, where anything
+// that is not an operation is a single operand on its own
+fn own_precedence(expr: &Expression) -> u8 {
+    match expr {
+        Expression::BinaryOperation { operator, .. } => precedence(operator),
+        Expression::UnaryOperation { operator: UnaryOperator::Not, operand } if matches!(**operand, Expression::InSubquery { .. }) => 4,
+        Expression::UnaryOperation { operator: UnaryOperator::Asc | UnaryOperator::Desc, .. } => 1,
+        Expression::UnaryOperation { operator: UnaryOperator::BitwiseNot, .. } => PREFIX_PRECEDENCE + 1,
+        Expression::UnaryOperation { .. } => PREFIX_PRECEDENCE,
+        // A negative number starts with a minus, which is read as an operator
+        Expression::Number(number) if *number < 0 => PREFIX_PRECEDENCE,
+        Expression::Float(number) if number.is_sign_negative() => PREFIX_PRECEDENCE,
+        Expression::InList { .. } | Expression::Like { .. } | Expression::IsNull { .. } | Expression::InSubquery { .. } => 4,
+        _ => u8::MAX,
+    }
+}
+
+// Whether the expression starts with an operator that takes the whole rest of
+// it as its operand. Such an expression is never cut short by what follows
+// it, so it needs no parentheses on the right of another operator.
+fn is_prefix(expr: &Expression) -> bool {
+    match expr {
+        Expression::UnaryOperation { operator: UnaryOperator::Not, operand } => !matches!(**operand, Expression::InSubquery { .. }),
+        Expression::UnaryOperation { operator, .. } => matches!(operator, UnaryOperator::Plus | UnaryOperator::Minus | UnaryOperator::BitwiseNot),
+        Expression::Number(number) => *number < 0,
+        Expression::Float(number) => number.is_sign_negative(),
+        _ => false,
+    }
+}
+
+// Renders an expression that appears as an operand of an operator with the
+// given precedence, adding parentheses when it would bind looser than that
+pub(crate) fn expression(expr: &Expression, parent_precedence: u8) -> String {
+    let sql = operation(expr);
+    if own_precedence(expr) < parent_precedence { format!("({})", sql) } else { sql }
+}
+
+fn operation(expr: &Expression) -> String {
+    match expr {
+        // A chain like `a + b + c` nests on the left, so it is walked down the
+        // left operands instead of recursing into them, and long chains do not
+        // run out of stack
+        Expression::BinaryOperation { .. } => {
+            let mut rest = Vec::new();
+            let mut node = expr;
+            let first = loop {
+                let Expression::BinaryOperation { left_operand, operator, right_operand } = node else {
+                    break operation(node);
+                };
+                let own = precedence(operator);
+                // Operators are left-associative, so an operand on the right with the
+                // same precedence must keep its parentheses, except for the power,
+                // which is right-associative
+                let (left, right) = if *operator == BinaryOperator::Power { (own + 1, own) } else { (own, own + 1) };
+                let right = if is_prefix(right_operand) { 0 } else { right };
+                let symbol = match operator {
+                    BinaryOperator::NotEqual => "<>".to_string(),
+                    operator => operator.to_string(),
+                };
+                rest.push(format!(" {} {}", symbol, expression(right_operand, right)));
+                if own_precedence(left_operand) < left {
+                    break expression(left_operand, left);
+                }
+                node = left_operand;
+            };
+            rest.into_iter().rev().fold(first, |sql, operand| sql + &operand)
+        },
+        Expression::UnaryOperation { operand, operator: UnaryOperator::Not } => match &**operand {
+            Expression::InSubquery { expression: value, subquery } => format!("{} NOT IN ({})", expression(value, 4), GENERIC.query(subquery)),
+            // NOT right before EXISTS would be read as a part of it
+            Expression::Exists { .. } => format!("NOT ({})", operation(operand)),
+            _ => format!("NOT {}", expression(operand, PREFIX_PRECEDENCE + 1)),
+        },
+        Expression::UnaryOperation { operand, operator: UnaryOperator::Asc } => format!("{} ASC", expression(operand, 1)),
+        Expression::UnaryOperation { operand, operator: UnaryOperator::Desc } => format!("{} DESC", expression(operand, 1)),
+        Expression::UnaryOperation { operand, operator: UnaryOperator::BitwiseNot } => format!("~{}", expression(operand, PREFIX_PRECEDENCE + 2)),
+        // An operand that starts with a minus itself gets parentheses from its
+        // precedence, so `--` never starts a comment
+        Expression::UnaryOperation { operand, operator } => format!("{}{}", operator, expression(operand, PREFIX_PRECEDENCE + 1)),
+        Expression::Number(number) => number.to_string(),
+        // Debug keeps the fraction of whole numbers (`3.0`), so they are still
+        // read as floats
+        Expression::Float(number) => format!("{:?}", number),
+        Expression::BitString(bits) => format!("B'{}'", bits),
+        Expression::Bool(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        Expression::Null => "NULL".to_string(),
+        Expression::Placeholder(Placeholder::Anonymous) => "?".to_string(),
+        Expression::Placeholder(Placeholder::Numbered(number)) => format!("${}", number),
+        Expression::Placeholder(Placeholder::Named(name)) => format!(":{}", name),
+        Expression::Temporal { kind, value } => format!("{} {}", kind, string(value)),
+        Expression::Identifier(name) => identifier(name),
+        Expression::CompoundIdentifier(parts) => parts.iter().map(|part| identifier(part)).collect::<Vec<String>>().join("."),
+        Expression::String(text) => string(text),
+        Expression::Wildcard => "*".to_string(),
+        Expression::QualifiedWildcard(table) => format!("{}.*", identifier(table)),
+        Expression::Tuple(elements) => format!("({})", GENERIC.expressions(elements)),
+        Expression::Subquery(subquery) => format!("({})", GENERIC.query(subquery)),
+        Expression::InSubquery { expression: value, subquery } => format!("{} IN ({})", expression(value, 4), GENERIC.query(subquery)),
+        Expression::InList { expression: value, list, negated } => {
+            format!("{} {}IN ({})", expression(value, 4), if *negated { "NOT " } else { "" }, GENERIC.expressions(list))
+        },
+        Expression::Quantified { quantifier, subquery } => format!("{} ({})", quantifier, GENERIC.query(subquery)),
+        Expression::Exists { subquery, negated } => format!("{}EXISTS ({})", if *negated { "NOT " } else { "" }, GENERIC.query(subquery)),
+        Expression::Like { expression: value, pattern, negated, escape } => {
+            let mut sql = format!("{} {}LIKE {}", expression(value, 4), if *negated { "NOT " } else { "" }, expression(pattern, 5));
+            if let Some(escape) = escape {
+                sql.push_str(&format!(" ESCAPE {}", string(&escape.to_string())));
+            }
+            sql
+        },
+        Expression::IsNull { expression: value, negated } => format!("{} IS {}NULL", expression(value, 4), if *negated { "NOT " } else { "" }),
+        Expression::Cast { expression: value, target_type } => format!("CAST({} AS {})", expression(value, 0), db_type(target_type)),
+        Expression::Extract { field, expression: value } => format!("EXTRACT({} FROM {})", field, expression(value, 0)),
+        Expression::Function { name, distinct, arguments, over } => {
+            let call = format!("{}({}{})", identifier(name), if *distinct { "DISTINCT " } else { "" }, GENERIC.expressions(arguments));
+            match over {
+                Some(window) => format!("{} OVER ({})", call, GENERIC.window_specification(window)),
+                None => call,
+            }
+        },
+    }
+}
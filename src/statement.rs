@@ -2,15 +2,47 @@ use std::fmt::{Debug, Display, Formatter};
 
 /// The main entity of the whole parser. `Statement` is implemented as an enumeration because adding functionality is as easy as adding an enumeration constant and implementing functionality for that enumeration constant (implementation in the database command interpreter, which is not a part of this project). Parsing any correct `SELECT` or `CREATE`  (or `UPDATE`, `INSERT INTO`, ... hypothetically) statement should be turned into an instance of this enumeration. Ultimately, your main parser function (something like `build_statement(query: &str) -> Statement`) should return this enumeration.
 ///
-/// The `SELECT` statement has four components:
-/// 1. `columns` – A vector of columns from the selected table that the database should return.
-/// 2. `from` – A simple string, containing a table that is being queried (we aren't doing joins because they complicate stuff too much for this project).
-/// 3. `where` – A single expression that is the actual filter for the database query. It is wrapped in an `Option` because not every `SELECT` query contains a filter. The actual name is `r#where` because in Rust, `where` is a reserved keyword, and the prefix `r#` means: interpret this token as a raw string, do not check for keyword matches.
-/// 4. `orderby` – A vector of expressions that define how should the data be ordered. A vector is needed because the data can be ordered by the first column, and then all data that has the same first column can be ordered by the second column, ... Also, the data can be ordered not simply by columns, but by complex expressions as well.
+/// The `SELECT` statement has seven components:
+/// 1. `with` – A vector of common table expressions (`WITH name AS (SELECT ...)`), which are named queries that can be used as tables by the rest of the statement. It is empty for the queries without a `WITH` clause.
+/// 2. `columns` – A vector of columns from the selected table that the database should return.
+/// 3. `from` – A vector of table references, containing the tables that are being queried. Each of them is either a single table or a tree of joined tables, as described in `TableReference`. Multiple comma-separated table references (`FROM a, b`) are combined as a cross join of all of them. It is empty for the queries without a `FROM` clause, like `SELECT 1 + 2;`, which compute a single row.
+/// 4. `where` – A single expression that is the actual filter for the database query. It is wrapped in an `Option` because not every `SELECT` query contains a filter. The actual name is `r#where` because in Rust, `where` is a reserved keyword, and the prefix `r#` means: interpret this token as a raw string, do not check for keyword matches.
+/// 5. `orderby` – A vector of expressions that define how should the data be ordered. A vector is needed because the data can be ordered by the first column, and then all data that has the same first column can be ordered by the second column, ... Also, the data can be ordered not simply by columns, but by complex expressions as well.
+/// 6. `limit` – The maximum number of rows the query returns, written as `LIMIT 10`, or as `TOP 10` right after `SELECT` in the SQL Server dialect. It is `None` for the queries that return all of their rows.
+/// 7. `lock` – The row-locking clause at the end of the query (`FOR UPDATE`, `FOR SHARE SKIP LOCKED`, ...), as described in `LockingClause`. It is `None` for the queries that do not lock the rows they read.
 ///
-/// The `CREATE TABLE` statement has two components:
-/// 1. `table_name` – A simple string, the name of the table.
-/// 2. `column_list` – A vector of table column types, where each table column contains the definition of one column.
+/// The `CREATE TABLE` statement has seven components:
+/// 1. `table_name` – An `ObjectName`, the name of the table, possibly qualified with its schema.
+/// 2. `temporary` – Whether the table is created with `CREATE TEMPORARY TABLE` (or `CREATE TEMP TABLE`), so that it only exists until the end of the session.
+/// 3. `if_not_exists` – Whether the statement is guarded with `IF NOT EXISTS`, which makes creating a table that already exists do nothing instead of failing.
+/// 4. `column_list` – A vector of table column types, where each table column contains the definition of one column.
+/// 5. `constraints` – The constraints of the whole table that are written between the columns, like `PRIMARY KEY (a, b)`, as described in `TableConstraint`.
+/// 6. `options` – The table options after the column definitions, as pairs of a name and a value, in the order they were written. Both the MySQL form (`ENGINE=InnoDB DEFAULT CHARSET=utf8`, where the name is `DEFAULT CHARSET`) and the Postgres form (`WITH (fillfactor=70)`) are accepted. The options are kept as they are without being interpreted, so that the statement can be written back unchanged.
+/// 7. `partitioning` – How the rows of the table are split into partitions (`PARTITION BY RANGE (created_at)`), as described in `Partitioning`. It is `None` for the tables that are not partitioned.
+///
+/// The `CREATE VIEW` statement has four components:
+/// 1. `name` – An `ObjectName`, the name of the view, possibly qualified with its schema.
+/// 2. `or_replace` – Whether the view is created with `CREATE OR REPLACE VIEW`, which replaces the definition of an existing view of the same name instead of failing.
+/// 3. `if_not_exists` – Whether the statement is guarded with `IF NOT EXISTS`, which makes creating a view that already exists do nothing instead of failing.
+/// 4. `query` – The `SELECT` statement that defines the view (without its semicolon).
+///
+/// The `COMMENT ON` statement (`COMMENT ON TABLE users IS 'Registered users';`) documents a schema object, and has two components:
+/// 1. `target` – The table, view or column that is documented, as described in `CommentTarget`.
+/// 2. `text` – The text of the comment. It is `None` for `IS NULL`, which removes the comment.
+///
+/// The `INSERT INTO` statement adds rows to a table, and has four components:
+/// 1. `table_name` – An `ObjectName`, the name of the table the rows are added to.
+/// 2. `columns` – The columns the values are given for (`INSERT INTO users (id, name) ...`). It is empty when the values are given for all of the columns, in the order of the table.
+/// 3. `source` – Where the rows come from, either a `VALUES` list or a query, as described in `InsertSource`.
+/// 4. `returning` – The values the statement gives back for every inserted row (`RETURNING id`), as described in `ReturningItem`. It is empty for the statements without a `RETURNING` clause.
+///
+/// The `DROP TABLE` statement removes a table, and has two components:
+/// 1. `table_name` – An `ObjectName`, the name of the table, possibly qualified with its schema.
+/// 2. `if_exists` – Whether the statement is guarded with `IF EXISTS`, which makes dropping a table that does not exist do nothing instead of failing.
+///
+/// The `Custom` statement is one the parser does not know itself, like `VACUUM` of SQLite, which was parsed by a `StatementHandler` of the application. It has two components:
+/// 1. `keyword` – The word the statement starts with, in upper case.
+/// 2. `payload` – Whatever the handler made of the rest of the statement, in a form of its choosing (the text itself, or JSON, say), so that the statement can be stored and serialized like any other.
 ///
 /// Examples:
 ///
@@ -21,13 +53,16 @@ use std::fmt::{Debug, Display, Formatter};
 /// is a `SELECT` statement that,  when parsed, looks like this:
 /// ```rust
 /// Statement::Select {
+///     with: vec![],
 ///     columns: vec![
-/// 		Expression::Identifier("name".to_string()),
-/// 		Expression:Identifier("surname".to_string())
-/// 	],
-///     from: "users".to_string(),
+///         Expression::Identifier("name".to_string()),
+///         Expression::Identifier("surname".to_string())
+///     ],
+///     from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
 ///     r#where: None,
-///     orderby: vec![]
+///     orderby: vec![],
+///     limit: None,
+///     lock: None
 /// }
 /// ```
 /// ---
@@ -37,6 +72,7 @@ use std::fmt::{Debug, Display, Formatter};
 /// is a `SELECT` statement that,  when parsed, looks like this:
 /// ```rust
 /// Statement::Select {
+///     with: vec![],
 ///     columns: vec![
 ///         Expression::BinaryOperation {
 ///             left_operand: Box::new(Expression::Identifier("age".to_string())),
@@ -45,9 +81,11 @@ use std::fmt::{Debug, Display, Formatter};
 ///         },
 ///         Expression::String("this is a string".to_string()),
 ///     ],
-///     from: "users".to_string(),
+///     from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
 ///     r#where: None,
-///     orderby: vec![]
+///     orderby: vec![],
+///     limit: None,
+///     lock: None
 /// }
 /// ```
 /// ---
@@ -57,27 +95,30 @@ use std::fmt::{Debug, Display, Formatter};
 /// is a  `SELECT` statement that, when parsed, looks like this:
 /// ```rust
 /// Statement::Select {
-///     columns: [
+///     with: vec![],
+///     columns: vec![
 ///         Expression::Identifier("name".to_string()),
 ///         Expression::Identifier("surname".to_string()),
 ///     ],
-///     from: "users".to_string(),
+///     from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
 ///     r#where: Some(
 ///         Expression::BinaryOperation {
 ///             left_operand: Box::new(Expression::BinaryOperation {
 ///                 left_operand: Box::new(Expression::Identifier("name".to_string())),
-///                 operand: BinaryOperator::Equals,
+///                 operator: BinaryOperator::Equals,
 ///                 right_operand: Box::new(Expression::String("Voldemort".to_string())),
 ///             }),
-///             operand: BinaryOperator::And,
+///             operator: BinaryOperator::And,
 ///             right_operand: Box::new(Expression::BinaryOperation {
 ///                 left_operand: Box::new(Expression::Identifier("surname".to_string())),
-///                 operand: BinaryOperator::Equals,
+///                 operator: BinaryOperator::Equals,
 ///                 right_operand: Box::new(Expression::String("Riddle".to_string())),
 ///             }),
 ///         },
 ///     ),
-///     orderby: vec![]
+///     orderby: vec![],
+///     limit: None,
+///     lock: None
 /// }
 /// ```
 ///  ---
@@ -87,11 +128,12 @@ use std::fmt::{Debug, Display, Formatter};
 /// is a  `SELECT` statement that, when parsed, looks like this:
 /// ```rust
 /// Statement::Select {
+///     with: vec![],
 ///     columns: vec![
 ///         Expression::Identifier("id".to_string()),
 ///         Expression::Identifier("salary".to_string()),
 ///     ],
-///     from: "users".to_string(),
+///     from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
 ///     r#where: None,
 ///     orderby: vec![
 ///         Expression::UnaryOperation {
@@ -111,6 +153,8 @@ use std::fmt::{Debug, Display, Formatter};
 ///             operator: UnaryOperator::Desc,
 ///         },
 ///     ],
+///     limit: None,
+///     lock: None,
 /// }
 /// ```
 ///  ---
@@ -120,10 +164,11 @@ use std::fmt::{Debug, Display, Formatter};
 /// is a  `SELECT` statement that, when parsed, looks like this:
 /// ```rust
 /// Statement::Select {
+///     with: vec![],
 ///     columns: vec![
 ///         Expression::Identifier("id".to_string())
 ///     ],
-///     from: "registered_users".to_string(),
+///     from: vec![TableReference::Table { name: ObjectName::new("registered_users"), alias: None }],
 ///     r#where: Some(
 ///         Expression::BinaryOperation {
 ///             left_operand: Box::new(Expression::Identifier("password_encryption".to_string())),
@@ -136,30 +181,34 @@ use std::fmt::{Debug, Display, Formatter};
 ///             operand: Box::new(Expression::Identifier("id".to_string())),
 ///             operator: UnaryOperator::Desc
 ///         }
-///     ]
+///     ],
+///     limit: None,
+///     lock: None
 /// }
 /// ```
 /// ---
 /// ```sql
 /// CREATE TABLE simple_table(
-/// 	int_col INT,
-/// 	string_col VARCHAR(255),
-/// 	bool_col BOOL
+///     int_col INT,
+///     string_col VARCHAR(255),
+///     bool_col BOOL
 /// );
 /// ```
 /// is a  `CREATE TABLE` statement that, when parsed, looks like this:
 /// ```rust
 /// Statement::CreateTable {
-///     table_name: "simple_table".to_string(),
+///     table_name: ObjectName::new("simple_table"),
+///     temporary: false,
+///     if_not_exists: false,
 ///     column_list: vec![
 ///         TableColumn {
 ///             column_name: "int_col".to_string(),
-///             column_type: DBType::Int,
+///             column_type: DBType::Int { unsigned: false },
 ///             constraints: vec![],
 ///         },
 ///         TableColumn {
 ///             column_name: "string_col".to_string(),
-///             column_type: DBType::Varchar(255),
+///             column_type: DBType::Varchar(Some(255)),
 ///             constraints: vec![],
 ///         },
 ///         TableColumn {
@@ -167,45 +216,50 @@ use std::fmt::{Debug, Display, Formatter};
 ///             column_type: DBType::Bool,
 ///             constraints: vec![],
 ///         },
-///     ]
+///     ],
+///     constraints: vec![],
+///     options: vec![],
+///     partitioning: None
 /// }
 /// ```
 /// ---
 /// ```sql
 /// CREATE TABLE complex_table(
-/// 	id INT PRIMARY KEY,
-/// 	email VARCHAR(255) NOT NULL,
-/// 	is_junior BOOL,
-/// 	age INT CHECK(age >= 18) CHECK(age <= 65)
+///     id INT PRIMARY KEY,
+///     email VARCHAR(255) NOT NULL,
+///     is_junior BOOL,
+///     age INT CHECK(age >= 18) CHECK(age <= 65)
 /// );
 /// ```
 /// is a  `CREATE TABLE` statement that, when parsed, looks like this:
 /// ```rust
 /// Statement::CreateTable {
-///     table_name: Expression::Identifier("complex_table".to_string()),
+///     table_name: ObjectName::new("complex_table"),
+///     temporary: false,
+///     if_not_exists: false,
 ///     column_list: vec![
 ///         TableColumn {
-///             column_name: Expression::Identifier("id".to_string()),
-///             column_type: DBType::Int,
+///             column_name: "id".to_string(),
+///             column_type: DBType::Int { unsigned: false },
 ///             constraints: vec![
 ///                 Constraint::PrimaryKey,
 ///             ],
 ///         },
 ///         TableColumn {
-///             column_name: Expression::Identifier("email".to_string()),
-///             column_type: DBType::Varchar(255),
+///             column_name: "email".to_string(),
+///             column_type: DBType::Varchar(Some(255)),
 ///             constraints: vec![
 ///                 Constraint::NotNull,
 ///             ],
 ///         },
 ///         TableColumn {
-///             column_name: Expression::Identifier("is_junior".to_string()),
+///             column_name: "is_junior".to_string(),
 ///             column_type: DBType::Bool,
 ///             constraints: vec![],
 ///         },
 ///         TableColumn {
-///             column_name: Expression::Identifier("age".to_string()),
-///             column_type: DBType::Int,
+///             column_name: "age".to_string(),
+///             column_type: DBType::Int { unsigned: false },
 ///             constraints: vec![
 ///                 Constraint::Check(Expression::BinaryOperation {
 ///                     left_operand: Box::new(Expression::Identifier("age".to_string())),
@@ -220,41 +274,227 @@ use std::fmt::{Debug, Display, Formatter};
 ///             ],
 ///         },
 ///     ],
+///     constraints: vec![],
+///     options: vec![],
+///     partitioning: None,
 /// }
 /// ```
 /// ---
 /// ```sql
 /// SELECT salary WHERE salary > 1000;
 /// ```
-/// is a string, that, the parser should throw an error to the user when it encounters it (no `FROM` clause).
+/// is a string, that, the parser should throw an error to the user when it encounters it (a `WHERE` clause without a `FROM` clause).
 ///
 /// ---
 /// ```sql
 /// CREATE TABLE work_hours(num_hours INT)
 /// ```
 /// is a string, that, the parser should throw an error to the user when it encounters it (no semicolon at the end).
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Statement {
     Select {
+        with: Vec<CommonTableExpression>,
         columns: Vec<Expression>,
-        from: String,
+        from: Vec<TableReference>,
         r#where: Option<Expression>,
         orderby: Vec<Expression>,
+        limit: Option<Expression>,
+        lock: Option<LockingClause>,
     },
     CreateTable {
-        table_name: String,
+        table_name: ObjectName,
+        temporary: bool,
+        if_not_exists: bool,
         column_list: Vec<TableColumn>,
-    }
+        constraints: Vec<TableConstraint>,
+        options: Vec<(String, Expression)>,
+        partitioning: Option<Partitioning>,
+    },
+    CreateView {
+        name: ObjectName,
+        or_replace: bool,
+        if_not_exists: bool,
+        query: Box<Statement>,
+    },
+    Comment {
+        target: CommentTarget,
+        text: Option<String>,
+    },
+    Insert {
+        table_name: ObjectName,
+        columns: Vec<String>,
+        source: InsertSource,
+        returning: Vec<ReturningItem>,
+    },
+    DropTable {
+        table_name: ObjectName,
+        if_exists: bool,
+    },
+    Custom {
+        keyword: String,
+        payload: String,
+    },
+}
+
+/// The rows an `INSERT` statement adds to its table: either a `VALUES` list of one or more rows (`VALUES (1, 'a'), (2, 'b')`), every row being a list of expressions, or the rows produced by a query (`INSERT INTO archive SELECT * FROM orders`). `columns` of the statement tells which columns the values go to, in order; it is empty when the statement does not list them, in which case every column of the table gets a value.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertSource {
+    Values(Vec<Vec<Expression>>),
+    Query(Box<Statement>),
+}
+
+/// A value that `INSERT ... RETURNING` gives back for every row it inserted, like the `id` generated for it or `price * 2 AS total`, computed from the columns of the new row. The `alias` is the name the value is returned under, when it is given one with `AS`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReturningItem {
+    pub expression: Expression,
+    pub alias: Option<String>,
+}
+
+/// The name of a table or a view, made of one or more parts separated by dots: `users`, `public.users` or `mydb.public.users`. The last part is the name of the object itself, and the parts before it name the schema (and the database) it belongs to. Names are kept as they were written, so `users` and `public.users` are different objects, even if the database would find both in the same place.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectName(pub Vec<String>);
+
+/// The schema object a `COMMENT ON` statement documents. A column is always given together with its table (`COMMENT ON COLUMN users.email IS '...'`), and the last part of the dotted name is the column.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentTarget {
+    Table(ObjectName),
+    View(ObjectName),
+    Column {
+        table: ObjectName,
+        column: String,
+    },
+}
+
+/// A named query of the `WITH` clause. Inside of the statement it is defined in, it can be used in the `FROM` clause like any other table.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommonTableExpression {
+    pub name: String,
+    pub query: Statement,
+}
+
+/// The `PARTITION BY` clause of a `CREATE TABLE` statement, which tells how the rows of the table are split into partitions: by the ranges (`RANGE`) or the lists (`LIST`) of values the partitions hold, or by a hash of the values (`HASH`, and `KEY` in MySQL, which hashes with a function of its own choosing). `expressions` are the values the rows are partitioned by, usually plain columns:
+/// ```sql
+/// CREATE TABLE events(id INT, created_at INT) PARTITION BY RANGE (created_at);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Partitioning {
+    pub method: PartitionMethod,
+    pub expressions: Vec<Expression>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartitionMethod {
+    Range,
+    List,
+    Hash,
+    Key,
+}
+
+/// The row-locking clause of a `SELECT` query, like `FOR UPDATE NOWAIT`. It is how applications lock the rows they are about to change, so that other transactions can not change them in between.
+/// 1. `strength` – Whether the rows are locked for changing them (`FOR UPDATE`) or only to keep others from changing them (`FOR SHARE`).
+/// 2. `wait_policy` – What happens when some of the rows are already locked by another transaction: wait for them (the default), fail right away (`NOWAIT`), or leave them out of the result (`SKIP LOCKED`).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockingClause {
+    pub strength: LockStrength,
+    pub wait_policy: WaitPolicy,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LockStrength {
+    Update,
+    Share,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaitPolicy {
+    Wait,
+    NoWait,
+    SkipLocked,
+}
+
+/// An element of the `FROM` clause of a `SELECT` statement. A table reference is either a single named table (optionally renamed with an alias: `users AS u` or just `users u`), a derived table (an embedded `SELECT` that is visible under its alias, which is also how views are expanded), or a join of two other table references with the condition the rows are matched by. Joins are left-associative, so they naturally form a tree:
+/// ```sql
+/// SELECT * FROM users JOIN orders ON users.id = orders.user_id LEFT JOIN items ON orders.item_id = items.id;
+/// ```
+/// is parsed into a `FROM` clause that looks like this:
+/// ```rust
+/// TableReference::Join {
+///     left: Box::new(TableReference::Join {
+///         left: Box::new(TableReference::Table { name: ObjectName::new("users"), alias: None }),
+///         join_type: JoinType::Inner,
+///         right: Box::new(TableReference::Table { name: ObjectName::new("orders"), alias: None }),
+///         on: /* users.id = orders.user_id */,
+///     }),
+///     join_type: JoinType::Left,
+///     right: Box::new(TableReference::Table { name: ObjectName::new("items"), alias: None }),
+///     on: /* orders.item_id = items.id */,
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableReference {
+    Table {
+        name: ObjectName,
+        alias: Option<String>,
+    },
+    Derived {
+        subquery: Box<Statement>,
+        alias: String,
+    },
+    Join {
+        left: Box<TableReference>,
+        join_type: JoinType,
+        right: Box<TableReference>,
+        on: Expression,
+    },
+}
+
+/// The kind of join between two table references. A plain `JOIN` is the same as `INNER JOIN`, while the optional `OUTER` keyword of `LEFT`, `RIGHT` and `FULL` joins does not change their meaning, so it is not stored.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
 }
 
 /// The main entity of the expression parser. The Expression enum is structured like this, where an expression can contain another expression. This naturally allows us to represent complex expressions as trees. `Box<T>` smart pointers are used on unary and binary types of expressions because the compiler needs to know the size of the enum at compile time which is impossible when an enum contains itself (infinite size).
 ///
 /// An expression can be:
 /// * complex - a number of other expressions (tree-like structure, unary and binary operations)
-/// * a single number
+/// * a single number, either a whole one or a decimal fraction like `9.99` (stored as `Float`). Whole numbers are signed, so that negative constants (like the ones a `Transformer` computes) can be stored as literals, even though the parser reads `-5` as a minus operation over `5`. The one exception is `-9223372036854775808`, which the parser reads as a single `Number`, because `9223372036854775808` alone does not fit Hexadecimal numbers like `0xFF` are stored as the `Number` they stand for
+/// * a bit string, like `B'1010'`, whose digits are stored as they were written (stored as `BitString`)
 /// * a single identifier (like a variable 'x')
+/// * a compound identifier, which is a dotted name like `users.name` (stored as `["users", "name"]`), used to refer to a column of a specific table
 /// * a single string (when doing parsing of WHERE statements that do operations with strings, strings must be in matching quotes – either `""` or `''`)
 /// * a boolean (only true or false)
+/// * `NULL`, the missing value, like in `DEFAULT NULL` or `VALUES (1, NULL)`. Whether a value is missing is checked with `IS NULL`, as `a = NULL` is never true
+/// * a tuple of several values in parentheses, like `(a, b)` in `(a, b) = (1, 2)` or `(x, y) IN ((1, 2), (3, 4))`. A single expression in parentheses is only a grouping, so it is stored as the expression itself
+/// * a typed date or time literal, like `DATE '2024-01-01'`, `TIME '12:00:00'` or `TIMESTAMP '2024-01-01 12:00:00'` (see `TemporalKind`). The text of the literal is kept as it was written
+/// * a placeholder for a parameter of a prepared statement, whose value is supplied when the statement is executed (see `Placeholder`)
+/// * a scalar subquery – a parenthesized `SELECT` that produces a single value, like `(SELECT max_age FROM limits)`
+/// * an `IN` predicate with a subquery, like `id IN (SELECT user_id FROM orders)`, which checks whether the value of the expression is among the rows of the subquery. `NOT IN` with a subquery is stored as a `NOT` operation over the predicate
+/// * an `IN` predicate with a list of values, like `status IN ('new', 'paid')`, which checks whether the value of the expression is one of them. `NOT IN` is stored as the same expression with `negated` set
+/// * a function call, like `COUNT(*)` or `LOWER(name)`, optionally computed as a window function over a set of rows with an `OVER` clause (see `WindowSpecification`). The `*` of `COUNT(*)` is stored as a single `Wildcard` argument, and an aggregate over the distinct values of its arguments (`COUNT(DISTINCT name)`) has `distinct` set. `COALESCE` and `NULLIF` are stored as function calls too
+/// * a quantified subquery, like `ALL (SELECT salary FROM interns)` in `salary > ALL (SELECT salary FROM interns)`, which is only found as the right operand of a comparison. The comparison holds for `ANY` (or its synonym `SOME`) when it holds for at least one row of the subquery, and for `ALL` when it holds for every row
+/// * an `EXISTS` predicate, like `EXISTS (SELECT * FROM orders WHERE user_id = id)`, which checks whether the subquery returns any rows. `NOT EXISTS` is stored as the same expression with `negated` set
+/// * a `LIKE` predicate, like `name LIKE 'A%'`, which matches a string against a pattern where `%` stands for any number of characters and `_` for exactly one. `NOT LIKE` is stored with `negated` set, and the character given with `ESCAPE` (`name LIKE '100\%' ESCAPE '\'`) makes the `%` or `_` after it match itself
+/// * a conversion of a value to another type, written either as `CAST(age AS VARCHAR(10))` or in the style of Postgres as `age::VARCHAR(10)`. Both are stored the same way
+/// * a part of a date or a time, like `EXTRACT(YEAR FROM created_at)` (see `DateField`)
+/// * an `IS NULL` predicate, like `email IS NULL`, which checks whether the value of the expression is missing. `IS NOT NULL` is stored as the same expression with `negated` set
+/// * a wildcard – `*` for all the columns of all the tables of the query (or all the rows in `COUNT(*)`), or a qualified wildcard like `u.*` for all the columns of a single table
 ///
 /// Examples:
 ///
@@ -361,7 +601,8 @@ pub enum Statement {
 /// ```
 /// is a string, that, the parser should throw an error to the user when it encounters it.
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
     BinaryOperation {
         left_operand: Box<Expression>,
@@ -372,47 +613,250 @@ pub enum Expression {
         operand: Box<Expression>,
         operator: UnaryOperator,
     },
-    Number(u64),
+    Number(i64),
+    Float(f64),
+    BitString(String),
     Bool(bool),
+    Null,
+    Placeholder(Placeholder),
+    Temporal {
+        kind: TemporalKind,
+        value: String,
+    },
     Identifier(String),
+    CompoundIdentifier(Vec<String>),
     String(String),
     Wildcard,
+    QualifiedWildcard(String),
+    Tuple(Vec<Expression>),
+    Subquery(Box<Statement>),
+    InSubquery {
+        expression: Box<Expression>,
+        subquery: Box<Statement>,
+    },
+    InList {
+        expression: Box<Expression>,
+        list: Vec<Expression>,
+        negated: bool,
+    },
+    Quantified {
+        quantifier: Quantifier,
+        subquery: Box<Statement>,
+    },
+    Exists {
+        subquery: Box<Statement>,
+        negated: bool,
+    },
+    Like {
+        expression: Box<Expression>,
+        pattern: Box<Expression>,
+        negated: bool,
+        escape: Option<char>,
+    },
+    IsNull {
+        expression: Box<Expression>,
+        negated: bool,
+    },
+    Cast {
+        expression: Box<Expression>,
+        target_type: DBType,
+    },
+    Extract {
+        field: DateField,
+        expression: Box<Expression>,
+    },
+    Function {
+        name: String,
+        distinct: bool,
+        arguments: Vec<Expression>,
+        over: Option<Box<WindowSpecification>>,
+    },
+}
+
+/// A parameter of a prepared statement, as drivers write them in their logs: `Anonymous` is a `?`, and the parameters are numbered by the order they appear in, while `Numbered(n)` is `$n` in the style of Postgres, which refers to the parameter `n` no matter where it appears (so it can be used several times). `Named` is a parameter referred to by its name, written either as `:name` or as `@name`; the name is stored without the prefix. `Statement::parameters` lists the parameters of a statement.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Placeholder {
+    Anonymous,
+    Numbered(u64),
+    Named(String),
+}
+
+/// The part of a date or a time that `EXTRACT` takes out of it, as a number: the year, the quarter of the year (1 to 4), the month (1 to 12), the week of the year, the day of the month, or the hour, minute or second of the time.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DateField {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateField {
+    pub const ALL: [DateField; 8] = [
+        DateField::Year,
+        DateField::Quarter,
+        DateField::Month,
+        DateField::Week,
+        DateField::Day,
+        DateField::Hour,
+        DateField::Minute,
+        DateField::Second,
+    ];
+}
+
+/// The quantifier of a subquery compared against, see `Expression::Quantified`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantifier {
+    Any,
+    All,
+}
+
+/// The type of a typed literal: a calendar day (`DATE '2024-01-01'`), a time of day (`TIME '12:00:00'`, optionally with a fraction of a second) or both (`TIMESTAMP '2024-01-01 12:00:00'`).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TemporalKind {
+    Date,
+    Time,
+    Timestamp,
+}
+
+/// The `OVER (...)` clause of a window function call, which defines the rows the function is computed over:
+/// 1. `partition_by` – A vector of expressions that split the rows into partitions, the function is computed separately for each of them. It is empty when the clause has no `PARTITION BY`, in which case all rows form one partition.
+/// 2. `order_by` – A vector of expressions that order the rows inside of a partition, the same way as the `ORDER BY` of a `SELECT` statement.
+///
+/// For example, `ROW_NUMBER() OVER (PARTITION BY department ORDER BY salary DESC)` looks like this:
+/// ```rust
+/// Expression::Function {
+///     name: "ROW_NUMBER".to_string(),
+///     distinct: false,
+///     arguments: vec![],
+///     over: Some(Box::new(WindowSpecification {
+///         partition_by: vec![Expression::Identifier("department".to_string())],
+///         order_by: vec![Expression::UnaryOperation {
+///             operand: Box::new(Expression::Identifier("salary".to_string())),
+///             operator: UnaryOperator::Desc,
+///         }],
+///     })),
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowSpecification {
+    pub partition_by: Vec<Expression>,
+    pub order_by: Vec<Expression>,
 }
 
 /// A structure containing a definition for one column, when creating a table.
 /// 1. `column_name` – A simple string, representing a name.
 /// 2. `column_type` – The type of the column. Types are defined in the `DBType` enum.
 /// 3.  `constraints` – A vector of constraints on the column. Types of constraints are defined in the `Constraint` enum.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableColumn {
     pub column_name: String,
     pub column_type: DBType,
     pub constraints: Vec<Constraint>,
 }
 
-/// A column in the database can be any of these types. `Int` and `Bool` types have no additional info, while the `Varchar(n)` type has an additional argument – the length of the string. Adding a type, such as `DECIMAL(n, m)` is boiled down to adding tokens for that type, parsing that type and adding it to this enum.
-#[derive(Debug, PartialEq)]
+/// A column in the database can be any of these types. The `Bool` type has no additional info, and the integers – `Int`, the two-byte `SmallInt`, the eight-byte `BigInt` and the one-byte `TinyInt` of MySQL – only record whether they are `unsigned`, which MySQL writes as `INT UNSIGNED`, while the `Varchar(n)` type has an additional argument – the maximum length of the string, which is `None` when the length is left out (`VARCHAR` without parentheses) and the string is not limited. `Decimal` is an exact number written as `DECIMAL(precision, scale)` or `NUMERIC(precision, scale)`: at most `precision` digits, `scale` of which are after the decimal point. Both of them may be left out, starting from the scale, and the database picks its own defaults for the missing ones. `Float`, `Real` and `Double` are approximate numbers; `Double` is written `DOUBLE PRECISION` in the standard, and MySQL also allows just `DOUBLE`. `Date`, `Time`, `Timestamp` and the `DateTime` of MySQL hold the values of the typed literals (see `TemporalKind`); a `TIMESTAMP WITH TIME ZONE` has `with_time_zone` set. `Blob` is a string of bytes, written `BLOB` in MySQL and SQLite and `BYTEA` in PostgreSQL. `Uuid` is a universally unique identifier of PostgreSQL, like `a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11`. `Serial` and `BigSerial` are the integers of PostgreSQL that take their values from a sequence when none is given. `Custom` is a type named by a word that the application registered as its own keyword (see `Keywords`), like `VECTOR`, kept by that name. Adding a type is boiled down to adding tokens for that type, parsing that type and adding it to this enum.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DBType {
-    Int,
-    Varchar(usize),
+    Int {
+        unsigned: bool,
+    },
+    SmallInt {
+        unsigned: bool,
+    },
+    BigInt {
+        unsigned: bool,
+    },
+    TinyInt {
+        unsigned: bool,
+    },
+    Varchar(Option<usize>),
     Bool,
+    Decimal {
+        precision: Option<usize>,
+        scale: Option<usize>,
+    },
+    Float,
+    Real,
+    Double,
+    Date,
+    Time,
+    Timestamp {
+        with_time_zone: bool,
+    },
+    DateTime,
+    Blob,
+    Uuid,
+    Serial,
+    BigSerial,
+    Custom(String),
 }
 
-/// A column can be limited to a domain of values, which is defined by constraints on that column. `PrimaryKey` and `NotNull` constraints have no additional info, while the `Check` constraints has an additional argument – the expression which every table row must satisfy.
-#[derive(Debug, PartialEq)]
+/// A column can be limited to a domain of values, which is defined by constraints on that column. `PrimaryKey` and `NotNull` constraints have no additional info, and neither does `Null`, which is the explicit opposite of `NotNull` (`age INT NULL`) and only states the default, while the `Check` constraints has an additional argument – the expression which every table row must satisfy. `Comment` does not limit the values at all, it is the documentation of the column written in MySQL style (`email VARCHAR(255) COMMENT 'primary email'`), kept among the constraints because it is written among them. `Identity` makes the database generate the values of the column, as in `id INT GENERATED ALWAYS AS IDENTITY`; with `always` unset (`GENERATED BY DEFAULT AS IDENTITY`) a value may still be given explicitly. `Default` is the value a column gets when a row is inserted without one, like the `0` of `age INT DEFAULT 0`. `AutoIncrement` is the older way of MySQL (`AUTO_INCREMENT`) and SQLite (`AUTOINCREMENT`) to generate the values of an integer column.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Constraint {
     NotNull,
+    Null,
     PrimaryKey,
-    Check(Expression)
+    Check(Expression),
+    Comment(String),
+    Default(Expression),
+    AutoIncrement,
+    Identity {
+        always: bool,
+    },
 }
 
-/// Binary and unary operators are defined as enums, where each enumeration constant represents one operator. Binary and unary operators are defined separately because a `-` (minus), for example can be in a binary operation: `5 - 4`, as well as in a unary operation: `-2`. `Asc` and `Desc` are `ORDER BY` operators that have the lowest operator precedence in any expression. While both unary and binary operators may be the exact same as tokens that represent them, it is important to make a distinction between them, as they are used in different contexts.
-#[derive(Debug, PartialEq)]
+/// A constraint of the whole table, written as an entry of the column list of `CREATE TABLE` rather than after a column, which can span several columns: `PRIMARY KEY (order_id, line)` or `UNIQUE (first_name, last_name)`. A `CHECK` of the table is like the `Check` of a column, but it is not tied to any of the columns, so it is the usual place for a condition over several of them, like `CHECK (price > cost)`. A constraint can be named by writing `CONSTRAINT name` before it.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableConstraint {
+    PrimaryKey {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Unique {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Check {
+        name: Option<String>,
+        expression: Expression,
+    },
+}
+
+/// Binary and unary operators are defined as enums, where each enumeration constant represents one operator. Binary and unary operators are defined separately because a `-` (minus), for example can be in a binary operation: `5 - 4`, as well as in a unary operation: `-2`. `Asc` and `Desc` are `ORDER BY` operators that have the lowest operator precedence in any expression. While both unary and binary operators may be the exact same as tokens that represent them, it is important to make a distinction between them, as they are used in different contexts. `IsDistinctFrom` and `IsNotDistinctFrom` are the comparisons `a IS [NOT] DISTINCT FROM b`, which unlike `=` and `!=` treat `NULL` as an ordinary value: two `NULL`s are not distinct, while `NULL` is distinct from any other value. `SimilarTo` is the pattern match `SIMILAR TO` of the SQL standard, and `RegexMatch` a match against a regular expression, written `REGEXP` in MySQL and `~` in PostgreSQL. `Power` raises the left operand to the power of the right one; only PostgreSQL writes it as an operator (`^`), and it is the only operator that groups from the right.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOperator {
     Plus,
     Minus,
     Multiply,
     Divide,
+    Modulo,
+    Power,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
+    SimilarTo,
+    NotSimilarTo,
+    RegexMatch,
+    NotRegexMatch,
     GreaterThan,
     GreaterThanOrEqual,
     LessThan,
@@ -423,12 +867,14 @@ pub enum BinaryOperator {
     Or,
 }
 
-/// Binary and unary operators are defined as enums, where each enumeration constant represents one operator. Binary and unary operators are defined separately because a `-` (minus), for example can be in a binary operation: `5 - 4`, as well as in a unary operation: `-2`. `Asc` and `Desc` are `ORDER BY` operators that have the lowest operator precedence in any expression. While both unary and binary operators may be the exact same as tokens that represent them, it is important to make a distinction between them, as they are used in different contexts.
-#[derive(Debug, PartialEq)]
+/// Binary and unary operators are defined as enums, where each enumeration constant represents one operator. Binary and unary operators are defined separately because a `-` (minus), for example can be in a binary operation: `5 - 4`, as well as in a unary operation: `-2`. `BitwiseNot` is the prefix `~`, which flips every bit of an integer. `Asc` and `Desc` are `ORDER BY` operators that have the lowest operator precedence in any expression. While both unary and binary operators may be the exact same as tokens that represent them, it is important to make a distinction between them, as they are used in different contexts.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOperator {
     Not,
     Plus,
     Minus,
+    BitwiseNot,
     Asc,
     Desc,
 }
@@ -438,11 +884,72 @@ pub enum UnaryOperator {
 // will be the same as in Debug prints which is not useful
 // when printing to the end user.
 
+impl DBType {
+    // Whether the type holds whole numbers
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DBType::Int { .. } | DBType::SmallInt { .. } | DBType::BigInt { .. } | DBType::TinyInt { .. } | DBType::Serial | DBType::BigSerial
+        )
+    }
+}
+
+impl Statement {
+    // The name of the kind of the statement, such as `CREATE TABLE`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Statement::Select { lock: Some(LockingClause { strength: LockStrength::Update, .. }), .. } => "SELECT FOR UPDATE",
+            Statement::Select { lock: Some(LockingClause { strength: LockStrength::Share, .. }), .. } => "SELECT FOR SHARE",
+            Statement::Select { .. } => "SELECT",
+            Statement::CreateTable { .. } => "CREATE TABLE",
+            Statement::CreateView { .. } => "CREATE VIEW",
+            Statement::Comment { .. } => "COMMENT",
+            Statement::Insert { .. } => "INSERT",
+            Statement::DropTable { .. } => "DROP TABLE",
+            Statement::Custom { .. } => "CUSTOM",
+        }
+    }
+    
+    // The statement as SQL that parses back into it, see `Display`
+    pub fn to_sql(&self) -> String {
+        crate::sql::statement(self)
+    }
+    
+    // Whether the statement changes the database in any way, as opposed to
+    // only reading from it. Locking rows counts as a change, like it does in
+    // the read-only transactions of real databases. Custom statements count
+    // as changes too, as there is no telling what they do.
+    pub fn is_write(&self) -> bool {
+        !matches!(self, Statement::Select { lock: None, .. })
+    }
+    
+    // Whether the statement changes the schema of the database (the tables
+    // and views that exist, or their documentation)
+    pub fn changes_schema(&self) -> bool {
+        match self {
+            Statement::Select { .. } | Statement::Insert { .. } => false,
+            Statement::CreateTable { .. } | Statement::CreateView { .. } | Statement::Comment { .. } => true,
+            Statement::DropTable { .. } => true,
+            // Only the handler knows what the statement does
+            Statement::Custom { .. } => false,
+        }
+    }
+}
+
+impl Expression {
+    // The expression as SQL that parses back into it, with parentheses only
+    // where the precedence of the operators needs them
+    pub fn to_sql(&self) -> String {
+        crate::sql::expression(self, 0)
+    }
+}
+
 impl Display for UnaryOperator {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             UnaryOperator::Minus => write!(f, "-"),
             UnaryOperator::Plus => write!(f, "+"),
+            UnaryOperator::BitwiseNot => write!(f, "~"),
             UnaryOperator::Desc => write!(f, "DESC"),
             UnaryOperator::Asc => write!(f, "ASC"),
             UnaryOperator::Not => write!(f, "NOT"),
@@ -461,6 +968,19 @@ impl Display for BinaryOperator {
             BinaryOperator::NotEqual => write!(f, "!="),
             BinaryOperator::Multiply => write!(f, "*"),
             BinaryOperator::Divide => write!(f, "/"),
+            BinaryOperator::Modulo => write!(f, "%"),
+            BinaryOperator::Power => write!(f, "^"),
+            BinaryOperator::BitwiseAnd => write!(f, "&"),
+            BinaryOperator::BitwiseOr => write!(f, "|"),
+            BinaryOperator::BitwiseXor => write!(f, "^"),
+            BinaryOperator::ShiftLeft => write!(f, "<<"),
+            BinaryOperator::ShiftRight => write!(f, ">>"),
+            BinaryOperator::IsDistinctFrom => write!(f, "IS DISTINCT FROM"),
+            BinaryOperator::IsNotDistinctFrom => write!(f, "IS NOT DISTINCT FROM"),
+            BinaryOperator::SimilarTo => write!(f, "SIMILAR TO"),
+            BinaryOperator::NotSimilarTo => write!(f, "NOT SIMILAR TO"),
+            BinaryOperator::RegexMatch => write!(f, "REGEXP"),
+            BinaryOperator::NotRegexMatch => write!(f, "NOT REGEXP"),
             BinaryOperator::Minus => write!(f, "-"),
             BinaryOperator::Plus => write!(f, "+"),
             BinaryOperator::And => write!(f, "AND"),
@@ -469,20 +989,120 @@ impl Display for BinaryOperator {
     }
 }
 
+impl Display for DateField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateField::Year => write!(f, "YEAR"),
+            DateField::Quarter => write!(f, "QUARTER"),
+            DateField::Month => write!(f, "MONTH"),
+            DateField::Week => write!(f, "WEEK"),
+            DateField::Day => write!(f, "DAY"),
+            DateField::Hour => write!(f, "HOUR"),
+            DateField::Minute => write!(f, "MINUTE"),
+            DateField::Second => write!(f, "SECOND"),
+        }
+    }
+}
+
+impl Display for Quantifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Quantifier::Any => write!(f, "ANY"),
+            Quantifier::All => write!(f, "ALL"),
+        }
+    }
+}
+
+impl Display for TemporalKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemporalKind::Date => write!(f, "DATE"),
+            TemporalKind::Time => write!(f, "TIME"),
+            TemporalKind::Timestamp => write!(f, "TIMESTAMP"),
+        }
+    }
+}
+
 impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_sql())
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_sql())
+    }
+}
+
+impl Display for DBType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", crate::sql::db_type(self))
+    }
+}
+
+impl Display for LockingClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.strength {
+            LockStrength::Update => write!(f, "FOR UPDATE")?,
+            LockStrength::Share => write!(f, "FOR SHARE")?,
+        }
+        match self.wait_policy {
+            WaitPolicy::Wait => Ok(()),
+            WaitPolicy::NoWait => write!(f, " NOWAIT"),
+            WaitPolicy::SkipLocked => write!(f, " SKIP LOCKED"),
+        }
+    }
+}
+
+impl Display for JoinType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Expression::BinaryOperation { left_operand, operator, right_operand } => {
-                write!(f, "({:?} {:?} {:?})", left_operand, operator, right_operand)
-            }
-            Expression::UnaryOperation { operand, operator } => {
-                write!(f, "({:?} {:?})", operator, operand)
-            }
-            Expression::Number(num) => write!(f, "{num}"),
-            Expression::Identifier(iden) => write!(f, "{}", iden),
-            Expression::String(str) => write!(f, "\"{}\"", str),
-            Expression::Bool(b) => write!(f, "{}", b),
-            Expression::Wildcard => write!(f, "*"),
+            JoinType::Inner => write!(f, "INNER JOIN"),
+            JoinType::Left => write!(f, "LEFT JOIN"),
+            JoinType::Right => write!(f, "RIGHT JOIN"),
+            JoinType::Full => write!(f, "FULL JOIN"),
         }
     }
+}
+
+impl ObjectName {
+    // A name without a schema
+    pub fn new(name: &str) -> Self {
+        ObjectName(vec![name.to_string()])
+    }
+
+    // The last part of the name, which names the object itself
+    pub fn base_name(&self) -> &str {
+        self.0.last().map(String::as_str).unwrap_or_default()
+    }
+
+    pub fn is_qualified(&self) -> bool {
+        self.0.len() > 1
+    }
+}
+
+impl Display for ObjectName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", crate::sql::GENERIC.object_name(self))
+    }
+}
+
+impl TableReference {
+    // The name under which the table is visible to the rest of the statement:
+    // its alias, or the last part of its name, the same as in SQL. Joins are
+    // not visible under a single name.
+    pub fn visible_name(&self) -> Option<&str> {
+        match self {
+            TableReference::Table { name, alias } => Some(alias.as_deref().unwrap_or(name.base_name())),
+            TableReference::Derived { alias, .. } => Some(alias),
+            TableReference::Join { .. } => None,
+        }
+    }
+}
+
+impl Display for TableReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", crate::sql::GENERIC.table_reference(self))
+    }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(PartialEq, Clone, Debug)]
@@ -5,8 +6,22 @@ pub enum Token {
     Keyword(Keyword),
     Identifier(String),
     String(String),
+    // A string written with a prefix: `N'...'` of the national character set,
+    // and `E'...'` of PostgreSQL, in which backslashes are escapes
+    NationalString(String),
+    EscapeString(String),
     Number(u64),
-    Invalid(char),
+    Float(f64),
+    // The digits of a `B'1010'` literal
+    BitString(String),
+    // A bind parameter as written, like `?`, `$1` or `:name`
+    Placeholder(String),
+    // Whitespace and comments as written, only returned by a tokenizer that
+    // was asked to keep them
+    Whitespace(String),
+    Comment(String),
+    // Text that is not a token, with where it was found
+    Invalid { text: String, span: Span },
     RightParentheses,
     LeftParentheses,
     GreaterThan,
@@ -17,13 +32,53 @@ pub enum Token {
     NotEqual,
     Star,
     Divide,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
+    ShiftLeft,
+    ShiftRight,
+    Tilde,
+    NotTilde,
     Minus,
     Plus,
     Comma,
+    Dot,
+    DoubleColon,
     Semicolon,
     Eof,
 }
 
+/// A range of bytes `start..end` of the input, used to tell where a token or a
+/// bigger piece of a statement is. `&input[span.start..span.end]` is the text it covers.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A place in the input, for telling a person where to look: the byte offset,
+/// and the line and the column it is on. Lines and columns are counted from 1,
+/// and columns are counted in characters rather than bytes.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self { offset: 0, line: 1, column: 1 }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Keyword {
     Select,
@@ -47,6 +102,137 @@ pub enum Keyword {
     Bool,
     Varchar,
     Null,
+    Join,
+    Inner,
+    Left,
+    Right,
+    Full,
+    Outer,
+    On,
+    View,
+    As,
+    With,
+    In,
+    Exists,
+    For,
+    Update,
+    Limit,
+    Is,
+    Insert,
+    Drop,
+    Into,
+    Values,
+    Distinct,
+    Like,
+    Cast,
+    Decimal,
+    Numeric,
+    Float,
+    Real,
+    Double,
+    SmallInt,
+    BigInt,
+    TinyInt,
+    Blob,
+    Bytea,
+    // A word that an application registered in its `Keywords`, in upper case
+    Custom(String),
+}
+
+/// The words that the tokenizer reads as keywords rather than identifiers, looked up regardless of their case. `Keywords::default()` is the standard table of the parser. An application can add the words of its own extensions to it as `Keyword::Custom`, like the `VECTOR` type of a plugin, or remove a keyword so that it can be used as a name again, and then hand the table to `Tokenizer::set_keywords`.
+#[derive(Clone, Debug)]
+pub struct Keywords {
+    words: HashMap<String, Keyword>,
+}
+
+const STANDARD_KEYWORDS: &[(&str, Keyword)] = &[
+    ("SELECT", Keyword::Select),
+    ("CREATE", Keyword::Create),
+    ("TABLE", Keyword::Table),
+    ("WHERE", Keyword::Where),
+    ("ORDER", Keyword::Order),
+    ("BY", Keyword::By),
+    ("ASC", Keyword::Asc),
+    ("DESC", Keyword::Desc),
+    ("FROM", Keyword::From),
+    ("AND", Keyword::And),
+    ("OR", Keyword::Or),
+    ("NOT", Keyword::Not),
+    ("TRUE", Keyword::True),
+    ("FALSE", Keyword::False),
+    ("PRIMARY", Keyword::Primary),
+    ("KEY", Keyword::Key),
+    ("CHECK", Keyword::Check),
+    ("INT", Keyword::Int),
+    ("BOOL", Keyword::Bool),
+    ("VARCHAR", Keyword::Varchar),
+    ("NULL", Keyword::Null),
+    ("JOIN", Keyword::Join),
+    ("INNER", Keyword::Inner),
+    ("LEFT", Keyword::Left),
+    ("RIGHT", Keyword::Right),
+    ("FULL", Keyword::Full),
+    ("OUTER", Keyword::Outer),
+    ("ON", Keyword::On),
+    ("VIEW", Keyword::View),
+    ("AS", Keyword::As),
+    ("WITH", Keyword::With),
+    ("IN", Keyword::In),
+    ("EXISTS", Keyword::Exists),
+    ("FOR", Keyword::For),
+    ("UPDATE", Keyword::Update),
+    ("LIMIT", Keyword::Limit),
+    ("IS", Keyword::Is),
+    ("INSERT", Keyword::Insert),
+    ("DROP", Keyword::Drop),
+    ("INTO", Keyword::Into),
+    ("VALUES", Keyword::Values),
+    ("DISTINCT", Keyword::Distinct),
+    ("LIKE", Keyword::Like),
+    ("CAST", Keyword::Cast),
+    ("DECIMAL", Keyword::Decimal),
+    ("NUMERIC", Keyword::Numeric),
+    ("FLOAT", Keyword::Float),
+    ("REAL", Keyword::Real),
+    ("DOUBLE", Keyword::Double),
+    ("SMALLINT", Keyword::SmallInt),
+    ("BIGINT", Keyword::BigInt),
+    ("TINYINT", Keyword::TinyInt),
+    ("BLOB", Keyword::Blob),
+    ("BYTEA", Keyword::Bytea),
+];
+
+impl Keywords {
+    // The keyword the word stands for, if any
+    pub fn get(&self, word: &str) -> Option<&Keyword> {
+        self.words.get(&word.to_uppercase())
+    }
+    
+    // Every word of the table, in upper case
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.keys().map(String::as_str)
+    }
+    
+    pub fn insert(&mut self, word: &str, keyword: Keyword) {
+        self.words.insert(word.to_uppercase(), keyword);
+    }
+    
+    // Adds a word as a keyword of its own, `Keyword::Custom`
+    pub fn insert_custom(&mut self, word: &str) {
+        let word = word.to_uppercase();
+        self.words.insert(word.clone(), Keyword::Custom(word));
+    }
+    
+    pub fn remove(&mut self, word: &str) -> Option<Keyword> {
+        self.words.remove(&word.to_uppercase())
+    }
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        let words = STANDARD_KEYWORDS.iter().map(|(word, keyword)| (word.to_string(), keyword.clone())).collect();
+        Self { words }
+    }
 }
 
 impl Display for Token {
@@ -56,6 +242,11 @@ impl Display for Token {
             Token::Identifier(iden) => write!(f, "{:?}", iden),
             Token::String(str) => write!(f, "{:?}", str),
             Token::Number(num) => write!(f, "{:?}", num),
+            Token::Float(num) => write!(f, "{:?}", num),
+            Token::NationalString(str) => write!(f, "N{:?}", str),
+            Token::EscapeString(str) => write!(f, "E{:?}", str),
+            Token::BitString(bits) => write!(f, "B'{}'", bits),
+            Token::Placeholder(text) => write!(f, "{}", text),
             Token::RightParentheses => write!(f, "("),
             Token::LeftParentheses => write!(f, ")"),
             Token::GreaterThan => write!(f, ">"),
@@ -66,12 +257,23 @@ impl Display for Token {
             Token::NotEqual => write!(f, "!="),
             Token::Star => write!(f, "*"),
             Token::Divide => write!(f, "/"),
+            Token::Percent => write!(f, "%"),
+            Token::Ampersand => write!(f, "&"),
+            Token::Pipe => write!(f, "|"),
+            Token::Caret => write!(f, "^"),
+            Token::ShiftLeft => write!(f, "<<"),
+            Token::ShiftRight => write!(f, ">>"),
+            Token::Tilde => write!(f, "~"),
+            Token::NotTilde => write!(f, "!~"),
             Token::Minus => write!(f, "-"),
             Token::Plus => write!(f, "+"),
             Token::Comma => write!(f, ","),
+            Token::Dot => write!(f, "."),
+            Token::DoubleColon => write!(f, "::"),
             Token::Semicolon => write!(f, ";"),
             Token::Eof => write!(f, "Eof"),
-            Token::Invalid(c) => write!(f, "{}", c),
+            Token::Whitespace(text) | Token::Comment(text) => write!(f, "{}", text),
+            Token::Invalid { text, .. } => write!(f, "{}", text),
         }
     }
 }
@@ -100,6 +302,40 @@ impl Display for Keyword {
             Keyword::Bool => write!(f, "Bool"),
             Keyword::Varchar => write!(f, "Varchar"),
             Keyword::Null => write!(f, "Null"),
+            Keyword::Join => write!(f, "Join"),
+            Keyword::Inner => write!(f, "Inner"),
+            Keyword::Left => write!(f, "Left"),
+            Keyword::Right => write!(f, "Right"),
+            Keyword::Full => write!(f, "Full"),
+            Keyword::Outer => write!(f, "Outer"),
+            Keyword::On => write!(f, "On"),
+            Keyword::View => write!(f, "View"),
+            Keyword::As => write!(f, "As"),
+            Keyword::With => write!(f, "With"),
+            Keyword::In => write!(f, "In"),
+            Keyword::Exists => write!(f, "Exists"),
+            Keyword::For => write!(f, "For"),
+            Keyword::Update => write!(f, "Update"),
+            Keyword::Limit => write!(f, "Limit"),
+            Keyword::Is => write!(f, "Is"),
+            Keyword::Insert => write!(f, "Insert"),
+            Keyword::Drop => write!(f, "Drop"),
+            Keyword::Into => write!(f, "Into"),
+            Keyword::Values => write!(f, "Values"),
+            Keyword::Distinct => write!(f, "Distinct"),
+            Keyword::Like => write!(f, "Like"),
+            Keyword::Cast => write!(f, "Cast"),
+            Keyword::Decimal => write!(f, "Decimal"),
+            Keyword::Numeric => write!(f, "Numeric"),
+            Keyword::Float => write!(f, "Float"),
+            Keyword::Real => write!(f, "Real"),
+            Keyword::Double => write!(f, "Double"),
+            Keyword::SmallInt => write!(f, "SmallInt"),
+            Keyword::BigInt => write!(f, "BigInt"),
+            Keyword::TinyInt => write!(f, "TinyInt"),
+            Keyword::Blob => write!(f, "Blob"),
+            Keyword::Bytea => write!(f, "Bytea"),
+            Keyword::Custom(word) => write!(f, "{}", word),
         }
     }
 }
\ No newline at end of file
@@ -1,55 +1,331 @@
-use crate::token::{Keyword, Token};
-use std::iter::Peekable;
+use crate::dialects::Dialect;
+use crate::messages::{EnglishMessages, Message, Messages};
+use crate::token::{Keywords, Position, Span, Token};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
 use std::str::Chars;
+use std::sync::LazyLock;
+
+pub(crate) static STANDARD_KEYWORDS: LazyLock<Keywords> = LazyLock::new(Keywords::default);
+
+/// An error the tokenizer recovered from in lenient mode, see `Tokenizer::set_lenient`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct LexicalError {
+    pub message: String,
+    pub position: Position,
+    pub span: Span,
+}
 
 pub struct Tokenizer<'a> {
-    input: Peekable<Chars<'a>>,
+    input: Source<'a>,
+    lookahead: VecDeque<char>, // Characters after the current one that were peeked at
     current_char: Option<char>,
     reached_end: bool, // EOF flag
+    offset: usize, // Byte offset of the current character
+    line: usize, // Line of the current character, counted from 1
+    column: usize, // Column of the current character, counted from 1
+    token_start: Position, // Where the last token started
+    lexeme: String, // The text of the last token, only kept in lenient mode
+    messages: &'a dyn Messages,
+    keywords: &'a Keywords,
+    dialect: Dialect,
+    backslash_escapes: bool,
+    preserve_trivia: bool,
+    lenient: bool,
+    errors: Vec<LexicalError>,
 }
 
 impl<'a> Tokenizer<'a> {
     pub fn new(input: &'a str) -> Self {
-        let mut chars = input.chars().peekable();
-        let current_char = chars.next();
+        Self::with_messages(input, &EnglishMessages)
+    }
+    
+    // Creates a tokenizer that reports errors using the given message catalog
+    pub fn with_messages(input: &'a str, messages: &'a dyn Messages) -> Self {
+        Self::from_source(Source::Text(input.chars()), messages)
+    }
+    
+    // Creates a tokenizer that reads the input from a reader as the tokens are
+    // requested, so that large dumps never have to be loaded into memory as a
+    // whole. The input must be UTF-8; a failed read or an invalid byte ends the
+    // input, and the tokenizer returns the failure as an error.
+    pub fn from_reader(reader: impl Read + 'a) -> Self {
+        Self::from_source(Source::Reader(ReaderChars::new(reader)), &EnglishMessages)
+    }
+    
+    fn from_source(mut input: Source<'a>, messages: &'a dyn Messages) -> Self {
+        let current_char = input.next();
         Self {
-            input: chars,
+            input,
+            lookahead: VecDeque::new(),
             current_char,
             reached_end: false, // EOF flag
+            offset: 0,
+            line: 1,
+            column: 1,
+            token_start: Position::default(),
+            lexeme: String::new(),
+            messages,
+            keywords: &STANDARD_KEYWORDS,
+            dialect: Dialect::Generic,
+            backslash_escapes: false,
+            preserve_trivia: false,
+            lenient: false,
+            errors: Vec::new(),
         }
     }
+    
+    pub(crate) fn set_messages(&mut self, messages: &'a dyn Messages) {
+        self.messages = messages;
+    }
+    
+    // Makes the tokenizer read keywords from the given table instead of the
+    // standard one, see `Keywords`
+    pub fn set_keywords(&mut self, keywords: &'a Keywords) {
+        self.keywords = keywords;
+    }
+    
+    pub(crate) fn keywords(&self) -> &Keywords {
+        self.keywords
+    }
+    
+    // Makes the tokenizer read the input as the given dialect does: backticks
+    // only quote identifiers in the dialects that have them, and MySQL reads
+    // strings with backslash escapes (see `set_backslash_escapes`) and `#` as
+    // the start of a comment
+    pub fn set_dialect(&mut self, dialect: Dialect) {
+        self.dialect = dialect;
+        self.backslash_escapes = dialect == Dialect::MySql;
+    }
+    
+    // Makes a backslash escape the character after it inside of strings, the
+    // way MySQL reads them: `'it\'s'`, or `'\n'` for a new line. Otherwise a
+    // backslash is an ordinary character, and only a doubled quote (`'it''s'`)
+    // stands for the quote itself.
+    pub fn set_backslash_escapes(&mut self, enabled: bool) {
+        self.backslash_escapes = enabled;
+    }
+
+    // Makes the tokenizer return whitespace and comments as tokens instead of
+    // skipping them, so that a formatter can write them back out
+    pub fn set_preserve_trivia(&mut self, enabled: bool) {
+        self.preserve_trivia = enabled;
+    }
+
+    // Makes the tokenizer carry on after an error instead of returning it: the
+    // error is recorded (see `errors`) and the text it was found in becomes an
+    // Invalid token. Invalid characters are recorded as errors too, so that an
+    // editor can show every problem of the input at once.
+    pub fn set_lenient(&mut self, enabled: bool) {
+        self.lenient = enabled;
+    }
+    
+    // The errors recorded so far in lenient mode
+    pub fn errors(&self) -> &[LexicalError] {
+        &self.errors
+    }
+
+    // The span of the last token returned by the tokenizer
+    pub fn span(&self) -> Span {
+        Span {
+            start: self.token_start.offset,
+            end: self.offset,
+        }
+    }
+    
+    // Where the last token returned by the tokenizer started, or where the
+    // error it returned was found
+    pub fn position(&self) -> Position {
+        self.token_start
+    }
+    
+    // Turns the tokenizer into an iterator over the tokens together with the
+    // spans of the input they were read from, for tools that need to map the
+    // tokens back to the text, like highlighters
+    pub fn spanned(mut self) -> impl Iterator<Item = Result<(Token, Span), String>> + 'a {
+        std::iter::from_fn(move || {
+            let token = self.next()?;
+            Some(token.map(|token| (token, self.span())))
+        })
+    }
 
     fn advance(&mut self) {
-        self.current_char = self.input.next();
+        if let Some(c) = self.current_char {
+            if self.lenient {
+                self.lexeme.push(c);
+            }
+            self.offset += c.len_utf8();
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.current_char = self.lookahead.pop_front().or_else(|| self.input.next());
+    }
+    
+    // The character `n` places after the current one, without consuming it
+    fn peek(&mut self, n: usize) -> Option<char> {
+        while self.lookahead.len() <= n {
+            let c = self.input.next()?;
+            self.lookahead.push_back(c);
+        }
+        self.lookahead.get(n).copied()
     }
 
-    fn skip_whitespace(&mut self) {
+    // Skips whitespace and line comments, which start with `--` (or with `#`
+    // in MySQL) and go until the end of the line
+    fn skip_trivia(&mut self) {
         while let Some(c) = self.current_char {
-            if !c.is_whitespace() {
+            if c.is_whitespace() {
+                self.advance();
+            } else if self.at_comment(c) {
+                while !matches!(self.current_char, None | Some('\n')) {
+                    self.advance();
+                }
+            } else {
                 break;
             }
-            self.advance();
+        }
+    }
+    
+    fn at_comment(&mut self, c: char) -> bool {
+        (c == '#' && self.dialect == Dialect::MySql) || (c == '-' && self.peek(0) == Some('-'))
+    }
+    
+    // Reads a run of whitespace or a line comment, the comment without the
+    // new line that ends it
+    fn read_trivia(&mut self) -> Option<Token> {
+        let c = self.current_char?;
+        let mut text = String::new();
+        if c.is_whitespace() {
+            while let Some(c) = self.current_char.filter(|c| c.is_whitespace()) {
+                text.push(c);
+                self.advance();
+            }
+            Some(Token::Whitespace(text))
+        } else if self.at_comment(c) {
+            while let Some(c) = self.current_char.filter(|&c| c != '\n') {
+                text.push(c);
+                self.advance();
+            }
+            Some(Token::Comment(text))
+        } else {
+            None
         }
     }
 
     fn read_number(&mut self) -> Token {
         let mut number = String::new();
         
+        // `0x` starts a hexadecimal number only when a hexadecimal digit follows it
+        if self.current_char == Some('0')
+            && matches!(self.peek(0), Some('x' | 'X'))
+            && self.peek(1).is_some_and(|c| c.is_ascii_hexdigit())
+        {
+            self.advance();
+            self.advance();
+            return self.read_hex_number();
+        }
+        
+        self.read_digits(&mut number);
+        let mut float = false;
+        
+        // A dot makes the number a decimal fraction only when digits follow it,
+        // so `1.` is still a number followed by a dot
+        if self.current_char == Some('.') && self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+            number.push('.');
+            self.advance();
+            self.read_digits(&mut number);
+            float = true;
+        }
+        
+        // Likewise an exponent needs its digits, so `1e` is a number followed by an identifier
+        if matches!(self.current_char, Some('e' | 'E')) {
+            let exponent = match self.peek(0) {
+                Some('+' | '-') => self.peek(1).is_some_and(|c| c.is_ascii_digit()),
+                next => next.is_some_and(|c| c.is_ascii_digit()),
+            };
+            if exponent {
+                number.push('e');
+                self.advance();
+                if let Some(sign @ ('+' | '-')) = self.current_char {
+                    number.push(sign);
+                    self.advance();
+                }
+                self.read_digits(&mut number);
+                float = true;
+            }
+        }
+        
+        // A float too big for f64 parses as infinity, which is no number SQL can hold
+        if float {
+            return match number.parse::<f64>() {
+                Ok(n) if n.is_finite() => Token::Float(n),
+                _ => self.invalid(number),
+            };
+        }
+        
+        match number.parse::<u64>() {
+            Ok(n) => Token::Number(n),
+            Err(_) => self.invalid(number),
+        }
+    }
+
+    fn read_hex_number(&mut self) -> Token {
+        let mut digits = String::new();
         while let Some(c) = self.current_char {
-            if c.is_digit(10) {
-                number.push(c);
+            if c.is_ascii_hexdigit() {
+                digits.push(c);
                 self.advance();
             } else {
                 break;
             }
         }
         
-        match number.parse::<u64>() {
+        match u64::from_str_radix(&digits, 16) {
             Ok(n) => Token::Number(n),
-            Err(_) => Token::Invalid('0'),
+            Err(_) => self.invalid(format!("0x{}", digits)),
         }
     }
 
+    // Reads a `B'1010'` literal, starting at the B
+    fn read_bit_string(&mut self) -> Result<Token, String> {
+        self.advance(); // Skip the B
+        let bits = self.read_quoted('\'')?;
+        if bits.chars().all(|c| c == '0' || c == '1') {
+            Ok(Token::BitString(bits))
+        } else {
+            Err(self.messages.render(&Message::InvalidBitString { value: bits }))
+        }
+    }
+
+    fn read_digits(&mut self, number: &mut String) {
+        while let Some(c) = self.current_char {
+            if c.is_ascii_digit() {
+                number.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Reads the name of a `:name` or `@name` parameter, after its prefix
+    fn read_named_placeholder(&mut self, prefix: char) -> Token {
+        let mut placeholder = String::from(prefix);
+        while let Some(c) = self.current_char {
+            if c.is_alphanumeric() || c == '_' {
+                placeholder.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        Token::Placeholder(placeholder)
+    }
+
     fn read_identifier_or_keyword(&mut self) -> Token {
         let mut identifier = String::new();
         
@@ -62,64 +338,164 @@ impl<'a> Tokenizer<'a> {
             }
         }
         
-        // Check if it's a keyword
-        match identifier.to_uppercase().as_str() {
-            "SELECT" => Token::Keyword(Keyword::Select),
-            "CREATE" => Token::Keyword(Keyword::Create),
-            "TABLE" => Token::Keyword(Keyword::Table),
-            "WHERE" => Token::Keyword(Keyword::Where),
-            "ORDER" => Token::Keyword(Keyword::Order),
-            "BY" => Token::Keyword(Keyword::By),
-            "ASC" => Token::Keyword(Keyword::Asc),
-            "DESC" => Token::Keyword(Keyword::Desc),
-            "FROM" => Token::Keyword(Keyword::From),
-            "AND" => Token::Keyword(Keyword::And),
-            "OR" => Token::Keyword(Keyword::Or),
-            "NOT" => Token::Keyword(Keyword::Not),
-            "TRUE" => Token::Keyword(Keyword::True),
-            "FALSE" => Token::Keyword(Keyword::False),
-            "PRIMARY" => Token::Keyword(Keyword::Primary),
-            "KEY" => Token::Keyword(Keyword::Key),
-            "CHECK" => Token::Keyword(Keyword::Check),
-            "INT" => Token::Keyword(Keyword::Int),
-            "BOOL" => Token::Keyword(Keyword::Bool),
-            "VARCHAR" => Token::Keyword(Keyword::Varchar),
-            "NULL" => Token::Keyword(Keyword::Null),
-            "NOT NULL" => Token::Keyword(Keyword::Null), // This won't work as is, will handle "NOT NULL" differently
-            _ => Token::Identifier(identifier),
+        match self.keywords.get(&identifier) {
+            Some(keyword) => Token::Keyword(keyword.clone()),
+            None => Token::Identifier(identifier),
         }
     }
 
     fn read_string(&mut self, quote_char: char) -> Result<Token, String> {
+        self.read_quoted(quote_char).map(Token::String)
+    }
+
+    // Reads an `N'...'` or `E'...'` string, starting at the prefix
+    fn read_prefixed_string(&mut self, prefix: char) -> Result<Token, String> {
+        self.advance(); // Skip the prefix
+        if prefix.eq_ignore_ascii_case(&'n') {
+            return self.read_quoted('\'').map(Token::NationalString);
+        }
+        let backslash_escapes = std::mem::replace(&mut self.backslash_escapes, true);
+        let value = self.read_quoted('\'');
+        self.backslash_escapes = backslash_escapes;
+        value.map(Token::EscapeString)
+    }
+
+    fn read_quoted(&mut self, quote_char: char) -> Result<String, String> {
         let mut string_value = String::new();
         self.advance(); // Skip the opening quote
         
         while let Some(c) = self.current_char {
-            if c == '\'' || c == '"' {
+            if c == quote_char && self.peek(0) == Some(quote_char) {
+                // Two quotes stand for one
+                string_value.push(c);
+                self.advance();
+                self.advance();
+            } else if c == '\\' && self.backslash_escapes {
+                self.advance();
+                let Some(escaped) = self.current_char else {
+                    break;
+                };
+                match escaped {
+                    'n' => string_value.push('\n'),
+                    't' => string_value.push('\t'),
+                    'r' => string_value.push('\r'),
+                    '0' => string_value.push('\0'),
+                    // MySQL keeps the backslash before the wildcards of LIKE
+                    '%' | '_' => {
+                        string_value.push('\\');
+                        string_value.push(escaped);
+                    },
+                    _ => string_value.push(escaped),
+                }
+                self.advance();
+            } else if c == '\'' || c == '"' {
                 if c != quote_char {
                     // Advance past the mismatched quote to prevent double error
                     self.advance();
-                    return Err(format!("Mismatched quotes: string started with {} but found {}", quote_char, c));
+                    return Err(self.messages.render(&Message::MismatchedQuotes { opening: quote_char, found: c }));
                 }
                 self.advance();
-                return Ok(Token::String(string_value));
+                return Ok(string_value);
             } else {
                 string_value.push(c);
                 self.advance();
             }
         }
         
-        Err(format!("Unterminated string starting with {}", quote_char))
+        Err(self.messages.render(&Message::UnterminatedString { quote: quote_char }))
+    }
+
+    // Reads an identifier quoted with backticks, as written by MySQL. Quoted
+    // identifiers are never keywords, and two backticks stand for one.
+    fn read_quoted_identifier(&mut self) -> Result<Token, String> {
+        let mut identifier = String::new();
+        self.advance(); // Skip the opening backtick
+        
+        while let Some(c) = self.current_char {
+            self.advance();
+            if c != '`' {
+                identifier.push(c);
+            } else if self.current_char == Some('`') {
+                identifier.push(c);
+                self.advance();
+            } else if !self.dialect.has_backtick_identifiers() {
+                // The whole identifier is read first, so that the tokenizer
+                // carries on after it
+                let syntax = "Quoting identifiers with backticks".to_string();
+                return Err(self.messages.render(&Message::UnsupportedSyntax { syntax, dialect: self.dialect.to_string() }));
+            } else {
+                return Ok(Token::Identifier(identifier));
+            }
+        }
+        
+        Err(self.messages.render(&Message::UnterminatedQuotedIdentifier))
     }
 
     pub fn next_token(&mut self) -> Result<Token, String> {
-        self.skip_whitespace();
+        let token = self.read_token();
+        // A failed read cuts the input short, so the token read last can not be
+        // trusted and the failure is reported in its place
+        if let Some(reason) = self.input.take_error() {
+            let message = self.messages.render(&Message::UnreadableInput { reason });
+            return self.recover(message, Token::Eof);
+        }
+        match token {
+            Err(message) => {
+                let text = std::mem::take(&mut self.lexeme);
+                let invalid = self.invalid(text);
+                self.recover(message, invalid)
+            },
+            Ok(Token::Invalid { text, span }) if self.lenient => {
+                let message = self.messages.render(&Message::InvalidToken { found: text.clone() });
+                self.recover(message, Token::Invalid { text, span })
+            },
+            token => token,
+        }
+    }
+    
+    // Returns the error, or in lenient mode records it and returns the token to
+    // carry on with instead
+    fn recover(&mut self, message: String, token: Token) -> Result<Token, String> {
+        if !self.lenient {
+            return Err(message);
+        }
+        self.errors.push(LexicalError { message, position: self.token_start, span: self.span() });
+        Ok(token)
+    }
+    
+    // Whether a token (or whitespace or a comment) can start with the character
+    fn starts_token(&self, c: char) -> bool {
+        c.is_whitespace()
+            || c.is_ascii_alphanumeric()
+            || "_\"'`(),.;:@<>=!~*/%?$&|^+-".contains(c)
+            || (c == '#' && self.dialect == Dialect::MySql)
+    }
+    
+    // An Invalid token of the given text, which was read since the token started
+    fn invalid(&self, text: String) -> Token {
+        Token::Invalid { text, span: self.span() }
+    }
+    
+    fn read_token(&mut self) -> Result<Token, String> {
+        if !self.preserve_trivia {
+            self.skip_trivia();
+        }
+        self.token_start = Position { offset: self.offset, line: self.line, column: self.column };
+        self.lexeme.clear();
+        if self.preserve_trivia {
+            if let Some(trivia) = self.read_trivia() {
+                return Ok(trivia);
+            }
+        }
         
         if let Some(current) = self.current_char {
-            let token = match current {
+            match current {
                 '0'..='9' => Ok(self.read_number()),
+                'b' | 'B' if self.peek(0) == Some('\'') => self.read_bit_string(),
+                'n' | 'N' | 'e' | 'E' if self.peek(0) == Some('\'') => self.read_prefixed_string(current),
                 'a'..='z' | 'A'..='Z' | '_' => Ok(self.read_identifier_or_keyword()),
                 '"' | '\'' => self.read_string(current),
+                '`' => self.read_quoted_identifier(),
                 '(' => {
                     self.advance();
                     Ok(Token::LeftParentheses)
@@ -132,26 +508,63 @@ impl<'a> Tokenizer<'a> {
                     self.advance();
                     Ok(Token::Comma)
                 },
+                '.' => {
+                    self.advance();
+                    Ok(Token::Dot)
+                },
                 ';' => {
                     self.advance();
                     Ok(Token::Semicolon)
                 },
+                ':' => {
+                    self.advance();
+                    match self.current_char {
+                        Some(':') => {
+                            self.advance();
+                            Ok(Token::DoubleColon)
+                        },
+                        Some(c) if c.is_alphabetic() || c == '_' => Ok(self.read_named_placeholder(':')),
+                        _ => Ok(self.invalid(":".to_string())),
+                    }
+                },
+                '@' => {
+                    self.advance();
+                    match self.current_char {
+                        Some(c) if c.is_alphabetic() || c == '_' => Ok(self.read_named_placeholder('@')),
+                        _ => Ok(self.invalid("@".to_string())),
+                    }
+                },
                 '>' => {
                     self.advance();
-                    if let Some('=') = self.current_char {
-                        self.advance();
-                        Ok(Token::GreaterThanOrEqual)
-                    } else {
-                        Ok(Token::GreaterThan)
+                    match self.current_char {
+                        Some('=') => {
+                            self.advance();
+                            Ok(Token::GreaterThanOrEqual)
+                        },
+                        Some('>') => {
+                            self.advance();
+                            Ok(Token::ShiftRight)
+                        },
+                        _ => Ok(Token::GreaterThan),
                     }
                 },
                 '<' => {
                     self.advance();
-                    if let Some('=') = self.current_char {
-                        self.advance();
-                        Ok(Token::LessThanOrEqual)
-                    } else {
-                        Ok(Token::LessThan)
+                    match self.current_char {
+                        Some('=') => {
+                            self.advance();
+                            Ok(Token::LessThanOrEqual)
+                        },
+                        Some('<') => {
+                            self.advance();
+                            Ok(Token::ShiftLeft)
+                        },
+                        // The standard spelling of !=
+                        Some('>') => {
+                            self.advance();
+                            Ok(Token::NotEqual)
+                        },
+                        _ => Ok(Token::LessThan),
                     }
                 },
                 '=' => {
@@ -160,13 +573,22 @@ impl<'a> Tokenizer<'a> {
                 },
                 '!' => {
                     self.advance();
-                    if let Some('=') = self.current_char {
-                        self.advance();
-                        Ok(Token::NotEqual)
-                    } else {
-                        Ok(Token::Invalid('!'))
+                    match self.current_char {
+                        Some('=') => {
+                            self.advance();
+                            Ok(Token::NotEqual)
+                        },
+                        Some('~') => {
+                            self.advance();
+                            Ok(Token::NotTilde)
+                        },
+                        _ => Ok(self.invalid("!".to_string())),
                     }
                 },
+                '~' => {
+                    self.advance();
+                    Ok(Token::Tilde)
+                },
                 '*' => {
                     self.advance();
                     Ok(Token::Star)
@@ -175,6 +597,36 @@ impl<'a> Tokenizer<'a> {
                     self.advance();
                     Ok(Token::Divide)
                 },
+                '%' => {
+                    self.advance();
+                    Ok(Token::Percent)
+                },
+                '?' => {
+                    self.advance();
+                    Ok(Token::Placeholder("?".to_string()))
+                },
+                '$' => {
+                    self.advance();
+                    let mut placeholder = String::from("$");
+                    self.read_digits(&mut placeholder);
+                    if placeholder.len() > 1 {
+                        Ok(Token::Placeholder(placeholder))
+                    } else {
+                        Ok(self.invalid(placeholder))
+                    }
+                },
+                '&' => {
+                    self.advance();
+                    Ok(Token::Ampersand)
+                },
+                '|' => {
+                    self.advance();
+                    Ok(Token::Pipe)
+                },
+                '^' => {
+                    self.advance();
+                    Ok(Token::Caret)
+                },
                 '+' => {
                     self.advance();
                     Ok(Token::Plus)
@@ -183,18 +635,108 @@ impl<'a> Tokenizer<'a> {
                     self.advance();
                     Ok(Token::Minus)
                 },
+                // Characters that can not start a token are taken together
                 _ => {
-                    self.advance();
-                    Ok(Token::Invalid(current))
+                    let mut text = String::new();
+                    while let Some(c) = self.current_char.filter(|&c| !self.starts_token(c)) {
+                        text.push(c);
+                        self.advance();
+                    }
+                    Ok(self.invalid(text))
                 }
-            };
-            token
+            }
         } else {
             Ok(Token::Eof)
         }
     }
 }
 
+// Where the tokenizer takes the characters of the input from
+enum Source<'a> {
+    Text(Chars<'a>),
+    Reader(ReaderChars<'a>),
+}
+
+impl Source<'_> {
+    fn next(&mut self) -> Option<char> {
+        match self {
+            Source::Text(chars) => chars.next(),
+            Source::Reader(chars) => chars.next(),
+        }
+    }
+    
+    fn take_error(&mut self) -> Option<String> {
+        match self {
+            Source::Text(_) => None,
+            Source::Reader(chars) => chars.error.take(),
+        }
+    }
+}
+
+// The characters of a reader, decoded one buffer at a time
+struct ReaderChars<'a> {
+    reader: BufReader<Box<dyn Read + 'a>>,
+    chars: VecDeque<char>,
+    partial: Vec<u8>, // The first bytes of a character split between two buffers
+    failed: bool,
+    error: Option<String>,
+}
+
+impl<'a> ReaderChars<'a> {
+    fn new(reader: impl Read + 'a) -> Self {
+        Self {
+            reader: BufReader::new(Box::new(reader)),
+            chars: VecDeque::new(),
+            partial: Vec::new(),
+            failed: false,
+            error: None,
+        }
+    }
+    
+    fn next(&mut self) -> Option<char> {
+        while self.chars.is_empty() {
+            if self.failed || !self.fill() {
+                return None;
+            }
+        }
+        self.chars.pop_front()
+    }
+    
+    // Decodes the next buffer, returning false at the end of the input
+    fn fill(&mut self) -> bool {
+        let bytes = match self.reader.fill_buf() {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::Interrupted => return true,
+            Err(e) => return self.fail(e.to_string()),
+        };
+        if bytes.is_empty() {
+            if !self.partial.is_empty() {
+                return self.fail("the last UTF-8 character is cut off".to_string());
+            }
+            return false;
+        }
+        self.partial.extend_from_slice(bytes);
+        let length = bytes.len();
+        self.reader.consume(length);
+        
+        let valid = match std::str::from_utf8(&self.partial) {
+            Ok(_) => self.partial.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return self.fail("invalid UTF-8".to_string()),
+        };
+        let text = String::from_utf8_lossy(&self.partial[..valid]);
+        self.chars.extend(text.chars());
+        self.partial.drain(..valid);
+        true
+    }
+    
+    fn fail(&mut self, reason: String) -> bool {
+        self.failed = true;
+        self.error = Some(reason);
+        false
+    }
+}
+
 impl<'a> Iterator for Tokenizer<'a> {
     type Item = Result<Token, String>;
     
@@ -0,0 +1,61 @@
+use crate::token::{Span, Token};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+
+/// What the parser did, in the order it did it: `Rule` is a grammar rule or branch it entered, named the same way as in `coverage::RULES`, and `Token` is a token it consumed from the tokenizer, with the span of the input it was read from. The parser reads one token ahead, so the token that decided which branch to take is consumed before the branch is entered.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TraceEvent {
+    Rule(&'static str),
+    Token(Token, Span),
+}
+
+thread_local! {
+    // The events of the current trace, or None when nothing is traced
+    static EVENTS: RefCell<Option<Vec<TraceEvent>>> = const { RefCell::new(None) };
+}
+
+// Called by the parser (through the `cover!` macro) every time a rule is entered
+pub fn record_rule(rule: &'static str) {
+    EVENTS.with(|events| {
+        if let Some(events) = events.borrow_mut().as_mut() {
+            events.push(TraceEvent::Rule(rule));
+        }
+    });
+}
+
+// Called by the parser for every token it reads
+pub fn record_token(token: &Token, span: Span) {
+    EVENTS.with(|events| {
+        if let Some(events) = events.borrow_mut().as_mut() {
+            events.push(TraceEvent::Token(token.clone(), span));
+        }
+    });
+}
+
+/// The events that the parser went through while parsing, see `trace`. Its `Display` implementation lists them one per line, with every token indented under the rule it was read in.
+#[derive(Debug, PartialEq, Default)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for event in &self.events {
+            match event {
+                TraceEvent::Rule(rule) => writeln!(f, "{}", rule)?,
+                TraceEvent::Token(token, span) => writeln!(f, "    {:?} at {}..{}", token, span.start, span.end)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Runs the parsing on the current thread and returns its result together with
+// the trace of everything the parser did meanwhile, to see why a query was
+// parsed the way it was
+pub fn trace<T>(parse: impl FnOnce() -> T) -> (T, Trace) {
+    let outer = EVENTS.with(|events| events.borrow_mut().replace(Vec::new()));
+    let result = parse();
+    let events = EVENTS.with(|events| std::mem::replace(&mut *events.borrow_mut(), outer));
+    (result, Trace { events: events.unwrap_or_default() })
+}
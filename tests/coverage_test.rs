@@ -0,0 +1,40 @@
+#![cfg(feature = "coverage")]
+
+use programming_languages_project_kyrylo_yezholov::coverage::{self, RULES};
+
+#[test]
+fn test_corpus_coverage() {
+    let report = coverage::measure([
+        "SELECT name, age * 2 FROM users WHERE age > 18 ORDER BY age DESC;",
+        "CREATE TABLE users(id INT PRIMARY KEY, name VARCHAR(255) NOT NULL);",
+    ]);
+
+    assert_eq!(report.hits.len(), RULES.len());
+    assert_eq!(report.hits_of("statement.select"), 1);
+    assert_eq!(report.hits_of("statement.create_table"), 1);
+    assert_eq!(report.hits_of("prefix.identifier"), 4);
+    assert!(report.exercised().contains(&"postfix.desc"));
+    assert!(report.unexercised().contains(&"prefix.plus"));
+    assert!(report.unexercised().contains(&"column.check"));
+}
+
+#[test]
+fn test_failing_queries_still_count() {
+    let report = coverage::measure(["SELECT +5 FROM"]);
+    assert_eq!(report.hits_of("prefix.plus"), 1);
+    assert_eq!(report.hits_of("prefix.number"), 1);
+}
+
+#[test]
+fn test_report_rendering() {
+    let report = coverage::measure(["SELECT * FROM users;"]);
+    let rendered = report.to_string();
+    assert!(rendered.contains("select.wildcard"));
+    let exercised = report.exercised().len();
+    assert!(rendered.ends_with(&format!(
+        "{}/{} rules exercised ({:.1}%)",
+        exercised,
+        RULES.len(),
+        exercised as f64 * 100.0 / RULES.len() as f64
+    )));
+}
@@ -0,0 +1,510 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement,
+    dialects::{mysql, sqlite},
+    Statement, Expression, BinaryOperator, TableColumn, TableConstraint, DBType, Constraint, TableReference, ObjectName, Tokenizer, Parser,
+    ParserOptions, Dialect
+};
+
+fn render(query: &str) -> String {
+    sqlite::render(&build_statement(query).unwrap())
+}
+
+#[test]
+fn test_sqlite_create_table() {
+    let sql = render("CREATE TABLE users(id INT PRIMARY KEY, name VARCHAR(255) NOT NULL, age INT CHECK(age >= 18 AND age < 150), active BOOL);");
+    assert_eq!(sql, [
+        "CREATE TABLE \"users\" (",
+        "    \"id\" INTEGER PRIMARY KEY,",
+        "    \"name\" TEXT NOT NULL CHECK (length(\"name\") <= 255),",
+        "    \"age\" INTEGER CHECK (\"age\" >= 18 AND \"age\" < 150),",
+        "    \"active\" INTEGER CHECK (\"active\" IN (0, 1))",
+        ");",
+    ].join("\n"));
+}
+
+#[test]
+fn test_sqlite_if_not_exists() {
+    assert_eq!(render("CREATE TABLE IF NOT EXISTS t(a INT);"), "CREATE TABLE IF NOT EXISTS \"t\" (\n    \"a\" INTEGER\n);");
+    assert_eq!(render("CREATE VIEW IF NOT EXISTS v AS SELECT a FROM t;"), "CREATE VIEW IF NOT EXISTS \"v\" AS SELECT \"a\" FROM \"t\";");
+}
+
+#[test]
+fn test_sqlite_like() {
+    assert_eq!(
+        render("SELECT * FROM t WHERE name NOT LIKE 'a\\_%' ESCAPE '\\' OR code LIKE 'x%';"),
+        "SELECT * FROM \"t\" WHERE \"name\" NOT LIKE 'a\\_%' ESCAPE '\\' OR \"code\" LIKE 'x%';"
+    );
+}
+
+#[test]
+fn test_sqlite_in_list() {
+    assert_eq!(
+        render("SELECT * FROM t WHERE status NOT IN ('a', 'b') AND id IN (1);"),
+        "SELECT * FROM \"t\" WHERE \"status\" NOT IN ('a', 'b') AND \"id\" IN (1);"
+    );
+}
+
+#[test]
+fn test_sqlite_is_null() {
+    assert_eq!(
+        render("SELECT * FROM t WHERE email IS NULL OR phone IS NOT NULL;"),
+        "SELECT * FROM \"t\" WHERE \"email\" IS NULL OR \"phone\" IS NOT NULL;"
+    );
+}
+
+#[test]
+fn test_sqlite_cast() {
+    assert_eq!(
+        render("SELECT CAST(age AS VARCHAR(3)), flag::INT FROM t;"),
+        "SELECT CAST(\"age\" AS TEXT), CAST(\"flag\" AS INTEGER) FROM \"t\";"
+    );
+}
+
+#[test]
+fn test_sqlite_float() {
+    assert_eq!(render("SELECT price * 1.0 FROM t WHERE price > 9.99;"), "SELECT \"price\" * 1.0 FROM \"t\" WHERE \"price\" > 9.99;");
+}
+
+#[test]
+fn test_sqlite_bitwise() {
+    assert_eq!(render("SELECT * FROM t WHERE flags & 4 != 0;"), "SELECT * FROM \"t\" WHERE \"flags\" & 4 <> 0;");
+    // The bitwise operators share one precedence in SQLite and are evaluated
+    // from left to right, so only an operand on the right needs parentheses
+    assert_eq!(render("SELECT a | b & c, (a | b) & c, a << 1 + 2 FROM t;"), "SELECT \"a\" | (\"b\" & \"c\"), \"a\" | \"b\" & \"c\", \"a\" << 1 + 2 FROM \"t\";");
+    // SQLite has no ^, so the exclusive or of columns and literals is computed
+    // out of | and &, which reads both operands twice
+    assert_eq!(render("SELECT a ^ 3, (a ^ b) * 2 FROM t;"), "SELECT (\"a\" | 3) - (\"a\" & 3), ((\"a\" | \"b\") - (\"a\" & \"b\")) * 2 FROM \"t\";");
+    // Other operands could give another value the second time, like a call of
+    // random() or a subquery, so their ^ is kept for SQLite to reject
+    assert_eq!(render("SELECT a ^ 3 * b, random() ^ 1 FROM t;"), "SELECT \"a\" ^ 3 * \"b\", random() ^ 1 FROM \"t\";");
+    // So nested exclusive ors do not grow the statement, only the innermost one is computed
+    assert_eq!(render("SELECT a ^ b ^ c FROM t;"), "SELECT (\"a\" | \"b\") - (\"a\" & \"b\") ^ \"c\" FROM \"t\";");
+    assert_eq!(render("SELECT ~(a | b), ~a | b FROM t;"), "SELECT ~(\"a\" | \"b\"), ~\"a\" | \"b\" FROM \"t\";");
+}
+
+#[test]
+fn test_sqlite_numeric_types() {
+    assert_eq!(render("CREATE TABLE t(price DECIMAL(10, 2));"), "CREATE TABLE \"t\" (\n    \"price\" NUMERIC\n);");
+    assert_eq!(render("SELECT CAST(a AS DOUBLE PRECISION) FROM t;"), "SELECT CAST(\"a\" AS REAL) FROM \"t\";");
+}
+
+#[test]
+fn test_sqlite_binary_type() {
+    assert_eq!(render("CREATE TABLE t(data BYTEA NOT NULL);"), "CREATE TABLE \"t\" (\n    \"data\" BLOB NOT NULL\n);");
+}
+
+#[test]
+fn test_sqlite_default_values() {
+    assert_eq!(render("CREATE TABLE t(a INT DEFAULT 1 + 2);"), "CREATE TABLE \"t\" (\n    \"a\" INTEGER DEFAULT (1 + 2)\n);");
+}
+
+#[test]
+fn test_sqlite_varchar_without_length() {
+    // Without a length there is nothing to check
+    assert_eq!(render("CREATE TABLE t(name VARCHAR NOT NULL);"), "CREATE TABLE \"t\" (\n    \"name\" TEXT NOT NULL\n);");
+}
+
+#[test]
+fn test_sqlite_uuid_type() {
+    assert_eq!(render("CREATE TABLE t(id UUID PRIMARY KEY);"), "CREATE TABLE \"t\" (\n    \"id\" TEXT PRIMARY KEY CHECK (length(\"id\") = 36)\n);");
+}
+
+#[test]
+fn test_sqlite_date_and_time_types() {
+    assert_eq!(render("CREATE TABLE t(created TIMESTAMP WITH TIME ZONE);"), "CREATE TABLE \"t\" (\n    \"created\" TEXT\n);");
+}
+
+#[test]
+fn test_sqlite_bit_strings() {
+    assert_eq!(render("SELECT * FROM t WHERE flags = b'1010' OR mask = 0x10;"), "SELECT * FROM \"t\" WHERE \"flags\" = 10 OR \"mask\" = 16;");
+}
+
+#[test]
+fn test_sqlite_placeholders() {
+    assert_eq!(render("SELECT * FROM t WHERE a = ? AND b = $2;"), "SELECT * FROM \"t\" WHERE \"a\" = ? AND \"b\" = ?2;");
+}
+
+#[test]
+fn test_sqlite_temporal_literals() {
+    assert_eq!(render("SELECT * FROM t WHERE day < DATE '2024-01-01';"), "SELECT * FROM \"t\" WHERE \"day\" < '2024-01-01';");
+}
+
+#[test]
+fn test_sqlite_is_distinct_from() {
+    assert_eq!(
+        render("SELECT * FROM t WHERE a IS DISTINCT FROM b OR a IS NOT DISTINCT FROM 1;"),
+        "SELECT * FROM \"t\" WHERE \"a\" IS NOT \"b\" OR \"a\" IS 1;"
+    );
+}
+
+#[test]
+fn test_sqlite_tuples() {
+    assert_eq!(
+        render("SELECT * FROM t WHERE (a, b) = (1, 2) OR (a, b) IN ((3, 4), (5, 6)) OR a IN (7, 8);"),
+        "SELECT * FROM \"t\" WHERE (\"a\", \"b\") = (1, 2) OR (\"a\", \"b\") IN (VALUES (3, 4), (5, 6)) OR \"a\" IN (7, 8);"
+    );
+}
+
+#[test]
+fn test_sqlite_extract() {
+    assert_eq!(
+        render("SELECT EXTRACT(QUARTER FROM d) FROM t WHERE EXTRACT(YEAR FROM d) = 2024;"),
+        "SELECT (CAST(strftime('%m', \"d\") AS INTEGER) + 2) / 3 FROM \"t\" WHERE CAST(strftime('%Y', \"d\") AS INTEGER) = 2024;"
+    );
+}
+
+#[test]
+fn test_sqlite_insert() {
+    assert_eq!(
+        render("INSERT INTO users (id, name) VALUES (1, 'Ann'), (2, 'Bob');"),
+        "INSERT INTO \"users\" (\"id\", \"name\") VALUES (1, 'Ann'), (2, 'Bob');"
+    );
+    assert_eq!(
+        render("INSERT INTO archive SELECT * FROM orders;"),
+        "INSERT INTO \"archive\" SELECT * FROM \"orders\";"
+    );
+    assert_eq!(
+        render("INSERT INTO users (name) VALUES ('Ann') RETURNING id, TRUE AS created;"),
+        "INSERT INTO \"users\" (\"name\") VALUES ('Ann') RETURNING \"id\", 1 AS \"created\";"
+    );
+}
+
+#[test]
+fn test_sqlite_comments() {
+    assert_eq!(render("CREATE TABLE t(a INT COMMENT 'first');"), "CREATE TABLE \"t\" (\n    \"a\" INTEGER\n);");
+    assert_eq!(render("COMMENT ON COLUMN t.a IS 'the first column';"), "-- \"t\".\"a\": the first column");
+}
+
+#[test]
+fn test_sqlite_literals_and_identifiers() {
+    let sql = render("CREATE TABLE t(flag BOOL CHECK(flag = TRUE OR note != 'text'));");
+    assert!(sql.contains("CHECK (\"flag\" = 1 OR \"note\" <> 'text')"));
+
+    // The tokenizer does not allow quotes inside of strings and identifiers, but the AST does
+    let statement = Statement::Select {
+        with: vec![],
+        columns: vec![Expression::String("it's".to_string()), Expression::Identifier("a\"b".to_string())],
+        from: vec![],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    };
+    assert_eq!(sqlite::render(&statement), "SELECT 'it''s', \"a\"\"b\";");
+}
+
+#[test]
+fn test_sqlite_parentheses() {
+    assert_eq!(render("SELECT (a + b) * c, a - (b - c), a - b - c, NOT (a = b), -(-a) FROM t;"),
+        "SELECT (\"a\" + \"b\") * \"c\", \"a\" - (\"b\" - \"c\"), \"a\" - \"b\" - \"c\", NOT (\"a\" = \"b\"), -(-\"a\") FROM \"t\";");
+    assert_eq!(render("SELECT a FROM t ORDER BY a LIMIT 1 + 1;"), "SELECT \"a\" FROM \"t\" ORDER BY \"a\" LIMIT 1 + 1;");
+    assert_eq!(render("SELECT * FROM t WHERE (a OR b) AND c;"),
+        "SELECT * FROM \"t\" WHERE (\"a\" OR \"b\") AND \"c\";");
+    // NOT binds tighter than = in this crate, but looser in SQLite
+    assert_eq!(render("SELECT * FROM t WHERE NOT a = b AND NOT c;"),
+        "SELECT * FROM \"t\" WHERE (NOT \"a\") = \"b\" AND NOT \"c\";");
+}
+
+#[test]
+fn test_sqlite_queries() {
+    assert_eq!(
+        render("CREATE VIEW v AS WITH a AS (SELECT id FROM t) SELECT a.id, COUNT(*) OVER (PARTITION BY a.id ORDER BY id DESC) FROM a JOIN (SELECT id FROM u) AS d ON a.id = d.id WHERE EXISTS (SELECT * FROM w) ORDER BY a.id;"),
+        "CREATE VIEW \"v\" AS WITH \"a\" AS (SELECT \"id\" FROM \"t\") SELECT \"a\".\"id\", COUNT(*) OVER (PARTITION BY \"a\".\"id\" ORDER BY \"id\" DESC) \
+         FROM \"a\" JOIN (SELECT \"id\" FROM \"u\") AS \"d\" ON \"a\".\"id\" = \"d\".\"id\" WHERE EXISTS (SELECT * FROM \"w\") ORDER BY \"a\".\"id\";"
+    );
+}
+
+#[test]
+fn test_mysql_import() {
+    let dump = "
+        CREATE TABLE `users` (
+          `id` INT(11) UNSIGNED NOT NULL,
+          `email` VARCHAR(255) NOT NULL,
+          `order` INT,
+          PRIMARY KEY (`id`),
+          UNIQUE KEY `idx_email` (`email`(64)),
+          KEY (`order`, `email`) USING BTREE
+        ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+        SELECT `email` FROM `users`;
+    ";
+    let (statements, warnings) = mysql::import(dump).unwrap();
+
+    assert_eq!(statements[0], Statement::CreateTable {
+        table_name: ObjectName::new("users"),
+        temporary: false,
+        if_not_exists: false,
+        column_list: vec![
+            TableColumn {
+                column_name: "id".to_string(),
+                column_type: DBType::Int { unsigned: true },
+                constraints: vec![Constraint::NotNull, Constraint::PrimaryKey]
+            },
+            TableColumn {
+                column_name: "email".to_string(),
+                column_type: DBType::Varchar(Some(255)),
+                constraints: vec![Constraint::NotNull]
+            },
+            TableColumn {
+                column_name: "order".to_string(),
+                column_type: DBType::Int { unsigned: false },
+                constraints: vec![]
+            },
+        ],
+        constraints: vec![],
+        options: vec![
+            ("ENGINE".to_string(), Expression::Identifier("InnoDB".to_string())),
+            ("DEFAULT CHARSET".to_string(), Expression::Identifier("utf8mb4".to_string())),
+        ],
+        partitioning: None
+    });
+    assert_eq!(statements[1], build_statement("SELECT email FROM users;").unwrap());
+    assert_eq!(warnings, vec![
+        "Ignored index idx_email of table users",
+        "Ignored an index of table users",
+    ]);
+}
+
+#[test]
+fn test_mysql_composite_primary_key() {
+    let (statements, warnings) = mysql::import("CREATE TABLE t(a INT, b INT, PRIMARY KEY (a, b));").unwrap();
+    let Statement::CreateTable { constraints, .. } = &statements[0] else {
+        panic!("Expected CREATE TABLE statement");
+    };
+    assert_eq!(constraints, &vec![TableConstraint::PrimaryKey { name: None, columns: vec!["a".to_string(), "b".to_string()] }]);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_sqlite_null_constraint() {
+    assert_eq!(render("CREATE TABLE t(a INT NULL);"), "CREATE TABLE \"t\" (\n    \"a\" INTEGER NULL\n);");
+}
+
+#[test]
+fn test_sqlite_auto_increment() {
+    assert_eq!(
+        render("CREATE TABLE t(id INT AUTO_INCREMENT PRIMARY KEY, n INT AUTO_INCREMENT);"),
+        "CREATE TABLE \"t\" (\n    \"id\" INTEGER PRIMARY KEY AUTOINCREMENT,\n    \"n\" INTEGER\n);"
+    );
+    let (statements, _) = mysql::import("CREATE TABLE t(id INT NOT NULL AUTO_INCREMENT, PRIMARY KEY (id));").unwrap();
+    assert_eq!(sqlite::render(&statements[0]), "CREATE TABLE \"t\" (\n    \"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT\n);");
+}
+
+#[test]
+fn test_sqlite_table_constraints() {
+    assert_eq!(
+        render("CREATE TABLE t(a INT, b INT, PRIMARY KEY (a, b), CONSTRAINT u UNIQUE (b), CHECK (a < b));"),
+        "CREATE TABLE \"t\" (\n    \"a\" INTEGER,\n    \"b\" INTEGER,\n    PRIMARY KEY (\"a\", \"b\"),\n    CONSTRAINT \"u\" UNIQUE (\"b\"),\n    CHECK (\"a\" < \"b\")\n);"
+    );
+}
+
+#[test]
+fn test_sqlite_create_modifiers() {
+    assert_eq!(render("CREATE TEMP TABLE t(a INT);"), "CREATE TEMPORARY TABLE \"t\" (\n    \"a\" INTEGER\n);");
+    assert_eq!(render("CREATE OR REPLACE VIEW v AS SELECT 1;"), "DROP VIEW IF EXISTS \"v\";\nCREATE VIEW \"v\" AS SELECT 1;");
+}
+
+#[test]
+fn test_sqlite_qualified_names() {
+    assert_eq!(render("SELECT users.id FROM main.users;"), "SELECT \"users\".\"id\" FROM \"main\".\"users\";");
+    assert_eq!(render("DROP TABLE main.users;"), "DROP TABLE \"main\".\"users\";");
+}
+
+#[test]
+fn test_sqlite_drop_table() {
+    assert_eq!(render("DROP TABLE IF EXISTS t;"), "DROP TABLE IF EXISTS \"t\";");
+    assert_eq!(render("DROP TABLE t;"), "DROP TABLE \"t\";");
+}
+
+#[test]
+fn test_mysql_backslash_escapes() {
+    let (statements, _) = mysql::import(r"INSERT INTO t VALUES ('O\'Brien', 'it''s');").unwrap();
+    assert_eq!(sqlite::render(&statements[0]), "INSERT INTO \"t\" VALUES ('O''Brien', 'it''s');");
+    // Outside of MySQL, a backslash is an ordinary character
+    assert_eq!(render(r"SELECT 'C:\dir';"), r"SELECT 'C:\dir';");
+}
+
+#[test]
+fn test_mysql_import_null_defaults() {
+    let (statements, _) = mysql::import("CREATE TABLE `t` (\n  `note` varchar(255) DEFAULT NULL,\n  `id` int(11) NOT NULL\n);").unwrap();
+    let Statement::CreateTable { column_list, .. } = &statements[0] else {
+        panic!("Expected CREATE TABLE statement");
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::Default(Expression::Null)]);
+    assert_eq!(
+        sqlite::render(&statements[0]),
+        "CREATE TABLE \"t\" (\n    \"note\" TEXT DEFAULT (NULL) CHECK (length(\"note\") <= 255),\n    \"id\" INTEGER NOT NULL\n);"
+    );
+}
+
+#[test]
+fn test_mysql_import_comments() {
+    // A dump starts with comments of its own, which are read as MySQL from
+    // the first character on
+    let (statements, _) = mysql::import("# Dump of table t\n# ------------\nSELECT 1;\n# Dump completed\n").unwrap();
+    assert_eq!(statements, vec![build_statement("SELECT 1;").unwrap()]);
+}
+
+#[test]
+fn test_mysql_import_errors() {
+    assert_eq!(mysql::import("CREATE TABLE t(a INT, PRIMARY KEY (b));").unwrap_err(), "Unknown column b");
+    assert_eq!(mysql::import("CREATE TABLE t(a INT, KEY k (a);").unwrap_err(), "Expected ) after column definitions");
+    assert_eq!(mysql::import("SELECT `a FROM t;").unwrap_err(), "Unterminated identifier starting with `");
+}
+
+#[test]
+fn test_mysql_syntax_needs_the_dialect() {
+    // Backticks are understood everywhere, the rest only in the MySQL dialect
+    let statement = build_statement("SELECT `a``b` FROM `select`;").unwrap();
+    assert_eq!(statement, Statement::Select {
+        with: vec![],
+        columns: vec![Expression::Identifier("a`b".to_string())],
+        from: vec![TableReference::Table { name: ObjectName::new("select"), alias: None }],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    });
+    assert!(build_statement("CREATE TABLE t(a INT UNSIGNED);").is_err());
+    assert!(build_statement("CREATE TABLE t(a INT, KEY k (a));").is_err());
+
+    let mut parser = Parser::new(Tokenizer::new("CREATE TABLE t(a INT);")).unwrap();
+    parser.parse_statement().unwrap();
+    assert!(parser.warnings().is_empty());
+}
+
+fn parse_mssql(query: &str) -> Result<Statement, String> {
+    let mut parser = Parser::new(Tokenizer::new(query))?;
+    parser.set_options(ParserOptions { dialect: Dialect::MsSql, ..ParserOptions::default() });
+    parser.parse_statement()
+}
+
+#[test]
+fn test_mssql_top() {
+    // TOP is stored the same way as LIMIT
+    assert_eq!(
+        parse_mssql("SELECT TOP 10 * FROM t ORDER BY id;").unwrap(),
+        build_statement("SELECT * FROM t ORDER BY id LIMIT 10;").unwrap()
+    );
+    let Statement::Select { limit, .. } = parse_mssql("SELECT top (5 * 2) name FROM t;").unwrap() else {
+        panic!("Expected SELECT statement");
+    };
+    assert!(matches!(limit, Some(Expression::BinaryOperation { .. })));
+
+    assert_eq!(parse_mssql("SELECT TOP name FROM t;").unwrap_err(), "Expected number or ( after TOP");
+    assert_eq!(parse_mssql("SELECT TOP 1 * FROM t LIMIT 1;").unwrap_err(), "A query can not have both TOP and LIMIT");
+}
+
+fn parse_in_dialect(dialect: Dialect, query: &str) -> Result<Statement, String> {
+    let mut parser = Parser::with_options(Tokenizer::new(query), ParserOptions { dialect, ..ParserOptions::default() })?;
+    parser.parse_statement()
+}
+
+#[test]
+fn test_dialect_syntax() {
+    // Backticks quote identifiers in MySQL and SQLite, but not in PostgreSQL
+    let query = "SELECT `order` FROM t;";
+    assert_eq!(parse_in_dialect(Dialect::MySql, query), build_statement(query));
+    assert_eq!(parse_in_dialect(Dialect::Sqlite, query), build_statement(query));
+    assert_eq!(
+        parse_in_dialect(Dialect::Postgres, query).unwrap_err(),
+        "Quoting identifiers with backticks is not supported in PostgreSQL"
+    );
+    
+    // Only PostgreSQL casts with ::
+    let query = "SELECT a::INT FROM t;";
+    assert_eq!(parse_in_dialect(Dialect::Postgres, query), build_statement("SELECT CAST(a AS INT) FROM t;"));
+    assert_eq!(parse_in_dialect(Dialect::Sqlite, query).unwrap_err(), "The :: cast is not supported in SQLite");
+    assert!(parse_in_dialect(Dialect::MsSql, query).is_err());
+    
+    // MySQL reads strings with backslash escapes
+    let query = r"SELECT 'it\'s' FROM t;";
+    assert_eq!(parse_in_dialect(Dialect::MySql, query), build_statement("SELECT 'it''s' FROM t;"));
+    assert!(parse_in_dialect(Dialect::Sqlite, query).is_err());
+}
+
+#[test]
+fn test_regular_expression_match() {
+    // REGEXP of MySQL and ~ of PostgreSQL are the same operator
+    let mysql = parse_in_dialect(Dialect::MySql, "SELECT * FROM t WHERE name REGEXP '^A' AND code NOT REGEXP '[0-9]';").unwrap();
+    let postgres = parse_in_dialect(Dialect::Postgres, "SELECT * FROM t WHERE name ~ '^A' AND code !~ '[0-9]';").unwrap();
+    assert_eq!(mysql, postgres);
+    let Statement::Select { r#where: Some(Expression::BinaryOperation { left_operand, right_operand, .. }), .. } = mysql else {
+        panic!("Expected SELECT statement with a condition");
+    };
+    assert!(matches!(*left_operand, Expression::BinaryOperation { operator: BinaryOperator::RegexMatch, .. }));
+    assert!(matches!(*right_operand, Expression::BinaryOperation { operator: BinaryOperator::NotRegexMatch, .. }));
+    assert_eq!(
+        render("SELECT * FROM t WHERE name SIMILAR TO 'A%' OR name NOT SIMILAR TO 'B%';"),
+        "SELECT * FROM \"t\" WHERE \"name\" SIMILAR TO 'A%' OR \"name\" NOT SIMILAR TO 'B%';"
+    );
+    assert_eq!(sqlite::render(&postgres), "SELECT * FROM \"t\" WHERE \"name\" REGEXP '^A' AND \"code\" NOT REGEXP '[0-9]';");
+
+    // Other dialects do not know the operators
+    assert!(build_statement("SELECT * FROM t WHERE name REGEXP '^A';").is_err());
+    assert!(build_statement("SELECT * FROM t WHERE name ~ '^A';").is_err());
+    assert!(parse_in_dialect(Dialect::MySql, "SELECT * FROM t WHERE name ~ '^A';").is_err());
+    assert_eq!(
+        parse_in_dialect(Dialect::MySql, "SELECT * FROM t WHERE name NOT 'A';").unwrap_err(),
+        "Expected LIKE, IN, SIMILAR TO or REGEXP after NOT"
+    );
+}
+
+#[test]
+fn test_postgres_power() {
+    let power = |left, right| Expression::BinaryOperation {
+        left_operand: Box::new(left),
+        operator: BinaryOperator::Power,
+        right_operand: Box::new(right)
+    };
+    let Statement::Select { columns, .. } = parse_in_dialect(Dialect::Postgres, "SELECT 2 ^ 3 ^ 2, a * b ^ 2 FROM t;").unwrap() else {
+        panic!("Expected SELECT statement");
+    };
+    // The power groups from the right, and binds tighter than *
+    assert_eq!(columns[0], power(Expression::Number(2), power(Expression::Number(3), Expression::Number(2))));
+    assert_eq!(columns[1], Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("a".to_string())),
+        operator: BinaryOperator::Multiply,
+        right_operand: Box::new(power(Expression::Identifier("b".to_string()), Expression::Number(2)))
+    });
+    assert_eq!(
+        sqlite::render(&parse_in_dialect(Dialect::Postgres, "SELECT 2 ^ 3 ^ 2 - 1 FROM t;").unwrap()),
+        "SELECT pow(2, pow(3, 2)) - 1 FROM \"t\";"
+    );
+
+    // Elsewhere ^ is the exclusive or, which groups from the left
+    let Statement::Select { columns, .. } = build_statement("SELECT 2 ^ 3 ^ 2 FROM t;").unwrap() else {
+        panic!("Expected SELECT statement");
+    };
+    assert!(matches!(&columns[0], Expression::BinaryOperation { left_operand, operator: BinaryOperator::BitwiseXor, .. }
+        if matches!(**left_operand, Expression::BinaryOperation { operator: BinaryOperator::BitwiseXor, .. })));
+}
+
+#[test]
+fn test_mysql_integer_display_width() {
+    let Statement::CreateTable { column_list, .. } = parse_in_dialect(Dialect::MySql, "CREATE TABLE t(id BIGINT(20), flag TINYINT(1), n SMALLINT(6));").unwrap() else {
+        panic!("Expected CREATE TABLE statement");
+    };
+    let types: Vec<DBType> = column_list.into_iter().map(|column| column.column_type).collect();
+    assert_eq!(types, vec![DBType::BigInt { unsigned: false }, DBType::TinyInt { unsigned: false }, DBType::SmallInt { unsigned: false }]);
+    assert_eq!(parse_in_dialect(Dialect::MySql, "CREATE TABLE t(id BIGINT(20;").unwrap_err(), "Expected ) after BIGINT(");
+}
+
+#[test]
+fn test_mysql_unsigned() {
+    let mut parser = Parser::new(Tokenizer::new("CREATE TABLE t(id BIGINT UNSIGNED, flag TINYINT(1) unsigned, price DECIMAL(10, 2) UNSIGNED);")).unwrap();
+    parser.set_options(ParserOptions { dialect: Dialect::MySql, ..ParserOptions::default() });
+    let statement = parser.parse_statement().unwrap();
+    let Statement::CreateTable { column_list, .. } = &statement else {
+        panic!("Expected CREATE TABLE statement");
+    };
+    assert_eq!(column_list[0].column_type, DBType::BigInt { unsigned: true });
+    assert_eq!(column_list[1].column_type, DBType::TinyInt { unsigned: true });
+    // Other numbers only get a warning
+    assert_eq!(column_list[2].column_type, DBType::Decimal { precision: Some(10), scale: Some(2) });
+    assert_eq!(parser.warnings(), ["Ignored UNSIGNED modifier of column price"]);
+    
+    assert_eq!(
+        sqlite::render(&statement),
+        "CREATE TABLE \"t\" (\n    \"id\" INTEGER CHECK (\"id\" >= 0),\n    \"flag\" INTEGER CHECK (\"flag\" >= 0),\n    \"price\" NUMERIC\n);"
+    );
+}
@@ -0,0 +1,88 @@
+use programming_languages_project_kyrylo_yezholov::{build_statements, docs, Catalog};
+
+fn catalog(script: &str) -> Catalog {
+    Catalog::from_statements(&build_statements(script).unwrap()).unwrap()
+}
+
+#[test]
+fn test_markdown() {
+    let catalog = catalog(
+        "CREATE TABLE users(id INT PRIMARY KEY, name VARCHAR(255) NOT NULL, age INT CHECK(age >= 18 AND age < 150));
+         CREATE TABLE orders(id INT PRIMARY KEY, user_id INT, paid BOOL);
+         CREATE VIEW adults AS SELECT name FROM users JOIN orders ON users.id = user_id;
+         COMMENT ON TABLE users IS 'Registered users';
+         COMMENT ON COLUMN users.name IS 'Full name | as given';
+         COMMENT ON VIEW adults IS 'Users that placed orders';",
+    );
+    assert_eq!(
+        docs::markdown(&catalog),
+        "# Schema\n\
+         \n## Tables\n\
+         \n### users\n\n\
+         Registered users\n\n\
+         | Column | Type | Constraints | Description |\n\
+         | --- | --- | --- | --- |\n\
+         | id | INT | PRIMARY KEY |  |\n\
+         | name | VARCHAR(255) | NOT NULL | Full name \\| as given |\n\
+         | age | INT | CHECK ((age >= 18) AND (age < 150)) |  |\n\
+         \n### orders\n\n\
+         | Column | Type | Constraints | Description |\n\
+         | --- | --- | --- | --- |\n\
+         | id | INT | PRIMARY KEY |  |\n\
+         | user_id | INT |  |  |\n\
+         | paid | BOOL |  |  |\n\
+         \n## Views\n\
+         \n### adults\n\n\
+         Users that placed orders\n\n\
+         Reads from: `users`, `orders`\n"
+    );
+}
+
+#[test]
+fn test_html_escapes_text() {
+    let catalog = catalog(
+        "CREATE TABLE items(price INT CHECK(price > 0));
+         COMMENT ON TABLE items IS '<b>Goods</b> & services';",
+    );
+    let html = docs::html(&catalog);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h3>items</h3>\n<p>&lt;b&gt;Goods&lt;/b&gt; &amp; services</p>\n"));
+    assert!(html.contains("<tr><td>price</td><td>INT</td><td>CHECK (price &gt; 0)</td><td></td></tr>\n"));
+    assert!(!html.contains("<h2>Views</h2>"));
+}
+
+#[test]
+fn test_view_sources_skip_common_table_expressions() {
+    let catalog = catalog(
+        "CREATE TABLE users(id INT);
+         CREATE VIEW ids AS WITH recent AS (SELECT id FROM users) SELECT id FROM recent;
+         CREATE VIEW all_ids AS SELECT id FROM ids JOIN users ON ids.id = users.id;",
+    );
+    let markdown = docs::markdown(&catalog);
+    assert!(markdown.contains("### ids\n\nReads from: `users`\n"));
+    assert!(markdown.contains("### all_ids\n\nReads from: `ids`, `users`\n"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_summary() {
+    let catalog = catalog(
+        "CREATE TABLE users(id INT PRIMARY KEY, name VARCHAR(20) COMMENT 'Full name');
+         CREATE VIEW names AS SELECT name FROM users;
+         COMMENT ON TABLE users IS 'Registered users';",
+    );
+    assert_eq!(
+        docs::json(&catalog),
+        serde_json::json!({
+            "tables": [{
+                "name": "users",
+                "comment": "Registered users",
+                "columns": [
+                    { "name": "id", "type": "INT", "constraints": ["PRIMARY KEY"], "comment": null },
+                    { "name": "name", "type": "VARCHAR(20)", "constraints": [], "comment": "Full name" },
+                ],
+            }],
+            "views": [{ "name": "names", "comment": null, "reads_from": ["users"] }],
+        })
+    );
+}
@@ -0,0 +1,45 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement,
+    filters::{NormalizeLineEndings, NormalizeQuotes, StripByteOrderMark, StripPsqlCommands},
+    FilteredInput, Span
+};
+
+#[test]
+fn test_standard_filters() {
+    let raw = "\u{FEFF}\\connect shop\r\nSELECT * FROM users WHERE name = \u{2018}Ann\u{2019};\r\n";
+    let input = FilteredInput::standard(raw);
+    assert_eq!(input.text(), "\nSELECT * FROM users WHERE name = 'Ann';\n");
+    assert!(build_statement(input.text()).is_ok());
+}
+
+#[test]
+fn test_spans_map_to_the_original_input() {
+    let raw = "\u{FEFF}SELECT a\r\nFROM \u{201C}t\u{201D};";
+    let input = FilteredInput::standard(raw);
+    assert_eq!(input.text(), "SELECT a\nFROM \"t\";");
+    
+    // `FROM` starts after the byte order mark (3 bytes) and the \r\n
+    let from = input.text().find("FROM").unwrap();
+    assert_eq!(input.original_span(Span { start: from, end: from + 4 }), Span { start: 13, end: 17 });
+    assert_eq!(&raw[13..17], "FROM");
+    // The replaced quotes cover the whole characters they stand for
+    let quoted = input.text().find('"').unwrap();
+    let span = input.original_span(Span { start: quoted, end: quoted + 3 });
+    assert_eq!(&raw[span.start..span.end], "\u{201C}t\u{201D}");
+    // The line break covers both characters of \r\n
+    let line_break = input.text().find('\n').unwrap();
+    assert_eq!(input.original_span(Span { start: line_break, end: line_break + 1 }), Span { start: 11, end: 13 });
+    assert_eq!(input.original_offset(input.text().len()), raw.len());
+}
+
+#[test]
+fn test_filters_are_chosen_by_the_caller() {
+    let raw = "\u{FEFF}\\set x 1\r\nSELECT 1;";
+    assert_eq!(FilteredInput::new(raw, &[]).text(), raw);
+    assert_eq!(FilteredInput::new(raw, &[&StripByteOrderMark]).text(), "\\set x 1\r\nSELECT 1;");
+    // The order matters: with the byte order mark in front, the first line does not start with a backslash
+    assert_eq!(FilteredInput::new(raw, &[&StripPsqlCommands, &NormalizeLineEndings]).text(), "\u{FEFF}\\set x 1\nSELECT 1;");
+    assert_eq!(FilteredInput::new(raw, &[&StripByteOrderMark, &StripPsqlCommands]).text(), "\r\nSELECT 1;");
+    assert_eq!(FilteredInput::new("\u{201E}a\u{201C}", &[&NormalizeQuotes]).text(), "\"a\"");
+    assert_eq!(FilteredInput::new("a\rb", &[&NormalizeLineEndings]).text(), "a\nb");
+}
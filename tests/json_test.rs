@@ -0,0 +1,190 @@
+#![cfg(feature = "serde")]
+
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement, to_json, from_json, migrate_ast_json, AST_SCHEMA_VERSION,
+    Message, Statement
+};
+use serde_json::json;
+
+#[test]
+fn test_round_trip() {
+    for query in [
+        "SELECT u.name, age * 2 FROM users AS u JOIN orders o ON u.id = o.user_id WHERE NOT u.age < 18 ORDER BY u.name DESC;",
+        "WITH a AS (SELECT id FROM t) SELECT * FROM a, b;",
+        "CREATE TABLE t(id INT PRIMARY KEY, name VARCHAR(10) NOT NULL, ok BOOL CHECK(ok = TRUE));",
+        "CREATE TABLE t(id INT) ENGINE=InnoDB WITH_ROWID=FALSE PARTITION BY HASH (id);",
+        "CREATE VIEW v AS SELECT 'text' FROM t;",
+        "SELECT * FROM jobs FOR UPDATE SKIP LOCKED;",
+        "INSERT INTO t VALUES (1, NULL);",
+        "INSERT INTO t VALUES (1) RETURNING id AS number;",
+    ] {
+        let statement = build_statement(query).unwrap();
+        let document = to_json(&statement);
+        assert_eq!(document["schema_version"], json!(AST_SCHEMA_VERSION));
+        assert_eq!(from_json(document).unwrap(), statement);
+    }
+}
+
+#[test]
+fn test_round_trip_through_text() {
+    let statement = build_statement("SELECT * FROM t WHERE x = 'y';").unwrap();
+    let text = to_json(&statement).to_string();
+    let document = serde_json::from_str(&text).unwrap();
+    assert_eq!(from_json(document).unwrap(), statement);
+}
+
+#[test]
+fn test_current_version_is_not_changed_by_migration() {
+    let document = to_json(&build_statement("SELECT id FROM t;").unwrap());
+    assert_eq!(migrate_ast_json(document.clone()).unwrap(), document);
+}
+
+#[test]
+fn test_unsupported_versions() {
+    let statement = json!({ "Select": {} });
+
+    let document = json!({ "schema_version": AST_SCHEMA_VERSION + 1, "statement": statement });
+    assert_eq!(
+        from_json(document).unwrap_err(),
+        Message::UnsupportedSchemaVersion { version: AST_SCHEMA_VERSION + 1 }
+    );
+    assert!(matches!(
+        from_json(json!({ "schema_version": 0, "statement": statement })).unwrap_err(),
+        Message::UnsupportedSchemaVersion { version: 0 }
+    ));
+    assert_eq!(from_json(json!({ "statement": statement })).unwrap_err(), Message::MissingSchemaVersion);
+}
+
+#[test]
+fn test_invalid_documents() {
+    let document = json!({ "schema_version": "one", "statement": {} });
+    assert!(matches!(from_json(document).unwrap_err(), Message::InvalidAstJson { .. }));
+
+    let document = json!({ "schema_version": AST_SCHEMA_VERSION });
+    assert_eq!(from_json(document).unwrap_err().to_string(), "Invalid serialized statement: missing statement");
+
+    let document = json!({ "schema_version": AST_SCHEMA_VERSION, "statement": { "Drop": {} } });
+    assert!(matches!(from_json(document).unwrap_err(), Message::InvalidAstJson { .. }));
+}
+
+#[test]
+fn test_documents_without_locking_clause() {
+    // Queries serialized before locking clauses existed have no `lock` field
+    let mut document = to_json(&build_statement("SELECT * FROM t;").unwrap());
+    document["statement"]["Select"].as_object_mut().unwrap().remove("lock");
+    let Statement::Select { lock, .. } = from_json(document).unwrap() else {
+        panic!("Expected SELECT statement");
+    };
+    assert!(lock.is_none());
+}
+
+#[test]
+fn test_migration_adds_table_options() {
+    let document = json!({
+        "schema_version": 1,
+        "statement": {
+            "CreateTable": {
+                "table_name": "t",
+                "column_list": [{ "column_name": "id", "column_type": "Int", "constraints": [] }]
+            }
+        }
+    });
+    assert_eq!(from_json(document).unwrap(), build_statement("CREATE TABLE t(id INT);").unwrap());
+}
+
+#[test]
+fn test_migration_adds_if_not_exists() {
+    let document = json!({
+        "schema_version": 2,
+        "statement": {
+            "CreateView": {
+                "name": "v",
+                "query": { "Select": {
+                    "with": [], "columns": ["Wildcard"], "from": [{ "Table": { "name": "t", "alias": null } }],
+                    "where": null, "orderby": []
+                } }
+            }
+        }
+    });
+    assert_eq!(from_json(document).unwrap(), build_statement("CREATE VIEW v AS SELECT * FROM t;").unwrap());
+}
+
+#[test]
+fn test_migration_adds_distinct_to_nested_function_calls() {
+    let statement = build_statement("SELECT COUNT(*) FROM t WHERE id IN (SELECT MAX(id) FROM t);").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(3);
+    document["statement"]["Select"]["columns"][0]["Function"].as_object_mut().unwrap().remove("distinct");
+    document["statement"]["Select"]["where"]["InSubquery"]["subquery"]["Select"]["columns"][0]["Function"]
+        .as_object_mut()
+        .unwrap()
+        .remove("distinct");
+    assert_eq!(from_json(document).unwrap(), statement);
+}
+
+#[test]
+fn test_migration_adds_integer_signedness() {
+    let statement = build_statement("CREATE TABLE t(id INT CHECK (CAST(id AS INT) > 0), name VARCHAR(10));").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(4);
+    document["statement"]["CreateTable"]["column_list"][0]["column_type"] = json!("Int");
+    document["statement"]["CreateTable"]["column_list"][0]["constraints"][0]["Check"]["BinaryOperation"]["left_operand"]["Cast"]["target_type"] = json!("Int");
+    assert_eq!(from_json(document).unwrap(), statement);
+}
+
+#[test]
+fn test_varchar_lengths_keep_their_format() {
+    // An optional length is serialized as the plain number, like before it was optional
+    let document = to_json(&build_statement("CREATE TABLE t(a VARCHAR(10), b VARCHAR);").unwrap());
+    assert_eq!(document["statement"]["CreateTable"]["column_list"][0]["column_type"], json!({ "Varchar": 10 }));
+    assert_eq!(document["statement"]["CreateTable"]["column_list"][1]["column_type"], json!({ "Varchar": null }));
+}
+
+#[test]
+fn test_migration_adds_table_constraints() {
+    let statement = build_statement("CREATE TABLE t(id INT);").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(5);
+    document["statement"]["CreateTable"].as_object_mut().unwrap().remove("constraints");
+    assert_eq!(from_json(document).unwrap(), statement);
+}
+
+#[test]
+fn test_migration_adds_create_modifiers() {
+    for query in ["CREATE TABLE t(id INT);", "CREATE VIEW v AS SELECT 1;"] {
+        let statement = build_statement(query).unwrap();
+        let mut document = to_json(&statement);
+        document["schema_version"] = json!(6);
+        let create = document["statement"].as_object_mut().unwrap().values_mut().next().unwrap().as_object_mut().unwrap();
+        create.remove("temporary");
+        create.remove("or_replace");
+        assert_eq!(from_json(document).unwrap(), statement);
+    }
+}
+
+#[test]
+fn test_migration_qualifies_object_names() {
+    let statement = build_statement("INSERT INTO archive SELECT * FROM orders JOIN (SELECT id FROM items) i ON orders.item_id = i.id;").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(7);
+    document["statement"]["Insert"]["table_name"] = json!("archive");
+    let from = &mut document["statement"]["Insert"]["source"]["Query"]["Select"]["from"][0]["Join"];
+    from["left"]["Table"]["name"] = json!("orders");
+    from["right"]["Derived"]["subquery"]["Select"]["from"][0]["Table"]["name"] = json!("items");
+    assert_eq!(from_json(document).unwrap(), statement);
+    
+    let statement = build_statement("COMMENT ON COLUMN users.email IS 'x';").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(7);
+    document["statement"]["Comment"]["target"]["Column"]["table"] = json!("users");
+    assert_eq!(from_json(document).unwrap(), statement);
+}
+
+#[test]
+fn test_migration_adds_returning() {
+    let statement = build_statement("INSERT INTO t VALUES (1);").unwrap();
+    let mut document = to_json(&statement);
+    document["schema_version"] = json!(8);
+    document["statement"]["Insert"].as_object_mut().unwrap().remove("returning");
+    assert_eq!(from_json(document).unwrap(), statement);
+}
@@ -0,0 +1,49 @@
+use programming_languages_project_kyrylo_yezholov::{
+    Tokenizer, Parser, Token,
+    Message, Messages, NameKind
+};
+
+struct Lithuanian;
+
+impl Messages for Lithuanian {
+    fn render(&self, message: &Message) -> String {
+        match message {
+            Message::ExpectedFrom => "Trūksta FROM dalies".to_string(),
+            Message::UnterminatedString { quote } => format!("Neuždaryta eilutė, prasidedanti {}", quote),
+            _ => message.to_string(),
+        }
+    }
+}
+
+fn parse_with(input: &str, messages: &dyn Messages) -> Result<(), String> {
+    Parser::with_messages(Tokenizer::new(input), messages)?.parse_statement()?;
+    Ok(())
+}
+
+#[test]
+fn test_custom_catalog_in_parser() {
+    assert_eq!(parse_with("SELECT id WHERE id = 1;", &Lithuanian).unwrap_err(), "Trūksta FROM dalies");
+    // Messages the catalog does not override fall back to English
+    assert_eq!(parse_with("SELECT id FROM", &Lithuanian).unwrap_err(), "Expected table name after FROM");
+}
+
+#[test]
+fn test_custom_catalog_in_tokenizer() {
+    // The parser hands its catalog to the tokenizer
+    assert_eq!(parse_with("SELECT 'abc", &Lithuanian).unwrap_err(), "Neuždaryta eilutė, prasidedanti '");
+    
+    let result = Tokenizer::with_messages("'abc", &Lithuanian).collect::<Result<Vec<Token>, String>>();
+    assert_eq!(result.unwrap_err(), "Neuždaryta eilutė, prasidedanti '");
+}
+
+#[test]
+fn test_english_catalog() {
+    let message = Message::ExpectedAfter {
+        expected: vec!["TABLE".to_string(), "VIEW".to_string(), "INDEX".to_string()],
+        after: "CREATE".to_string()
+    };
+    assert_eq!(message.to_string(), "Expected TABLE, VIEW or INDEX after CREATE");
+    
+    let message = Message::ExpectedName { kind: NameKind::Column, after: None };
+    assert_eq!(message.to_string(), "Expected column name");
+}
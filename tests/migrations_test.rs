@@ -0,0 +1,82 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statements,
+    migrations::{check_migration, check_script},
+    Message, NameKind
+};
+
+#[test]
+fn test_missing_guards() {
+    let script = build_statements(
+        "CREATE TABLE users(id INT);
+         CREATE TABLE IF NOT EXISTS orders(id INT);
+         CREATE VIEW ids AS SELECT id FROM users;
+         SELECT * FROM users;",
+    ).unwrap();
+    assert_eq!(check_script(&script), vec![
+        Message::MissingIfNotExists { statement: "CREATE TABLE".to_string(), name: "users".to_string() },
+        Message::MissingIfNotExists { statement: "CREATE VIEW".to_string(), name: "ids".to_string() },
+    ]);
+}
+
+#[test]
+fn test_recreated_objects() {
+    let script = build_statements(
+        "CREATE TABLE IF NOT EXISTS users(id INT);
+         CREATE TABLE IF NOT EXISTS Users(id INT);
+         CREATE TABLE IF NOT EXISTS USERS(id INT, name VARCHAR(20));
+         CREATE VIEW IF NOT EXISTS users AS SELECT 1;",
+    ).unwrap();
+    assert_eq!(check_script(&script), vec![
+        Message::DuplicateDefinition { kind: NameKind::Table, name: "Users".to_string() },
+        Message::ConflictingDefinition { kind: NameKind::Table, name: "USERS".to_string() },
+        Message::ConflictingDefinition { kind: NameKind::View, name: "users".to_string() },
+    ]);
+}
+
+#[test]
+fn test_migration_on_top_of_applied_scripts() {
+    let applied = build_statements("CREATE TABLE users(id INT); CREATE VIEW ids AS SELECT id FROM users;").unwrap();
+    let migration = build_statements(
+        "CREATE TABLE IF NOT EXISTS users(id INT);
+         CREATE VIEW IF NOT EXISTS ids AS SELECT id FROM users WHERE id > 0;
+         CREATE TABLE IF NOT EXISTS orders(id INT);",
+    ).unwrap();
+    assert_eq!(check_migration(&applied, &migration), vec![
+        Message::DuplicateDefinition { kind: NameKind::Table, name: "users".to_string() },
+        Message::ConflictingDefinition { kind: NameKind::View, name: "ids".to_string() },
+    ]);
+    assert_eq!(
+        Message::ConflictingDefinition { kind: NameKind::View, name: "ids".to_string() }.to_string(),
+        "The view ids is created again with a different definition"
+    );
+}
+
+#[test]
+fn test_dropped_tables() {
+    let applied = build_statements("CREATE TABLE users(id INT);").unwrap();
+    let migration = build_statements(
+        "DROP TABLE users;
+         CREATE TABLE IF NOT EXISTS users(id INT, name VARCHAR(20));
+         DROP TABLE IF EXISTS orders;",
+    ).unwrap();
+    assert_eq!(check_migration(&applied, &migration), vec![
+        Message::MissingIfExists { statement: "DROP TABLE".to_string(), name: "users".to_string() },
+    ]);
+    assert_eq!(
+        Message::MissingIfExists { statement: "DROP TABLE".to_string(), name: "users".to_string() }.to_string(),
+        "DROP TABLE users has no IF EXISTS guard, so running it again fails"
+    );
+}
+
+#[test]
+fn test_replaced_views() {
+    let applied = build_statements("CREATE TABLE users(id INT); CREATE VIEW ids AS SELECT id FROM users;").unwrap();
+    let migration = build_statements(
+        "CREATE OR REPLACE VIEW ids AS SELECT id FROM users WHERE id > 0;
+         CREATE VIEW IF NOT EXISTS ids AS SELECT id FROM users WHERE id > 0;",
+    ).unwrap();
+    // The replaced definition is the one that stays in effect
+    assert_eq!(check_migration(&applied, &migration), vec![
+        Message::DuplicateDefinition { kind: NameKind::View, name: "ids".to_string() },
+    ]);
+}
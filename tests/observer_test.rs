@@ -0,0 +1,95 @@
+use programming_languages_project_kyrylo_yezholov::{
+    Tokenizer, Parser, ParseObserver, Clause, Span,
+    Statement, TableColumn
+};
+
+// Records every event as the kind of the event and the text it covers
+#[derive(Default)]
+struct Recorder<'a> {
+    input: &'a str,
+    events: Vec<String>,
+}
+
+impl Recorder<'_> {
+    fn text(&self, span: Span) -> &str {
+        &self.input[span.start..span.end]
+    }
+}
+
+impl ParseObserver for Recorder<'_> {
+    fn on_statement_start(&mut self, start: usize) {
+        self.events.push(format!("start at {}", start));
+    }
+
+    fn on_statement(&mut self, _statement: &Statement, span: Span) {
+        self.events.push(format!("statement: {}", self.text(span)));
+    }
+
+    fn on_clause(&mut self, clause: Clause, span: Span) {
+        self.events.push(format!("{:?}: {}", clause, self.text(span)));
+    }
+
+    fn on_column_definition(&mut self, column: &TableColumn, span: Span) {
+        self.events.push(format!("column {}: {}", column.column_name, self.text(span)));
+    }
+}
+
+fn record(input: &str) -> (Result<Vec<Statement>, String>, Vec<String>) {
+    let mut recorder = Recorder { input, ..Default::default() };
+    let result = {
+        let mut parser = Parser::new(Tokenizer::new(input)).unwrap();
+        parser.set_observer(&mut recorder);
+        parser.parse_statements()
+    };
+    (result, recorder.events)
+}
+
+#[test]
+fn test_select_events() {
+    let (result, events) = record("SELECT name FROM users WHERE age > 18 ORDER BY name DESC;");
+    assert_eq!(result.unwrap().len(), 1);
+    assert_eq!(events, vec![
+        "start at 0",
+        "Select: SELECT name",
+        "From: FROM users",
+        "Where: WHERE age > 18",
+        "OrderBy: ORDER BY name DESC",
+        "statement: SELECT name FROM users WHERE age > 18 ORDER BY name DESC;",
+    ]);
+}
+
+#[test]
+fn test_script_events() {
+    let input = "CREATE TABLE t(id INT PRIMARY KEY, name VARCHAR(10));\n  WITH a AS (SELECT id FROM t) SELECT * FROM a;";
+    let (result, events) = record(input);
+    assert_eq!(result.unwrap().len(), 2);
+    assert_eq!(events, vec![
+        "start at 0",
+        "column id: id INT PRIMARY KEY",
+        "column name: name VARCHAR(10)",
+        "statement: CREATE TABLE t(id INT PRIMARY KEY, name VARCHAR(10));",
+        "start at 56",
+        // The clauses of the common table expression come first
+        "Select: SELECT id",
+        "From: FROM t",
+        "With: WITH a AS (SELECT id FROM t)",
+        "Select: SELECT *",
+        "From: FROM a",
+        "statement: WITH a AS (SELECT id FROM t) SELECT * FROM a;",
+    ]);
+}
+
+#[test]
+fn test_events_before_an_error() {
+    // The statements before the error have already been reported
+    let (result, events) = record("SELECT a FROM t; SELECT b t;");
+    assert!(result.is_err());
+    assert_eq!(events, vec![
+        "start at 0",
+        "Select: SELECT a",
+        "From: FROM t",
+        "statement: SELECT a FROM t;",
+        "start at 17",
+        "Select: SELECT b",
+    ]);
+}
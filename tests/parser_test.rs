@@ -1,8 +1,11 @@
 use programming_languages_project_kyrylo_yezholov::{
     Tokenizer,
-    Parser,
+    Parser, Keywords, Token, build_statement,
     Statement, Expression, TableColumn, DBType,
-    Constraint, BinaryOperator, UnaryOperator
+    Constraint, TableConstraint, BinaryOperator, UnaryOperator, Placeholder, TemporalKind, Quantifier, DateField,
+    TableReference, JoinType, ParserOptions, WindowSpecification,
+    LockingClause, LockStrength, WaitPolicy, Partitioning, PartitionMethod, CommentTarget,
+    InsertSource, ReturningItem, ObjectName, ParseError, Span, build_statements, build_located_statement, StatementHandler
 };
 fn parse_expression(input: &str) -> Result<Expression, String> {
     let tokenizer = Tokenizer::new(input);
@@ -61,6 +64,21 @@ fn test_unary_operation() {
     });
 }
 
+#[test]
+fn test_number_range() {
+    assert_eq!(parse_expression("9223372036854775807").unwrap(), Expression::Number(i64::MAX));
+    assert!(parse_expression("x > 9223372036854775808").unwrap_err().contains("The number 9223372036854775808 is too big"));
+    assert_eq!(parse_expression("-9223372036854775808").unwrap(), Expression::Number(i64::MIN));
+    assert_eq!(parse_expression("-9223372036854775808 * 2").unwrap(), Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Number(i64::MIN)),
+        operator: BinaryOperator::Multiply,
+        right_operand: Box::new(Expression::Number(2)),
+    });
+    assert!(parse_sql("SELECT -9223372036854775808;").is_ok());
+    assert!(parse_expression("-9223372036854775808::text").unwrap_err().contains("The number 9223372036854775808 is too big"));
+    assert!(parse_expression("- -9223372036854775808").is_ok());
+}
+
 #[test]
 fn test_complex_expression() {
     let expr = parse_expression("(5 - x) < (4 + y)").unwrap();
@@ -101,19 +119,66 @@ fn test_boolean_expression() {
 fn test_invalid_expression() {
     let result = parse_expression("5 + ");
     assert!(result.is_err());
+    assert_eq!(parse_sql("SELECT a §§ b FROM t;").unwrap_err(), "Invalid token §§");
+    assert_eq!(parse_sql("SELECT 99999999999999999999;").unwrap_err(), "Invalid token 99999999999999999999");
+}
+
+#[test]
+fn test_located_errors() {
+    let input = "SELECT name\n\tFROM users WHERE;";
+    let error = build_located_statement(input).unwrap_err();
+    assert_eq!(error.span, Span { start: 29, end: 30 });
+    assert_eq!(error.render(input), [
+        "Error at line 2, column 18: Unexpected token in prefix position: Semicolon",
+        "  |",
+        "2 | \tFROM users WHERE;",
+        "  | \t                ^",
+    ].join("\n"));
+    
+    // Errors of the tokenizer cover the text it failed to read, even in the first token
+    let error = build_located_statement("  'abc").unwrap_err();
+    assert_eq!(error, ParseError { message: "Unterminated string starting with '".to_string(), span: Span { start: 2, end: 6 } });
+    assert!(error.render("  'abc").ends_with("1 |   'abc\n  |   ^^^^"));
+    // At the end of the input there is nothing to underline but the place itself
+    assert!(build_located_statement("SELECT a FROM t").unwrap_err().render("SELECT a FROM t").ends_with("\n  |                ^"));
+}
+
+#[test]
+fn test_parse_all_with_errors() -> Result<(), String> {
+    let input = "SELECT a, 1 +, b FROM t;\nCREATE TABLE t(id INT, name TEXTUAL, age INT);\nSELECT c FROM;\nSELECT d FROM u;";
+    let (statements, errors) = Parser::new(Tokenizer::new(input))?.parse_all_with_errors();
+    assert_eq!(statements, build_statements("SELECT a, b FROM t; CREATE TABLE t(id INT, age INT); SELECT d FROM u;")?);
+    let spans: Vec<&str> = errors.iter().map(|error| &input[error.span.start..error.span.end]).collect();
+    assert_eq!(spans, vec![",", "TEXTUAL", ";"]);
+    assert_eq!(errors[2].render(input).lines().next(), Some("Error at line 3, column 14: Expected table name after FROM"));
+    Ok(())
+}
+
+#[test]
+fn test_keyword_suggestions() {
+    assert_eq!(parse_sql("SELEC * FROM t;").unwrap_err(), "Expected SELECT, WITH, CREATE, INSERT, DROP or COMMENT, found Identifier(\"SELEC\"). Did you mean SELECT?");
+    assert!(parse_sql("crate TABLE t(a INT);").unwrap_err().ends_with("Did you mean CREATE?"));
+    assert!(parse_sql("SELECT * FORM t;").unwrap_err().ends_with("Did you mean FROM?"));
+    assert!(parse_sql("CREATE TABLE t(a INT GENERATED ALWAS AS IDENTITY);").unwrap_err().ends_with("Did you mean ALWAYS?"));
+    // Words that are not close to any keyword get no suggestion
+    assert_eq!(parse_sql("SELECT a FROM t ORDER id;").unwrap_err(), "Expected BY after ORDER");
+    assert_eq!(parse_sql("SELECT a FROM t WHERE a = 1 extra;").unwrap_err(), "Expected operator, ORDER, LIMIT, FOR or ';', found Identifier(\"extra\")");
 }
 
 #[test]
 fn test_simple_select() {
     let stmt = parse_sql("SELECT name, age FROM users;").unwrap();
     assert_eq!(stmt, Statement::Select {
+        with: vec![],
         columns: vec![
             Expression::Identifier("name".to_string()),
             Expression::Identifier("age".to_string())
         ],
-        from: "users".to_string(),
+        from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
         r#where: None,
-        orderby: vec![]
+        orderby: vec![],
+        limit: None,
+        lock: None
     });
 }
 
@@ -121,14 +186,17 @@ fn test_simple_select() {
 fn test_select_with_where() {
     let stmt = parse_sql("SELECT id FROM users WHERE age > 18;").unwrap();
     assert_eq!(stmt, Statement::Select {
+        with: vec![],
         columns: vec![Expression::Identifier("id".to_string())],
-        from: "users".to_string(),
+        from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
         r#where: Some(Expression::BinaryOperation {
             left_operand: Box::new(Expression::Identifier("age".to_string())),
             operator: BinaryOperator::GreaterThan,
             right_operand: Box::new(Expression::Number(18))
         }),
-        orderby: vec![]
+        orderby: vec![],
+        limit: None,
+        lock: None
     });
 }
 
@@ -136,15 +204,18 @@ fn test_select_with_where() {
 fn test_select_with_order_by() {
     let stmt = parse_sql("SELECT id FROM users ORDER BY age DESC;").unwrap();
     assert_eq!(stmt, Statement::Select {
+        with: vec![],
         columns: vec![Expression::Identifier("id".to_string())],
-        from: "users".to_string(),
+        from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
         r#where: None,
         orderby: vec![
             Expression::UnaryOperation {
                 operand: Box::new(Expression::Identifier("age".to_string())),
                 operator: UnaryOperator::Desc
             }
-        ]
+        ],
+        limit: None,
+        lock: None
     });
 }
 
@@ -152,19 +223,24 @@ fn test_select_with_order_by() {
 fn test_create_table_simple() {
     let stmt = parse_sql("CREATE TABLE users(id INT, name VARCHAR(255));").unwrap();
     assert_eq!(stmt, Statement::CreateTable {
-        table_name: "users".to_string(),
+        table_name: ObjectName::new("users"),
+        temporary: false,
+        if_not_exists: false,
         column_list: vec![
             TableColumn {
                 column_name: "id".to_string(),
-                column_type: DBType::Int,
+                column_type: DBType::Int { unsigned: false },
                 constraints: vec![]
             },
             TableColumn {
                 column_name: "name".to_string(),
-                column_type: DBType::Varchar(255),
+                column_type: DBType::Varchar(Some(255)),
                 constraints: vec![]
             }
-        ]
+        ],
+        constraints: vec![],
+        options: vec![],
+        partitioning: None
     });
 }
 
@@ -172,16 +248,18 @@ fn test_create_table_simple() {
 fn test_create_table_with_constraints() {
     let stmt = parse_sql("CREATE TABLE employees(id INT PRIMARY KEY, age INT CHECK(age >= 18));").unwrap();
     assert_eq!(stmt, Statement::CreateTable {
-        table_name: "employees".to_string(),
+        table_name: ObjectName::new("employees"),
+        temporary: false,
+        if_not_exists: false,
         column_list: vec![
             TableColumn {
                 column_name: "id".to_string(),
-                column_type: DBType::Int,
+                column_type: DBType::Int { unsigned: false },
                 constraints: vec![Constraint::PrimaryKey]
             },
             TableColumn {
                 column_name: "age".to_string(),
-                column_type: DBType::Int,
+                column_type: DBType::Int { unsigned: false },
                 constraints: vec![
                     Constraint::Check(Expression::BinaryOperation {
                         left_operand: Box::new(Expression::Identifier("age".to_string())),
@@ -190,15 +268,19 @@ fn test_create_table_with_constraints() {
                     })
                 ]
             }
-        ]
+        ],
+        constraints: vec![],
+        options: vec![],
+        partitioning: None
     });
 }
 
 #[test]
 fn test_invalid_select() {
-    // Missing FROM clause
-    let result = parse_sql("SELECT id;");
+    // Missing FROM clause before WHERE
+    let result = parse_sql("SELECT id WHERE id > 1;");
     assert!(result.is_err());
+    assert!(parse_sql("SELECT *;").is_err());
 }
 
 #[test]
@@ -208,6 +290,201 @@ fn test_invalid_create_table() {
     assert!(result.is_err());
 }
 
+fn column_types(input: &str) -> Result<Vec<DBType>, String> {
+    match parse_sql(input)? {
+        Statement::CreateTable { column_list, .. } => Ok(column_list.into_iter().map(|column| column.column_type).collect()),
+        _ => Err("Expected CREATE TABLE statement".to_string()),
+    }
+}
+
+#[test]
+fn test_decimal_type() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE prices(a DECIMAL(10, 2), b NUMERIC(5), c numeric);")?, vec![
+        DBType::Decimal { precision: Some(10), scale: Some(2) },
+        DBType::Decimal { precision: Some(5), scale: None },
+        DBType::Decimal { precision: None, scale: None },
+    ]);
+    assert_eq!(parse_sql("CREATE TABLE t(a DECIMAL(2, 3));").unwrap_err(), "The scale 3 of DECIMAL is bigger than its precision 2");
+    assert_eq!(parse_sql("CREATE TABLE t(a NUMERIC(10,));").unwrap_err(), "Expected scale after NUMERIC(10,");
+    assert_eq!(parse_sql("CREATE TABLE t(a DECIMAL(10 2));").unwrap_err(), "Expected ) after the precision of DECIMAL");
+    Ok(())
+}
+
+#[test]
+fn test_varchar_without_length() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE t(a VARCHAR, b VARCHAR(20));")?, vec![DBType::Varchar(None), DBType::Varchar(Some(20))]);
+    assert_eq!(parse_sql("CREATE TABLE t(a VARCHAR());").unwrap_err(), "Expected number for VARCHAR length");
+    Ok(())
+}
+
+#[test]
+fn test_integer_types() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE t(a INT, b SMALLINT, c BIGINT, d TINYINT);")?, vec![
+        DBType::Int { unsigned: false },
+        DBType::SmallInt { unsigned: false },
+        DBType::BigInt { unsigned: false },
+        DBType::TinyInt { unsigned: false },
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_floating_point_types() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE t(a FLOAT, b REAL, c DOUBLE PRECISION, d DOUBLE);")?, vec![
+        DBType::Float,
+        DBType::Real,
+        DBType::Double,
+        DBType::Double,
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_binary_type() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE files(data BLOB, thumbnail bytea);")?, vec![DBType::Blob, DBType::Blob]);
+    Ok(())
+}
+
+#[test]
+fn test_table_constraints() -> Result<(), String> {
+    let Statement::CreateTable { column_list, constraints, .. } = parse_sql(
+        "CREATE TABLE lines(order_id INT, line INT, sku VARCHAR(20), PRIMARY KEY (order_id, line), CONSTRAINT one_sku UNIQUE (order_id, sku));"
+    )? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list.len(), 3);
+    assert_eq!(constraints, vec![
+        TableConstraint::PrimaryKey { name: None, columns: vec!["order_id".to_string(), "line".to_string()] },
+        TableConstraint::Unique { name: Some("one_sku".to_string()), columns: vec!["order_id".to_string(), "sku".to_string()] },
+    ]);
+    
+    assert_eq!(parse_sql("CREATE TABLE t(a INT, PRIMARY KEY a);").unwrap_err(), "Expected ( after PRIMARY KEY");
+    assert_eq!(parse_sql("CREATE TABLE t(a INT, UNIQUE (a, ));").unwrap_err(), "Expected column name after ,");
+    assert_eq!(parse_sql("CREATE TABLE t(a INT, CONSTRAINT c FOREIGN KEY (a));").unwrap_err(), "Expected PRIMARY KEY, UNIQUE or CHECK after CONSTRAINT c");
+    Ok(())
+}
+
+#[test]
+fn test_table_check_constraints() -> Result<(), String> {
+    let Statement::CreateTable { constraints, .. } = parse_sql("CREATE TABLE items(price INT, cost INT CHECK (cost > 0), CHECK (price > cost), CONSTRAINT positive CHECK (price > 0));")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(constraints, vec![
+        TableConstraint::Check { name: None, expression: parse_expression("price > cost")? },
+        TableConstraint::Check { name: Some("positive".to_string()), expression: parse_expression("price > 0")? },
+    ]);
+    assert_eq!(parse_sql("CREATE TABLE t(a INT, CHECK (a > 0);").unwrap_err(), "Expected ) after column definitions");
+    assert_eq!(parse_sql("CREATE TABLE t(a INT, CHECK a > 0);").unwrap_err(), "Expected ( after CHECK");
+    Ok(())
+}
+
+#[test]
+fn test_null_constraint() -> Result<(), String> {
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(age INT NULL, name VARCHAR(10) NULL DEFAULT 'x');")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::Null]);
+    assert_eq!(column_list[1].constraints, vec![Constraint::Null, Constraint::Default(Expression::String("x".to_string()))]);
+    assert_eq!(parse_sql("CREATE TABLE t(age INT NOT NULL NULL);").unwrap_err(), "Column age is declared both NULL and NOT NULL");
+    assert_eq!(parse_sql("CREATE TABLE t(age INT NULL NOT NULL);").unwrap_err(), "Column age is declared both NULL and NOT NULL");
+    Ok(())
+}
+
+#[test]
+fn test_default_values() -> Result<(), String> {
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(age INT DEFAULT 0 NOT NULL, created TIMESTAMP DEFAULT NOW(), score INT DEFAULT -1 * 2);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::Default(Expression::Number(0)), Constraint::NotNull]);
+    assert_eq!(column_list[1].constraints, vec![Constraint::Default(parse_expression("NOW()")?)]);
+    assert_eq!(column_list[2].constraints, vec![Constraint::Default(parse_expression("-1 * 2")?)]);
+    assert!(parse_sql("CREATE TABLE t(a INT DEFAULT);").is_err());
+    
+    // NULL is the default that nullable columns have in dumps, and is told
+    // apart from the NOT NULL constraint after it
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(a INT DEFAULT NULL, b INT NULL DEFAULT NULL, c INT DEFAULT NULL NOT NULL);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::Default(Expression::Null)]);
+    assert_eq!(column_list[1].constraints, vec![Constraint::Null, Constraint::Default(Expression::Null)]);
+    assert_eq!(column_list[2].constraints, vec![Constraint::Default(Expression::Null), Constraint::NotNull]);
+    Ok(())
+}
+
+#[test]
+fn test_null_literal() -> Result<(), String> {
+    assert!(matches!(parse_sql("SELECT NULL;")?, Statement::Select { columns, .. } if columns == vec![Expression::Null]));
+    assert_eq!(parse_expression("COALESCE(a, NULL)")?, Expression::Function {
+        name: "COALESCE".to_string(),
+        distinct: false,
+        arguments: vec![Expression::Identifier("a".to_string()), Expression::Null],
+        over: None,
+    });
+    assert!(matches!(parse_expression("NULLIF(x, NULL)")?, Expression::Function { arguments, .. } if arguments[1] == Expression::Null));
+    // IS NULL is still the predicate rather than a comparison with the literal
+    assert!(matches!(parse_expression("a IS NULL")?, Expression::IsNull { negated: false, .. }));
+    Ok(())
+}
+
+#[test]
+fn test_generated_keys() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE t(a SERIAL, b BIGSERIAL);")?, vec![DBType::Serial, DBType::BigSerial]);
+    
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(a INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY, b BIGINT GENERATED BY DEFAULT AS IDENTITY);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::Identity { always: true }, Constraint::PrimaryKey]);
+    assert_eq!(column_list[1].constraints, vec![Constraint::Identity { always: false }]);
+    
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(a INT NOT NULL AUTO_INCREMENT, b BIGINT PRIMARY KEY autoincrement);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::NotNull, Constraint::AutoIncrement]);
+    assert_eq!(column_list[1].constraints, vec![Constraint::PrimaryKey, Constraint::AutoIncrement]);
+    assert_eq!(
+        parse_sql("CREATE TABLE t(a VARCHAR(10) AUTO_INCREMENT);").unwrap_err(),
+        "Only integer columns can be AUTO_INCREMENT, but column a is not one"
+    );
+    
+    assert_eq!(parse_sql("CREATE TABLE t(a INT GENERATED AS IDENTITY);").unwrap_err(), "Expected ALWAYS or BY DEFAULT after GENERATED");
+    assert_eq!(parse_sql("CREATE TABLE t(a INT GENERATED ALWAYS AS);").unwrap_err(), "Expected IDENTITY after GENERATED AS");
+    Ok(())
+}
+
+#[test]
+fn test_uuid_type() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE users(uuid UUID PRIMARY KEY, parent uuid);")?, vec![DBType::Uuid, DBType::Uuid]);
+    Ok(())
+}
+
+#[test]
+fn test_custom_type() -> Result<(), String> {
+    let mut keywords = Keywords::default();
+    keywords.insert_custom("vector");
+    let mut tokenizer = Tokenizer::new("CREATE TABLE items(id INT, embedding vector);");
+    tokenizer.set_keywords(&keywords);
+    let statement = Parser::new(tokenizer)?.parse_statement()?;
+    let Statement::CreateTable { column_list, .. } = statement else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[1].column_type, DBType::Custom("VECTOR".to_string()));
+    assert!(parse_sql("CREATE TABLE items(embedding VECTOR);").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_date_and_time_types() -> Result<(), String> {
+    assert_eq!(column_types("CREATE TABLE events(date DATE, at TIME, created TIMESTAMP, updated timestamp with time zone, logged DATETIME);")?, vec![
+        DBType::Date,
+        DBType::Time,
+        DBType::Timestamp { with_time_zone: false },
+        DBType::Timestamp { with_time_zone: true },
+        DBType::DateTime,
+    ]);
+    assert_eq!(parse_sql("CREATE TABLE t(a TIMESTAMP WITH ZONE);").unwrap_err(), "Expected TIME ZONE after TIMESTAMP WITH");
+    Ok(())
+}
+
 #[test]
 fn test_unmatched_parentheses() -> Result<(), String> {
     let result = parse_expression("(5 + 3");
@@ -263,11 +540,14 @@ fn test_select_star() -> Result<(), String> {
     let stmt = parse_sql("SELECT * FROM users;")?;
     
     match stmt {
-        Statement::Select { columns, from, r#where, orderby } => {
+        Statement::Select { with, columns, from, r#where, orderby, limit, lock } => {
+            assert!(with.is_empty());
             assert_eq!(columns, vec![Expression::Wildcard]);
-            assert_eq!(from, "users");
+            assert_eq!(from, vec![TableReference::Table { name: ObjectName::new("users"), alias: None }]);
             assert!(r#where.is_none());
             assert!(orderby.is_empty());
+            assert!(limit.is_none());
+            assert!(lock.is_none());
             Ok(())
         },
         _ => Err("Expected SELECT statement".to_string()),
@@ -279,11 +559,14 @@ fn test_select_star_with_where() -> Result<(), String> {
     let stmt = parse_sql("SELECT * FROM users WHERE age > 18;")?;
     
     match stmt {
-        Statement::Select { columns, from, r#where, orderby } => {
+        Statement::Select { with, columns, from, r#where, orderby, limit, lock } => {
+            assert!(with.is_empty());
             assert_eq!(columns, vec![Expression::Wildcard]);
-            assert_eq!(from, "users");
+            assert_eq!(from, vec![TableReference::Table { name: ObjectName::new("users"), alias: None }]);
             assert!(r#where.is_some());
             assert!(orderby.is_empty());
+            assert!(limit.is_none());
+            assert!(lock.is_none());
             Ok(())
         },
         _ => Err("Expected SELECT statement".to_string()),
@@ -307,4 +590,1368 @@ fn test_star_as_multiply_operator() -> Result<(), String> {
         },
         _ => Err("Expected SELECT statement".to_string()),
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_select_with_join() {
+    let stmt = parse_sql("SELECT name FROM users JOIN orders ON id = user_id;").unwrap();
+    assert_eq!(stmt, Statement::Select {
+        with: vec![],
+        columns: vec![Expression::Identifier("name".to_string())],
+        from: vec![TableReference::Join {
+            left: Box::new(TableReference::Table { name: ObjectName::new("users"), alias: None }),
+            join_type: JoinType::Inner,
+            right: Box::new(TableReference::Table { name: ObjectName::new("orders"), alias: None }),
+            on: Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("id".to_string())),
+                operator: BinaryOperator::Equal,
+                right_operand: Box::new(Expression::Identifier("user_id".to_string()))
+            }
+        }],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    });
+}
+
+#[test]
+fn test_join_tree_is_left_associative() -> Result<(), String> {
+    let stmt = parse_sql("SELECT * FROM a LEFT OUTER JOIN b ON x = y FULL JOIN c ON y = z INNER JOIN d ON z = w WHERE x > 1;")?;
+    let Statement::Select { from, r#where, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert!(r#where.is_some());
+    match from.as_slice() {
+        [TableReference::Join { left, join_type, right, .. }] => {
+            assert_eq!(*join_type, JoinType::Inner);
+            assert_eq!(**right, TableReference::Table { name: ObjectName::new("d"), alias: None });
+            match &**left {
+                TableReference::Join { left, join_type: JoinType::Full, .. } => {
+                    assert!(matches!(**left, TableReference::Join { join_type: JoinType::Left, .. }));
+                    Ok(())
+                },
+                _ => Err("Expected FULL JOIN in the middle of the tree".to_string()),
+            }
+        },
+        _ => Err("Expected SELECT with a join".to_string()),
+    }
+}
+
+#[test]
+fn test_parenthesized_joins() -> Result<(), String> {
+    // Parentheses make a join the right side of another one
+    let Statement::Select { from, .. } = parse_sql("SELECT * FROM a JOIN (b LEFT JOIN (SELECT 1) AS c ON y = z) ON x = y;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    let [TableReference::Join { left, join_type: JoinType::Inner, right, .. }] = from.as_slice() else {
+        return Err("Expected SELECT with a join".to_string());
+    };
+    assert_eq!(**left, TableReference::Table { name: ObjectName::new("a"), alias: None });
+    assert!(matches!(&**right, TableReference::Join { join_type: JoinType::Left, right, .. } if matches!(**right, TableReference::Derived { .. })));
+    
+    assert_eq!(parse_sql("SELECT * FROM (a) JOIN ((b)) ON x = y;")?, parse_sql("SELECT * FROM a JOIN b ON x = y;")?);
+    assert_eq!(parse_sql("SELECT * FROM a JOIN (b JOIN c ON x = y ON x = z;").unwrap_err(), "Expected ) after the joined tables");
+    Ok(())
+}
+
+#[test]
+fn test_invalid_joins() {
+    assert!(parse_sql("SELECT * FROM a JOIN b;").unwrap_err().contains("Expected ON"));
+    assert!(parse_sql("SELECT * FROM a RIGHT b ON x = y;").unwrap_err().contains("Expected JOIN after RIGHT"));
+    assert!(parse_sql("SELECT * FROM a INNER OUTER JOIN b ON x = y;").is_err());
+}
+
+#[test]
+fn test_join_keywords_as_functions() {
+    // LEFT and RIGHT are still the string functions of MySQL and SQL Server
+    let stmt = parse_sql("SELECT LEFT(name, 3), right(code, 1) FROM a LEFT JOIN b ON LEFT(a.x, 1) = b.y;").unwrap();
+    let Statement::Select { columns, from, .. } = stmt else {
+        panic!("Expected SELECT statement");
+    };
+    assert_eq!(columns[0], Expression::Function {
+        name: "LEFT".to_string(),
+        distinct: false,
+        arguments: vec![Expression::Identifier("name".to_string()), Expression::Number(3)],
+        over: None,
+    });
+    assert!(matches!(&columns[1], Expression::Function { name, .. } if name == "RIGHT"));
+    assert!(matches!(&from[0], TableReference::Join { join_type: JoinType::Left, .. }));
+    assert_eq!(parse_sql("SELECT LEFT FROM t;").unwrap_err(), "Expected ( after LEFT");
+}
+
+#[test]
+fn test_create_view() {
+    let stmt = parse_sql("CREATE VIEW adults AS SELECT id FROM users WHERE age >= 18;").unwrap();
+    assert_eq!(stmt, Statement::CreateView {
+        name: ObjectName::new("adults"),
+        or_replace: false,
+        if_not_exists: false,
+        query: Box::new(Statement::Select {
+            with: vec![],
+            columns: vec![Expression::Identifier("id".to_string())],
+            from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
+            r#where: Some(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("age".to_string())),
+                operator: BinaryOperator::GreaterThanOrEqual,
+                right_operand: Box::new(Expression::Number(18))
+            }),
+            orderby: vec![],
+            limit: None,
+            lock: None
+        })
+    });
+}
+
+#[test]
+fn test_invalid_create_view() {
+    assert!(parse_sql("CREATE VIEW adults SELECT id FROM users;").unwrap_err().contains("Expected AS"));
+    assert!(parse_sql("CREATE VIEW adults AS SELECT id FROM users").is_err());
+    assert!(parse_sql("CREATE INDEX idx;").unwrap_err().contains("Expected TABLE or VIEW"));
+}
+
+#[test]
+fn test_select_with_cte() -> Result<(), String> {
+    let stmt = parse_sql("WITH adults AS (SELECT id FROM users WHERE age >= 18), ids AS (SELECT id FROM adults) SELECT * FROM ids;")?;
+    match stmt {
+        Statement::Select { with, from, .. } => {
+            assert_eq!(with.len(), 2);
+            assert_eq!(with[0].name, "adults");
+            assert_eq!(with[1].name, "ids");
+            assert!(matches!(&with[1].query, Statement::Select { from, .. } if from[0] == TableReference::Table { name: ObjectName::new("adults"), alias: None }));
+            assert_eq!(from, vec![TableReference::Table { name: ObjectName::new("ids"), alias: None }]);
+            Ok(())
+        },
+        _ => Err("Expected SELECT statement".to_string()),
+    }
+}
+
+#[test]
+fn test_invalid_cte() {
+    assert!(parse_sql("WITH a (SELECT 1 FROM t) SELECT * FROM a;").unwrap_err().contains("Expected AS"));
+    assert!(parse_sql("WITH a AS SELECT id FROM t SELECT * FROM a;").is_err());
+    assert_eq!(parse_sql("WITH a AS (SELECT id FROM t);").unwrap_err(), "Expected ',' or SELECT, found Semicolon");
+}
+
+#[test]
+fn test_table_aliases() -> Result<(), String> {
+    let stmt = parse_sql("SELECT * FROM users AS u JOIN orders o ON id = user_id;")?;
+    let Statement::Select { from, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    match from.as_slice() {
+        [TableReference::Join { left, right, .. }] => {
+            assert_eq!(**left, TableReference::Table { name: ObjectName::new("users"), alias: Some("u".to_string()) });
+            assert_eq!(**right, TableReference::Table { name: ObjectName::new("orders"), alias: Some("o".to_string()) });
+            Ok(())
+        },
+        _ => Err("Expected SELECT with a join".to_string()),
+    }
+}
+
+#[test]
+fn test_invalid_table_alias() {
+    assert!(parse_sql("SELECT * FROM users AS;").unwrap_err().contains("Expected alias name after AS"));
+    assert!(parse_sql("SELECT * FROM users u v;").is_err());
+}
+
+#[test]
+fn test_qualified_column_references() -> Result<(), String> {
+    let stmt = parse_sql("SELECT u.name FROM users u WHERE u.age > 18;")?;
+    match stmt {
+        Statement::Select { columns, r#where, .. } => {
+            assert_eq!(columns, vec![Expression::CompoundIdentifier(vec!["u".to_string(), "name".to_string()])]);
+            assert_eq!(r#where, Some(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::CompoundIdentifier(vec!["u".to_string(), "age".to_string()])),
+                operator: BinaryOperator::GreaterThan,
+                right_operand: Box::new(Expression::Number(18))
+            }));
+            Ok(())
+        },
+        _ => Err("Expected SELECT statement".to_string()),
+    }
+}
+
+#[test]
+fn test_invalid_qualified_column_reference() {
+    assert!(parse_sql("SELECT u. FROM users u;").unwrap_err().contains("Expected column name after ."));
+    assert!(parse_sql("SELECT .name FROM users;").is_err());
+}
+
+#[test]
+fn test_multiple_statements() -> Result<(), String> {
+    let statements = build_statements("CREATE TABLE t(id INT); SELECT id FROM t;\nSELECT * FROM t;")?;
+    assert_eq!(statements.len(), 3);
+    assert!(matches!(statements[0], Statement::CreateTable { .. }));
+    assert!(matches!(statements[2], Statement::Select { .. }));
+    
+    assert_eq!(build_statements("")?, vec![]);
+    assert_eq!(build_statements("-- nothing to run\n")?, vec![]);
+    assert!(build_statements("SELECT id FROM t; SELECT id FROM t").unwrap_err().ends_with("or ';', found end of input"));
+    
+    // Commented out lines are skipped
+    let statements = build_statements("-- schema\nCREATE TABLE t(\n  id INT, -- the key\n  n INT -- a number\n);\n-- SELECT 1;\n")?;
+    assert_eq!(statements.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_error_position() -> Result<(), String> {
+    let mut parser = Parser::new(Tokenizer::new("SELECT id FROM t;\nSELECT id\n  FROM t WHERE;"))?;
+    parser.parse_statement()?;
+    assert!(parser.parse_statement().is_err());
+    let position = parser.position();
+    assert_eq!((position.line, position.column), (3, 15));
+    Ok(())
+}
+
+#[test]
+fn test_skip_failed_statement() -> Result<(), String> {
+    let input = "SELECT FROM t; CREATE TABLE t(id INT); SELECT 'abc FROM t";
+    let mut parser = Parser::new(Tokenizer::new(input))?;
+    assert!(parser.parse_statement().is_err());
+    parser.skip_statement();
+    assert_eq!(parser.offset(), 15);
+    assert_eq!(parser.parse_statement()?.kind(), "CREATE TABLE");
+    // A tokenizer error does not stop skipping
+    assert!(parser.parse_statement().is_err());
+    parser.skip_statement();
+    assert!(parser.is_at_end());
+    Ok(())
+}
+
+#[test]
+fn test_comma_separated_tables() -> Result<(), String> {
+    let stmt = parse_sql("SELECT * FROM a, b x JOIN c ON x.id = c.id WHERE a.id = x.id;")?;
+    let Statement::Select { from, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(from.len(), 2);
+    assert_eq!(from[0], TableReference::Table { name: ObjectName::new("a"), alias: None });
+    assert!(matches!(from[1], TableReference::Join { join_type: JoinType::Inner, .. }));
+    
+    assert!(parse_sql("SELECT * FROM a, ;").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_derived_table() -> Result<(), String> {
+    let stmt = parse_sql("SELECT sub.id FROM (SELECT id FROM users WHERE age > 18) AS sub;")?;
+    let Statement::Select { from, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(from, vec![TableReference::Derived {
+        subquery: Box::new(parse_sql("SELECT id FROM users WHERE age > 18;")?),
+        alias: "sub".to_string()
+    }]);
+    
+    // Derived tables can be joined and nested
+    let stmt = parse_sql("SELECT * FROM (SELECT x FROM (SELECT x FROM t) inner_sub) a JOIN b ON a.x = b.x;")?;
+    assert!(matches!(stmt, Statement::Select { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_derived_table() {
+    assert!(parse_sql("SELECT * FROM (SELECT id FROM users);").unwrap_err().contains("Expected alias after derived table"));
+    assert!(parse_sql("SELECT * FROM (SELECT id FROM users sub;").unwrap_err().contains("Expected ) after subquery"));
+    assert!(parse_sql("SELECT * FROM (users) u;").is_err());
+}
+
+#[test]
+fn test_in_subquery() -> Result<(), String> {
+    let stmt = parse_sql("SELECT name FROM users WHERE id IN (SELECT user_id FROM orders) AND age > 18;")?;
+    let Statement::Select { r#where, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(r#where, Some(Expression::BinaryOperation {
+        left_operand: Box::new(Expression::InSubquery {
+            expression: Box::new(Expression::Identifier("id".to_string())),
+            subquery: Box::new(parse_sql("SELECT user_id FROM orders;")?)
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("age".to_string())),
+            operator: BinaryOperator::GreaterThan,
+            right_operand: Box::new(Expression::Number(18))
+        })
+    }));
+    Ok(())
+}
+
+#[test]
+fn test_in_list() -> Result<(), String> {
+    assert_eq!(parse_expression("status IN ('new', 'paid')")?, Expression::InList {
+        expression: Box::new(Expression::Identifier("status".to_string())),
+        list: vec![Expression::String("new".to_string()), Expression::String("paid".to_string())],
+        negated: false
+    });
+    assert_eq!(parse_expression("id NOT IN (1, 2 + 3) OR admin")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::InList {
+            expression: Box::new(Expression::Identifier("id".to_string())),
+            list: vec![
+                Expression::Number(1),
+                Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Number(2)),
+                    operator: BinaryOperator::Plus,
+                    right_operand: Box::new(Expression::Number(3))
+                }
+            ],
+            negated: true
+        }),
+        operator: BinaryOperator::Or,
+        right_operand: Box::new(Expression::Identifier("admin".to_string()))
+    });
+    // NOT IN with a subquery is the negation of the IN predicate
+    assert_eq!(parse_expression("id NOT IN (SELECT user_id FROM orders)")?, Expression::UnaryOperation {
+        operand: Box::new(Expression::InSubquery {
+            expression: Box::new(Expression::Identifier("id".to_string())),
+            subquery: Box::new(parse_sql("SELECT user_id FROM orders;")?)
+        }),
+        operator: UnaryOperator::Not
+    });
+    
+    assert!(parse_expression("id IN (1, 2").unwrap_err().contains("Expected ) after the values of IN"));
+    assert!(parse_expression("id IN ()").is_err());
+    assert!(parse_expression("id IN 1").unwrap_err().contains("Expected ( after IN"));
+    Ok(())
+}
+
+#[test]
+fn test_is_null() -> Result<(), String> {
+    assert_eq!(parse_expression("email IS NULL")?, Expression::IsNull {
+        expression: Box::new(Expression::Identifier("email".to_string())),
+        negated: false
+    });
+    // IS binds looser than arithmetic and tighter than AND
+    assert_eq!(parse_expression("a + 1 IS NOT NULL AND b")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::IsNull {
+            expression: Box::new(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("a".to_string())),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Number(1))
+            }),
+            negated: true
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::Identifier("b".to_string()))
+    });
+    
+    assert!(parse_expression("email IS 5").unwrap_err().contains("Expected NULL or DISTINCT FROM after IS"));
+    assert!(parse_expression("email IS NOT TRUE").unwrap_err().contains("Expected NULL or DISTINCT FROM after IS NOT"));
+    Ok(())
+}
+
+#[test]
+fn test_bitwise_operators() -> Result<(), String> {
+    let binary = |left, operator, right| Expression::BinaryOperation {
+        left_operand: Box::new(left),
+        operator,
+        right_operand: Box::new(right)
+    };
+    let identifier = |name: &str| Expression::Identifier(name.to_string());
+    
+    // The mask is applied before the comparison
+    assert_eq!(parse_expression("flags & 4 != 0")?, binary(
+        binary(identifier("flags"), BinaryOperator::BitwiseAnd, Expression::Number(4)),
+        BinaryOperator::NotEqual,
+        Expression::Number(0)
+    ));
+    assert_eq!(parse_expression("flags & 4 <> 0")?, parse_expression("flags & 4 != 0")?);
+    // | binds loosest, then ^, then &, then the shifts, which bind looser than arithmetic
+    assert_eq!(parse_expression("a | b ^ c & d << e + 1")?, binary(
+        identifier("a"),
+        BinaryOperator::BitwiseOr,
+        binary(
+            identifier("b"),
+            BinaryOperator::BitwiseXor,
+            binary(
+                identifier("c"),
+                BinaryOperator::BitwiseAnd,
+                binary(identifier("d"), BinaryOperator::ShiftLeft, binary(identifier("e"), BinaryOperator::Plus, Expression::Number(1)))
+            )
+        )
+    ));
+    assert_eq!(parse_expression("a >> 1 >> 2")?, binary(
+        binary(identifier("a"), BinaryOperator::ShiftRight, Expression::Number(1)),
+        BinaryOperator::ShiftRight,
+        Expression::Number(2)
+    ));
+    // The complement binds tighter than any binary operator
+    let not = |operand| Expression::UnaryOperation { operand: Box::new(operand), operator: UnaryOperator::BitwiseNot };
+    assert_eq!(parse_expression("~mask & flags * 2")?, binary(
+        not(identifier("mask")),
+        BinaryOperator::BitwiseAnd,
+        binary(identifier("flags"), BinaryOperator::Multiply, Expression::Number(2))
+    ));
+    assert_eq!(parse_expression("~~a")?, not(not(identifier("a"))));
+    assert!(parse_expression("~").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_hex_and_bit_string_literals() -> Result<(), String> {
+    assert_eq!(parse_expression("flags & 0xFF")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("flags".to_string())),
+        operator: BinaryOperator::BitwiseAnd,
+        right_operand: Box::new(Expression::Number(255))
+    });
+    assert_eq!(parse_expression("B'0101'")?, Expression::BitString("0101".to_string()));
+    // Hexadecimal numbers have the same range as decimal ones
+    assert!(parse_expression("0xFFFFFFFFFFFFFFFF").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_prefixed_string_literals() -> Result<(), String> {
+    assert_eq!(parse_expression("N'Zoë'")?, Expression::String("Zoë".to_string()));
+    assert_eq!(parse_expression(r"E'line\nbreak'")?, Expression::String("line\nbreak".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_placeholders() -> Result<(), String> {
+    assert_eq!(parse_expression("id = ?")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("id".to_string())),
+        operator: BinaryOperator::Equal,
+        right_operand: Box::new(Expression::Placeholder(Placeholder::Anonymous))
+    });
+    assert_eq!(parse_expression("$12")?, Expression::Placeholder(Placeholder::Numbered(12)));
+    assert_eq!(parse_expression(":name")?, Expression::Placeholder(Placeholder::Named("name".to_string())));
+    assert_eq!(parse_expression("@name")?, Expression::Placeholder(Placeholder::Named("name".to_string())));
+    
+    assert!(parse_expression("id = $0").unwrap_err().contains("Invalid placeholder $0"));
+    assert!(parse_expression("id = $99999999999999999999").unwrap_err().contains("Invalid placeholder"));
+    Ok(())
+}
+
+#[test]
+fn test_statement_parameters() -> Result<(), String> {
+    let stmt = parse_sql("SELECT name, ? FROM users WHERE id = :id OR (age > $2 AND age < @age) ORDER BY :id;")?;
+    assert_eq!(stmt.parameters(), vec![
+        &Placeholder::Anonymous,
+        &Placeholder::Named("id".to_string()),
+        &Placeholder::Numbered(2),
+        &Placeholder::Named("age".to_string()),
+        &Placeholder::Named("id".to_string()),
+    ]);
+    assert!(parse_sql("SELECT name FROM users;")?.parameters().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_temporal_literals() -> Result<(), String> {
+    assert_eq!(parse_expression("created_at >= DATE '2024-01-01'")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("created_at".to_string())),
+        operator: BinaryOperator::GreaterThanOrEqual,
+        right_operand: Box::new(Expression::Temporal { kind: TemporalKind::Date, value: "2024-01-01".to_string() })
+    });
+    assert_eq!(parse_expression("time '12:00:00.25'")?, Expression::Temporal { kind: TemporalKind::Time, value: "12:00:00.25".to_string() });
+    assert_eq!(
+        parse_expression("TIMESTAMP '2024-01-01T23:59:59'")?,
+        Expression::Temporal { kind: TemporalKind::Timestamp, value: "2024-01-01T23:59:59".to_string() }
+    );
+    // Without a string after them the words are ordinary names
+    assert_eq!(parse_expression("date")?, Expression::Identifier("date".to_string()));
+    assert!(matches!(parse_expression("DATE(created_at)")?, Expression::Function { .. }));
+    
+    assert!(parse_expression("DATE '2024-13-01'").unwrap_err().contains("'2024-13-01' is not a valid DATE literal"));
+    // Days past the end of the month, with February 29 only in leap years
+    for date in ["2024-02-30", "2023-02-29", "2023-04-31", "2023-11-31", "1900-02-29"] {
+        assert!(parse_expression(&format!("DATE '{}'", date)).unwrap_err().contains("not a valid DATE literal"), "{}", date);
+    }
+    for date in ["2024-02-29", "2000-02-29", "2023-01-31", "2023-04-30", "2023-12-31"] {
+        assert!(parse_expression(&format!("DATE '{}'", date)).is_ok(), "{}", date);
+    }
+    assert!(parse_expression("TIMESTAMP '2023-06-31 12:00:00'").unwrap_err().contains("not a valid TIMESTAMP literal"));
+    assert!(parse_expression("TIME '24:00:00'").unwrap_err().contains("not a valid TIME literal"));
+    assert!(parse_expression("TIMESTAMP '2024-01-01'").unwrap_err().contains("not a valid TIMESTAMP literal"));
+    Ok(())
+}
+
+#[test]
+fn test_coalesce_and_nullif() -> Result<(), String> {
+    let stmt = parse_sql("SELECT COALESCE(nickname, name, 'anonymous') FROM users WHERE COALESCE(a, b, c, d, 0) > NULLIF(e, 0);")?;
+    let Statement::Select { columns, r#where: Some(Expression::BinaryOperation { left_operand, right_operand, .. }), .. } = stmt else {
+        return Err("Expected SELECT statement with a comparison".to_string());
+    };
+    assert!(matches!(&columns[0], Expression::Function { name, arguments, .. } if name == "COALESCE" && arguments.len() == 3));
+    assert!(matches!(*left_operand, Expression::Function { arguments, .. } if arguments.len() == 5));
+    assert!(matches!(*right_operand, Expression::Function { name, arguments, .. } if name == "NULLIF" && arguments.len() == 2));
+    assert!(matches!(parse_expression("coalesce(a)")?, Expression::Function { arguments, .. } if arguments.len() == 1));
+    
+    assert!(parse_expression("COALESCE()").unwrap_err().contains("COALESCE takes at least one argument, got 0"));
+    assert!(parse_expression("NULLIF(a, b, c)").unwrap_err().contains("NULLIF takes two arguments, got 3"));
+    Ok(())
+}
+
+#[test]
+fn test_float_literal() -> Result<(), String> {
+    assert_eq!(parse_expression("price > 9.99")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("price".to_string())),
+        operator: BinaryOperator::GreaterThan,
+        right_operand: Box::new(Expression::Float(9.99))
+    });
+    Ok(())
+}
+
+#[test]
+fn test_modulo() -> Result<(), String> {
+    assert_eq!(parse_expression("id % 2 = 0")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("id".to_string())),
+            operator: BinaryOperator::Modulo,
+            right_operand: Box::new(Expression::Number(2))
+        }),
+        operator: BinaryOperator::Equal,
+        right_operand: Box::new(Expression::Number(0))
+    });
+    // Same precedence as * and /, evaluated from left to right
+    assert_eq!(parse_expression("a * b % c")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("a".to_string())),
+            operator: BinaryOperator::Multiply,
+            right_operand: Box::new(Expression::Identifier("b".to_string()))
+        }),
+        operator: BinaryOperator::Modulo,
+        right_operand: Box::new(Expression::Identifier("c".to_string()))
+    });
+    Ok(())
+}
+
+#[test]
+fn test_is_distinct_from() -> Result<(), String> {
+    assert_eq!(parse_expression("a IS DISTINCT FROM b + 1 AND c")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("a".to_string())),
+            operator: BinaryOperator::IsDistinctFrom,
+            right_operand: Box::new(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("b".to_string())),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Number(1))
+            })
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::Identifier("c".to_string()))
+    });
+    assert!(matches!(
+        parse_expression("a IS NOT DISTINCT FROM NULLIF(b, 0)")?,
+        Expression::BinaryOperation { operator: BinaryOperator::IsNotDistinctFrom, .. }
+    ));
+    assert!(parse_expression("a IS DISTINCT b").unwrap_err().contains("Expected FROM after IS DISTINCT"));
+    assert!(parse_expression("a IS NOT DISTINCT b").unwrap_err().contains("Expected FROM after IS NOT DISTINCT"));
+    Ok(())
+}
+
+#[test]
+fn test_extract() -> Result<(), String> {
+    let stmt = parse_sql("SELECT id FROM orders WHERE EXTRACT(YEAR FROM created_at) = 2024;")?;
+    let Statement::Select { r#where, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(r#where, Some(Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Extract {
+            field: DateField::Year,
+            expression: Box::new(Expression::Identifier("created_at".to_string()))
+        }),
+        operator: BinaryOperator::Equal,
+        right_operand: Box::new(Expression::Number(2024))
+    }));
+    assert!(matches!(parse_expression("extract(month from DATE '2024-05-01')")?, Expression::Extract { field: DateField::Month, .. }));
+    // Only EXTRACT followed by a parenthesis is the special form
+    assert_eq!(parse_expression("extract")?, Expression::Identifier("extract".to_string()));
+    
+    assert!(parse_expression("EXTRACT(DECADE FROM d)").unwrap_err().contains("Expected YEAR, QUARTER, MONTH, WEEK, DAY, HOUR, MINUTE or SECOND after EXTRACT("));
+    assert!(parse_expression("EXTRACT(YEAR d)").unwrap_err().contains("Expected FROM after YEAR"));
+    assert!(parse_expression("EXTRACT(YEAR FROM d").unwrap_err().contains("Expected ) after the arguments of EXTRACT"));
+    Ok(())
+}
+
+#[test]
+fn test_similar_to() -> Result<(), String> {
+    assert_eq!(parse_expression("name SIMILAR TO '(A|B)%' AND b")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("name".to_string())),
+            operator: BinaryOperator::SimilarTo,
+            right_operand: Box::new(Expression::String("(A|B)%".to_string()))
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::Identifier("b".to_string()))
+    });
+    assert!(matches!(parse_expression("name not similar to 'A%'")?, Expression::BinaryOperation { operator: BinaryOperator::NotSimilarTo, .. }));
+    assert!(parse_expression("name SIMILAR 'A%'").unwrap_err().contains("Expected TO after SIMILAR"));
+    Ok(())
+}
+
+#[test]
+fn test_cast() -> Result<(), String> {
+    let cast = Expression::Cast {
+        expression: Box::new(Expression::Identifier("age".to_string())),
+        target_type: DBType::Varchar(Some(10))
+    };
+    assert_eq!(parse_expression("CAST(age AS VARCHAR(10))")?, cast);
+    assert_eq!(parse_expression("age::VARCHAR(10)")?, cast);
+    // :: binds tighter than the arithmetic and the sign around it
+    assert_eq!(parse_expression("-a::INT * 2")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::UnaryOperation {
+            operand: Box::new(Expression::Cast {
+                expression: Box::new(Expression::Identifier("a".to_string())),
+                target_type: DBType::Int { unsigned: false }
+            }),
+            operator: UnaryOperator::Minus
+        }),
+        operator: BinaryOperator::Multiply,
+        right_operand: Box::new(Expression::Number(2))
+    });
+    assert!(matches!(parse_expression("CAST(a + 1 AS BOOL)::INT")?, Expression::Cast { target_type: DBType::Int { unsigned: false }, .. }));
+    
+    assert!(parse_expression("CAST age AS INT").unwrap_err().contains("Expected ( after CAST"));
+    assert!(parse_expression("CAST(age INT)").unwrap_err().contains("Expected AS after the expression of CAST"));
+    assert!(parse_expression("CAST(age AS INT").unwrap_err().contains("Expected ) after the type of CAST"));
+    assert!(parse_expression("age::users").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_quantified_comparison() -> Result<(), String> {
+    assert_eq!(parse_expression("salary > ALL (SELECT salary FROM interns)")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("salary".to_string())),
+        operator: BinaryOperator::GreaterThan,
+        right_operand: Box::new(Expression::Quantified {
+            quantifier: Quantifier::All,
+            subquery: Box::new(parse_sql("SELECT salary FROM interns;")?)
+        })
+    });
+    let any = parse_expression("id = ANY (SELECT user_id FROM orders) AND id != some (SELECT id FROM bans)")?;
+    let Expression::BinaryOperation { left_operand, right_operand, .. } = any else {
+        return Err("Expected AND".to_string());
+    };
+    assert!(matches!(*left_operand, Expression::BinaryOperation { right_operand, .. } if matches!(*right_operand, Expression::Quantified { quantifier: Quantifier::Any, .. })));
+    assert!(matches!(*right_operand, Expression::BinaryOperation { right_operand, .. } if matches!(*right_operand, Expression::Quantified { quantifier: Quantifier::Any, .. })));
+    // Without a parenthesis after them the quantifiers are column names
+    assert_eq!(parse_expression("a = all + 1")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("a".to_string())),
+        operator: BinaryOperator::Equal,
+        right_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("all".to_string())),
+            operator: BinaryOperator::Plus,
+            right_operand: Box::new(Expression::Number(1))
+        })
+    });
+    assert!(matches!(parse_expression("a = any.b")?, Expression::BinaryOperation { right_operand, .. } if matches!(*right_operand, Expression::CompoundIdentifier(_))));
+    
+    assert!(parse_expression("a = ANY (1, 2)").unwrap_err().contains("Expected SELECT after ANY ("));
+    assert!(parse_expression("a = ALL (SELECT b FROM t").unwrap_err().contains("Expected ) after subquery"));
+    Ok(())
+}
+
+#[test]
+fn test_tuples() -> Result<(), String> {
+    let identifier = |name: &str| Expression::Identifier(name.to_string());
+    assert_eq!(parse_expression("(a, b) = (1, 2)")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Tuple(vec![identifier("a"), identifier("b")])),
+        operator: BinaryOperator::Equal,
+        right_operand: Box::new(Expression::Tuple(vec![Expression::Number(1), Expression::Number(2)]))
+    });
+    assert_eq!(parse_expression("(x, y) IN ((1, 2), (3, 4))")?, Expression::InList {
+        expression: Box::new(Expression::Tuple(vec![identifier("x"), identifier("y")])),
+        list: vec![
+            Expression::Tuple(vec![Expression::Number(1), Expression::Number(2)]),
+            Expression::Tuple(vec![Expression::Number(3), Expression::Number(4)]),
+        ],
+        negated: false
+    });
+    // Parentheses around a single expression only group it
+    assert_eq!(parse_expression("(a)")?, identifier("a"));
+    assert_eq!(parse_expression("x IN ((1), 2)")?, Expression::InList {
+        expression: Box::new(identifier("x")),
+        list: vec![Expression::Number(1), Expression::Number(2)],
+        negated: false
+    });
+    
+    assert!(parse_expression("(a, b").unwrap_err().contains("Expected closing parenthesis"));
+    assert!(parse_expression("(a, )").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_scalar_subquery() -> Result<(), String> {
+    let stmt = parse_sql("SELECT name, (SELECT limit_age FROM limits) FROM users WHERE age > (SELECT limit_age FROM limits) + 1;")?;
+    let Statement::Select { columns, r#where, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    let subquery = Expression::Subquery(Box::new(parse_sql("SELECT limit_age FROM limits;")?));
+    assert_eq!(columns[1], subquery);
+    assert_eq!(r#where, Some(Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Identifier("age".to_string())),
+        operator: BinaryOperator::GreaterThan,
+        right_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(subquery),
+            operator: BinaryOperator::Plus,
+            right_operand: Box::new(Expression::Number(1))
+        })
+    }));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_subqueries() {
+    assert!(parse_sql("SELECT * FROM t WHERE id IN SELECT id FROM u;").unwrap_err().contains("Expected ( after IN"));
+    assert!(parse_sql("SELECT * FROM t WHERE id IN (SELECT id FROM u;").unwrap_err().contains("Expected ) after subquery"));
+    assert!(parse_sql("SELECT * FROM t WHERE id IN (SELECT id FROM u) x;").is_err());
+}
+
+#[test]
+fn test_exists() -> Result<(), String> {
+    let stmt = parse_sql("SELECT * FROM users u WHERE EXISTS (SELECT * FROM orders o WHERE o.user_id = u.id) AND NOT EXISTS (SELECT * FROM bans);")?;
+    let Statement::Select { r#where, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(r#where, Some(Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Exists {
+            subquery: Box::new(parse_sql("SELECT * FROM orders o WHERE o.user_id = u.id;")?),
+            negated: false
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::Exists {
+            subquery: Box::new(parse_sql("SELECT * FROM bans;")?),
+            negated: true
+        })
+    }));
+    
+    assert!(parse_sql("SELECT * FROM t WHERE EXISTS SELECT * FROM u;").unwrap_err().contains("Expected ( after EXISTS"));
+    assert!(parse_sql("SELECT * FROM t WHERE NOT EXISTS (1);").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_reject_writes() -> Result<(), String> {
+    let input = "SELECT * FROM t; CREATE TABLE t(id INT); CREATE VIEW v AS SELECT * FROM t; INSERT INTO t VALUES (1);";
+    let mut parser = Parser::new(Tokenizer::new(input))?;
+    parser.set_options(ParserOptions { reject_writes: true, ..ParserOptions::default() });
+    
+    assert!(matches!(parser.parse_statement()?, Statement::Select { .. }));
+    assert_eq!(parser.parse_statement().unwrap_err(), "CREATE TABLE statements are not allowed in read-only mode");
+    assert_eq!(parser.parse_statement().unwrap_err(), "CREATE VIEW statements are not allowed in read-only mode");
+    assert_eq!(parser.parse_statement().unwrap_err(), "INSERT statements are not allowed in read-only mode");
+    assert!(parser.is_at_end());
+    Ok(())
+}
+
+#[test]
+fn test_function_calls() -> Result<(), String> {
+    let stmt = parse_sql("SELECT COUNT(*), LOWER(name), NOW() FROM users;")?;
+    let Statement::Select { columns, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns, vec![
+        Expression::Function { name: "COUNT".to_string(), distinct: false, arguments: vec![Expression::Wildcard], over: None },
+        Expression::Function {
+            name: "LOWER".to_string(),
+            distinct: false,
+            arguments: vec![Expression::Identifier("name".to_string())],
+            over: None
+        },
+        Expression::Function { name: "NOW".to_string(), distinct: false, arguments: vec![], over: None },
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_window_functions() -> Result<(), String> {
+    let stmt = parse_sql("SELECT ROW_NUMBER() OVER (PARTITION BY x, z ORDER BY y DESC), SUM(total) OVER () FROM t;")?;
+    let Statement::Select { columns, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns, vec![
+        Expression::Function {
+            name: "ROW_NUMBER".to_string(),
+            distinct: false,
+            arguments: vec![],
+            over: Some(Box::new(WindowSpecification {
+                partition_by: vec![Expression::Identifier("x".to_string()), Expression::Identifier("z".to_string())],
+                order_by: vec![Expression::UnaryOperation {
+                    operand: Box::new(Expression::Identifier("y".to_string())),
+                    operator: UnaryOperator::Desc
+                }]
+            }))
+        },
+        Expression::Function {
+            name: "SUM".to_string(),
+            distinct: false,
+            arguments: vec![Expression::Identifier("total".to_string())],
+            over: Some(Box::new(WindowSpecification { partition_by: vec![], order_by: vec![] }))
+        },
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_function_calls() {
+    assert!(parse_sql("SELECT LOWER(name FROM t;").unwrap_err().contains("Expected ) after the arguments of LOWER"));
+    assert!(parse_sql("SELECT RANK() OVER FROM t;").unwrap_err().contains("Expected ( after OVER"));
+    assert!(parse_sql("SELECT RANK() OVER (PARTITION x) FROM t;").unwrap_err().contains("Expected BY after PARTITION"));
+    assert!(parse_sql("SELECT RANK() OVER (ORDER BY x FROM t;").unwrap_err().contains("Expected ) after window specification"));
+}
+
+#[test]
+fn test_window_words_are_not_reserved() -> Result<(), String> {
+    // OVER and PARTITION only mean something after a function call or a table
+    let stmt = parse_sql("SELECT over, partition FROM t ORDER BY over;")?;
+    let Statement::Select { columns, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns, vec![Expression::Identifier("over".to_string()), Expression::Identifier("partition".to_string())]);
+    let stmt = parse_sql("CREATE TABLE t(over INT, partition INT) ENGINE = InnoDB PARTITION BY HASH (partition);")?;
+    let Statement::CreateTable { column_list, options, partitioning: Some(partitioning), .. } = stmt else {
+        return Err("Expected CREATE TABLE statement with partitioning".to_string());
+    };
+    assert_eq!(column_list[0].column_name, "over");
+    assert_eq!(options, vec![("ENGINE".to_string(), Expression::Identifier("InnoDB".to_string()))]);
+    assert_eq!(partitioning.expressions, vec![Expression::Identifier("partition".to_string())]);
+    Ok(())
+}
+
+#[test]
+fn test_locking_clauses() -> Result<(), String> {
+    let stmt = parse_sql("SELECT * FROM accounts WHERE id = 1 ORDER BY id FOR UPDATE;")?;
+    let Statement::Select { r#where, orderby, lock, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert!(r#where.is_some());
+    assert_eq!(orderby.len(), 1);
+    assert_eq!(lock, Some(LockingClause { strength: LockStrength::Update, wait_policy: WaitPolicy::Wait }));
+    
+    for (query, expected) in [
+        ("SELECT * FROM jobs FOR UPDATE NOWAIT;", LockingClause { strength: LockStrength::Update, wait_policy: WaitPolicy::NoWait }),
+        ("SELECT * FROM jobs for update skip locked;", LockingClause { strength: LockStrength::Update, wait_policy: WaitPolicy::SkipLocked }),
+        ("SELECT * FROM jobs FOR SHARE;", LockingClause { strength: LockStrength::Share, wait_policy: WaitPolicy::Wait }),
+    ] {
+        let Statement::Select { lock, .. } = parse_sql(query)? else {
+            return Err("Expected SELECT statement".to_string());
+        };
+        assert_eq!(lock, Some(expected));
+    }
+    
+    assert!(parse_sql("SELECT * FROM jobs FOR;").unwrap_err().contains("Expected UPDATE or SHARE after FOR"));
+    assert!(parse_sql("SELECT * FROM jobs FOR UPDATE SKIP;").unwrap_err().contains("Expected LOCKED after SKIP"));
+    assert!(parse_sql("SELECT * FROM jobs FOR UPDATE WHERE id = 1;").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_locking_words_are_not_reserved() -> Result<(), String> {
+    let stmt = parse_sql("CREATE TABLE users(id INT, locked BOOL);")?;
+    let Statement::CreateTable { column_list, .. } = stmt else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[1].column_name, "locked");
+    let stmt = parse_sql("SELECT share, skip, nowait FROM t WHERE NOT locked FOR SHARE SKIP LOCKED;")?;
+    let Statement::Select { columns, lock, .. } = stmt else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns[1], Expression::Identifier("skip".to_string()));
+    assert_eq!(lock, Some(LockingClause { strength: LockStrength::Share, wait_policy: WaitPolicy::SkipLocked }));
+    Ok(())
+}
+
+#[test]
+fn test_locking_queries_are_writes() -> Result<(), String> {
+    let input = "SELECT * FROM t FOR SHARE;";
+    let mut parser = Parser::new(Tokenizer::new(input))?;
+    parser.set_options(ParserOptions { reject_writes: true, ..ParserOptions::default() });
+    assert_eq!(parser.parse_statement().unwrap_err(), "SELECT FOR SHARE statements are not allowed in read-only mode");
+    Ok(())
+}
+
+#[test]
+fn test_select_without_from() -> Result<(), String> {
+    assert_eq!(parse_sql("SELECT 1 + 2;")?, Statement::Select {
+        with: vec![],
+        columns: vec![Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Number(1)),
+            operator: BinaryOperator::Plus,
+            right_operand: Box::new(Expression::Number(2))
+        }],
+        from: vec![],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    });
+    
+    let Statement::Select { columns, from, .. } = parse_sql("SELECT 'hello', (SELECT 1) FROM t;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns.len(), 2);
+    assert_eq!(from.len(), 1);
+    
+    assert!(parse_sql("SELECT 1 WHERE TRUE;").unwrap_err().contains("Expected FROM clause"));
+    Ok(())
+}
+
+#[test]
+fn test_table_options() -> Result<(), String> {
+    let Statement::CreateTable { options, .. } = parse_sql("CREATE TABLE t(id INT) ENGINE=InnoDB AUTO_INCREMENT=5, DEFAULT CHARSET=utf8 COMMENT='Users';")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(options, vec![
+        ("ENGINE".to_string(), Expression::Identifier("InnoDB".to_string())),
+        ("AUTO_INCREMENT".to_string(), Expression::Number(5)),
+        ("DEFAULT CHARSET".to_string(), Expression::Identifier("utf8".to_string())),
+        ("COMMENT".to_string(), Expression::String("Users".to_string())),
+    ]);
+    
+    let Statement::CreateTable { options, .. } = parse_sql("CREATE TABLE t(id INT) WITH (fillfactor = 70, autovacuum_enabled = FALSE);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(options, vec![
+        ("fillfactor".to_string(), Expression::Number(70)),
+        ("autovacuum_enabled".to_string(), Expression::Bool(false)),
+    ]);
+    
+    assert!(parse_sql("CREATE TABLE t(id INT) ENGINE InnoDB;").unwrap_err().contains("Expected = after ENGINE"));
+    assert!(parse_sql("CREATE TABLE t(id INT) WITH (fillfactor = 70;").unwrap_err().contains("Expected ) after table options"));
+    assert!(parse_sql("CREATE TABLE t(id INT) WITH ();").unwrap_err().contains("Expected table option name"));
+    Ok(())
+}
+
+#[test]
+fn test_partitioning() -> Result<(), String> {
+    let Statement::CreateTable { options, partitioning, .. } = parse_sql("CREATE TABLE events(id INT, created_at INT) PARTITION BY RANGE (created_at) WITH (fillfactor = 70);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(partitioning, Some(Partitioning {
+        method: PartitionMethod::Range,
+        expressions: vec![Expression::Identifier("created_at".to_string())]
+    }));
+    assert_eq!(options.len(), 1);
+    
+    // MySQL writes the table options first
+    let Statement::CreateTable { options, partitioning, .. } = parse_sql("CREATE TABLE t(id INT, region INT) ENGINE=InnoDB partition by hash(id, region + 1);")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(options.len(), 1);
+    assert_eq!(partitioning.map(|partitioning| (partitioning.method, partitioning.expressions.len())), Some((PartitionMethod::Hash, 2)));
+    
+    assert!(parse_sql("CREATE TABLE t(id INT) PARTITION BY LIST (id);").is_ok());
+    assert!(parse_sql("CREATE TABLE t(id INT) PARTITION BY KEY (id);").is_ok());
+    assert!(parse_sql("CREATE TABLE t(id INT) PARTITION BY SIZE (id);").unwrap_err().contains("Expected RANGE, LIST, HASH or KEY after PARTITION BY"));
+    assert!(parse_sql("CREATE TABLE t(id INT) PARTITION BY HASH (id;").unwrap_err().contains("Expected ) after the partition key"));
+    Ok(())
+}
+
+#[test]
+fn test_limit() -> Result<(), String> {
+    let Statement::Select { orderby, limit, lock, .. } = parse_sql("SELECT * FROM jobs ORDER BY id LIMIT 10 FOR UPDATE SKIP LOCKED;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(orderby.len(), 1);
+    assert_eq!(limit, Some(Expression::Number(10)));
+    assert!(lock.is_some());
+    
+    assert!(parse_sql("SELECT * FROM t LIMIT;").is_err());
+    // TOP is only understood in the SQL Server dialect
+    assert!(parse_sql("SELECT TOP 10 * FROM t;").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_comments() -> Result<(), String> {
+    assert_eq!(parse_sql("COMMENT ON TABLE users IS 'Registered users';")?, Statement::Comment {
+        target: CommentTarget::Table(ObjectName::new("users")),
+        text: Some("Registered users".to_string())
+    });
+    assert_eq!(parse_sql("comment on column users.email is NULL;")?, Statement::Comment {
+        target: CommentTarget::Column { table: ObjectName::new("users"), column: "email".to_string() },
+        text: None
+    });
+    assert!(matches!(parse_sql("COMMENT ON VIEW adults IS 'x';")?, Statement::Comment { target: CommentTarget::View(_), .. }));
+    
+    let Statement::CreateTable { column_list, .. } = parse_sql("CREATE TABLE t(email VARCHAR(255) NOT NULL COMMENT 'primary email');")? else {
+        return Err("Expected CREATE TABLE statement".to_string());
+    };
+    assert_eq!(column_list[0].constraints, vec![Constraint::NotNull, Constraint::Comment("primary email".to_string())]);
+    
+    assert!(parse_sql("COMMENT ON INDEX i IS 'x';").unwrap_err().contains("Expected TABLE, VIEW or COLUMN after COMMENT ON"));
+    assert!(parse_sql("COMMENT ON COLUMN email IS 'x';").unwrap_err().contains("Expected . after email"));
+    assert!(parse_sql("COMMENT ON TABLE users IS 5;").unwrap_err().contains("Expected string or NULL"));
+    assert!(parse_sql("CREATE TABLE t(email INT COMMENT);").unwrap_err().contains("Expected string after COMMENT"));
+    Ok(())
+}
+
+#[test]
+fn test_qualified_object_names() -> Result<(), String> {
+    let qualified = |parts: &[&str]| ObjectName(parts.iter().map(|part| part.to_string()).collect());
+    let Statement::Select { from, .. } = parse_sql("SELECT u.id FROM public.users u JOIN mydb.sales.orders ON u.id = orders.user_id;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    let TableReference::Join { left, right, .. } = &from[0] else {
+        return Err("Expected a join".to_string());
+    };
+    assert_eq!(**left, TableReference::Table { name: qualified(&["public", "users"]), alias: Some("u".to_string()) });
+    assert_eq!(**right, TableReference::Table { name: qualified(&["mydb", "sales", "orders"]), alias: None });
+    assert_eq!(right.visible_name(), Some("orders"));
+    
+    assert!(matches!(parse_sql("CREATE TABLE public.users(id INT);")?, Statement::CreateTable { table_name, .. } if table_name == qualified(&["public", "users"])));
+    assert!(matches!(parse_sql("CREATE VIEW reports.adults AS SELECT 1;")?, Statement::CreateView { name, .. } if name.to_string() == "reports.adults"));
+    assert!(matches!(parse_sql("INSERT INTO public.users VALUES (1);")?, Statement::Insert { table_name, .. } if table_name.is_qualified()));
+    assert!(matches!(parse_sql("DROP TABLE public.users;")?, Statement::DropTable { table_name, .. } if table_name.base_name() == "users"));
+    assert_eq!(parse_sql("COMMENT ON COLUMN public.users.email IS 'x';")?, Statement::Comment {
+        target: CommentTarget::Column { table: qualified(&["public", "users"]), column: "email".to_string() },
+        text: Some("x".to_string())
+    });
+    
+    assert!(parse_sql("SELECT * FROM public.;").unwrap_err().contains("Expected table name after ."));
+    assert!(parse_sql("CREATE TABLE public.(id INT);").unwrap_err().contains("Expected table name after ."));
+    Ok(())
+}
+
+#[test]
+fn test_qualified_wildcard() -> Result<(), String> {
+    let Statement::Select { columns, .. } = parse_sql("SELECT u.*, o.id FROM users u JOIN orders o ON u.id = o.user_id;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns, vec![
+        Expression::QualifiedWildcard("u".to_string()),
+        Expression::CompoundIdentifier(vec!["o".to_string(), "id".to_string()]),
+    ]);
+    assert!(parse_sql("SELECT a.b.* FROM t;").unwrap_err().contains("Expected column name after ."));
+    assert!(parse_sql("SELECT u.*;").unwrap_err().contains("Expected FROM clause"));
+    Ok(())
+}
+
+#[test]
+fn test_insert() -> Result<(), String> {
+    assert_eq!(parse_sql("INSERT INTO users (id, name) VALUES (1, 'Ann'), (2, 'Bob');")?, Statement::Insert {
+        table_name: ObjectName::new("users"),
+        columns: vec!["id".to_string(), "name".to_string()],
+        source: InsertSource::Values(vec![
+            vec![Expression::Number(1), Expression::String("Ann".to_string())],
+            vec![Expression::Number(2), Expression::String("Bob".to_string())],
+        ]),
+        returning: vec![]
+    });
+    assert!(matches!(
+        parse_sql("INSERT INTO users VALUES (1, NULL);")?,
+        Statement::Insert { source: InsertSource::Values(rows), .. } if rows == vec![vec![Expression::Number(1), Expression::Null]]
+    ));
+    
+    let Statement::Insert { columns, source: InsertSource::Query(query), .. } = parse_sql("INSERT INTO archive SELECT * FROM orders WHERE total > 100;")? else {
+        return Err("Expected INSERT ... SELECT statement".to_string());
+    };
+    assert!(columns.is_empty());
+    assert!(matches!(*query, Statement::Select { r#where: Some(_), .. }));
+    assert!(matches!(
+        parse_sql("INSERT INTO ids WITH recent AS (SELECT id FROM orders) SELECT id FROM recent;")?,
+        Statement::Insert { source: InsertSource::Query(_), .. }
+    ));
+    
+    assert!(parse_sql("INSERT users VALUES (1);").unwrap_err().contains("Expected INTO after INSERT"));
+    assert!(parse_sql("INSERT INTO users (id VALUES (1);").unwrap_err().contains("Expected ) after the columns of INSERT"));
+    assert!(parse_sql("INSERT INTO users VALUES 1;").unwrap_err().contains("Expected ( after VALUES"));
+    assert!(parse_sql("INSERT INTO users VALUES (1, 2;").unwrap_err().contains("Expected ) after the values of a row"));
+    assert!(parse_sql("INSERT INTO users (id);").unwrap_err().contains("Expected VALUES or SELECT after users"));
+    assert_eq!(parse_sql("INSERT INTO users VALUES (1)").unwrap_err(), "Expected ',', RETURNING or ';', found end of input");
+    Ok(())
+}
+
+#[test]
+fn test_insert_returning() -> Result<(), String> {
+    let Statement::Insert { returning, .. } = parse_sql("INSERT INTO users (name) VALUES ('Ann') RETURNING id, price * 2 AS total;")? else {
+        return Err("Expected INSERT statement".to_string());
+    };
+    assert_eq!(returning, vec![
+        ReturningItem { expression: Expression::Identifier("id".to_string()), alias: None },
+        ReturningItem { expression: parse_expression("price * 2")?, alias: Some("total".to_string()) },
+    ]);
+    
+    // The last table of a query is not aliased as RETURNING, which is still
+    // a name everywhere else
+    let Statement::Insert { source: InsertSource::Query(query), returning, .. } = parse_sql("INSERT INTO archive SELECT * FROM orders RETURNING *;")? else {
+        return Err("Expected INSERT ... SELECT statement".to_string());
+    };
+    assert!(matches!(*query, Statement::Select { ref from, .. } if from[0] == TableReference::Table { name: ObjectName::new("orders"), alias: None }));
+    assert_eq!(returning, vec![ReturningItem { expression: Expression::Wildcard, alias: None }]);
+    assert!(matches!(parse_sql("SELECT returning FROM t AS returning;")?, Statement::Select { .. }));
+    
+    assert!(parse_sql("INSERT INTO t VALUES (1) RETURNING;").is_err());
+    assert_eq!(parse_sql("INSERT INTO t VALUES (1) RETURNING id AS;").unwrap_err(), "Expected alias name after AS");
+    Ok(())
+}
+
+#[test]
+fn test_if_not_exists() -> Result<(), String> {
+    assert!(matches!(parse_sql("CREATE TABLE IF NOT EXISTS users(id INT);")?, Statement::CreateTable { if_not_exists: true, .. }));
+    assert!(matches!(parse_sql("create view if not exists ids as select id from users;")?, Statement::CreateView { if_not_exists: true, .. }));
+    assert!(matches!(parse_sql("CREATE TABLE users(id INT);")?, Statement::CreateTable { if_not_exists: false, .. }));
+    assert!(parse_sql("CREATE TABLE IF EXISTS users(id INT);").unwrap_err().contains("Expected NOT EXISTS after CREATE TABLE IF"));
+    assert!(parse_sql("CREATE VIEW IF NOT ids AS SELECT 1;").unwrap_err().contains("Expected NOT EXISTS after CREATE VIEW IF"));
+    Ok(())
+}
+
+#[test]
+fn test_create_modifiers() -> Result<(), String> {
+    assert!(matches!(parse_sql("CREATE TEMPORARY TABLE t(id INT);")?, Statement::CreateTable { temporary: true, .. }));
+    assert!(matches!(parse_sql("create temp table if not exists t(id INT);")?, Statement::CreateTable { temporary: true, if_not_exists: true, .. }));
+    assert!(matches!(parse_sql("CREATE TABLE t(id INT);")?, Statement::CreateTable { temporary: false, .. }));
+    assert!(matches!(parse_sql("CREATE OR REPLACE VIEW v AS SELECT 1;")?, Statement::CreateView { or_replace: true, .. }));
+    assert!(matches!(parse_sql("CREATE VIEW v AS SELECT 1;")?, Statement::CreateView { or_replace: false, .. }));
+    
+    assert!(parse_sql("CREATE OR REPLACE TABLE t(id INT);").unwrap_err().contains("Expected VIEW after CREATE OR REPLACE"));
+    assert!(parse_sql("CREATE TEMPORARY VIEW v AS SELECT 1;").unwrap_err().contains("Expected TABLE after CREATE TEMPORARY"));
+    assert!(parse_sql("CREATE OR VIEW v AS SELECT 1;").unwrap_err().contains("Expected REPLACE after CREATE OR"));
+    assert!(parse_sql("CREATE OR REPLACE VIEW IF NOT EXISTS v AS SELECT 1;")
+        .unwrap_err()
+        .contains("A view can not be created with both OR REPLACE and IF NOT EXISTS"));
+    // Outside of the modifiers, TEMP is an ordinary name
+    assert!(matches!(parse_sql("CREATE TABLE temp(id INT);")?, Statement::CreateTable { temporary: false, .. }));
+    Ok(())
+}
+
+#[test]
+fn test_drop_table() -> Result<(), String> {
+    assert_eq!(parse_sql("DROP TABLE IF EXISTS users;")?, Statement::DropTable { table_name: ObjectName::new("users"), if_exists: true });
+    assert_eq!(parse_sql("drop table users;")?, Statement::DropTable { table_name: ObjectName::new("users"), if_exists: false });
+    assert!(parse_sql("DROP VIEW users;").unwrap_err().contains("Expected TABLE after DROP"));
+    assert!(parse_sql("DROP TABLE IF users;").unwrap_err().contains("Expected EXISTS after DROP TABLE IF"));
+    assert_eq!(parse_sql("DROP TABLE users").unwrap_err(), "Expected ';', found end of input");
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_arguments() -> Result<(), String> {
+    let Statement::Select { columns, .. } = parse_sql("SELECT COUNT(*), COUNT(DISTINCT name), SUM(DISTINCT a + b) FROM users;")? else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    assert_eq!(columns, vec![
+        Expression::Function { name: "COUNT".to_string(), distinct: false, arguments: vec![Expression::Wildcard], over: None },
+        Expression::Function {
+            name: "COUNT".to_string(),
+            distinct: true,
+            arguments: vec![Expression::Identifier("name".to_string())],
+            over: None
+        },
+        Expression::Function {
+            name: "SUM".to_string(),
+            distinct: true,
+            arguments: vec![Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("a".to_string())),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Identifier("b".to_string()))
+            }],
+            over: None
+        },
+    ]);
+    assert!(parse_sql("SELECT COUNT(DISTINCT *) FROM users;").unwrap_err().contains("COUNT can not count DISTINCT *"));
+    assert!(parse_sql("SELECT COUNT(DISTINCT) FROM users;").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_like() -> Result<(), String> {
+    assert_eq!(parse_expression("name LIKE 'A%'")?, Expression::Like {
+        expression: Box::new(Expression::Identifier("name".to_string())),
+        pattern: Box::new(Expression::String("A%".to_string())),
+        negated: false,
+        escape: None
+    });
+    assert_eq!(parse_expression("code NOT LIKE '100\\%' ESCAPE '\\' AND active = TRUE")?, Expression::BinaryOperation {
+        left_operand: Box::new(Expression::Like {
+            expression: Box::new(Expression::Identifier("code".to_string())),
+            pattern: Box::new(Expression::String("100\\%".to_string())),
+            negated: true,
+            escape: Some('\\')
+        }),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::BinaryOperation {
+            left_operand: Box::new(Expression::Identifier("active".to_string())),
+            operator: BinaryOperator::Equal,
+            right_operand: Box::new(Expression::Bool(true))
+        })
+    });
+    // The pattern can be computed, and binds tighter than the comparisons around it
+    assert!(matches!(parse_expression("a LIKE b + 'x%'")?, Expression::Like { pattern, .. } if matches!(*pattern, Expression::BinaryOperation { .. })));
+    
+    assert!(parse_expression("name NOT 'A%'").unwrap_err().contains("Expected LIKE, IN or SIMILAR TO after NOT"));
+    assert!(parse_expression("name LIKE 'A%' ESCAPE 'ab'").unwrap_err().contains("Expected a string of a single character after ESCAPE"));
+    assert!(parse_expression("name LIKE 'A%' ESCAPE 5").unwrap_err().contains("Expected a string of a single character after ESCAPE"));
+    Ok(())
+}
+
+#[test]
+fn test_nesting_depth_limit() -> Result<(), String> {
+    let error = "Expressions and queries can not be nested more than 64 levels deep";
+    assert_eq!(parse_expression(&"(".repeat(100_000)).unwrap_err(), error);
+    assert_eq!(parse_expression(&"NOT ".repeat(100_000)).unwrap_err(), error);
+    let subqueries = format!("SELECT * FROM {}t{};", "(SELECT * FROM ".repeat(100_000), ") AS t".repeat(100_000));
+    assert_eq!(parse_sql(&subqueries).unwrap_err(), error);
+    
+    // Up to the limit, the nesting is parsed
+    let nested = format!("{}1{}", "(".repeat(63), ")".repeat(63));
+    assert_eq!(parse_expression(&nested)?, Expression::Number(1));
+    let mut parser = Parser::new(Tokenizer::new(&nested))?;
+    parser.set_options(ParserOptions { max_depth: 10, ..ParserOptions::default() });
+    assert_eq!(parser.parse_expression(0).unwrap_err(), "Expressions and queries can not be nested more than 10 levels deep");
+    Ok(())
+}
+
+#[test]
+fn test_long_expressions() -> Result<(), String> {
+    // Chains of operators of any length parse in the stack of a test thread
+    let chain = format!("1{}", " + 2 * 3 - 4".repeat(7_000));
+    assert!(matches!(parse_expression(&chain)?, Expression::BinaryOperation { operator: BinaryOperator::Minus, .. }));
+    
+    // So does nesting in operators and parentheses, as deep as the limit allows
+    let parse_deep = |input: &str| -> Result<Expression, String> {
+        let mut parser = Parser::new(Tokenizer::new(input))?;
+        parser.set_options(ParserOptions { max_depth: 20_000, ..ParserOptions::default() });
+        parser.parse_expression(0)
+    };
+    let nested = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+    assert_eq!(parse_deep(&nested)?, Expression::Number(1));
+    let negations = format!("{}TRUE", "NOT ".repeat(10_000));
+    assert!(matches!(parse_deep(&negations)?, Expression::UnaryOperation { operator: UnaryOperator::Not, .. }));
+    let tuples = format!("{}1{}", "(1, ".repeat(5_000), ")".repeat(5_000));
+    assert!(matches!(parse_deep(&tuples)?, Expression::Tuple(elements) if elements.len() == 2));
+    assert_eq!(parse_deep(&format!("{}1", "(".repeat(10_000))).unwrap_err(), "Expected closing parenthesis");
+    
+    // The operators keep their precedence and grouping
+    assert_eq!(parse_expression("-(1 + 2) * 3")?, parse_expression("(-(1 + 2)) * 3")?);
+    assert_eq!(parse_expression("NOT a = 1 AND b")?, parse_expression("((NOT a) = 1) AND b")?);
+    assert_eq!(parse_expression("~1 + -2 * 3")?, parse_expression("(~1) + ((-2) * 3)")?);
+    Ok(())
+}
+
+#[test]
+fn test_optional_final_semicolon() -> Result<(), String> {
+    let parse = |input: &str| -> Result<Vec<Statement>, String> {
+        let mut parser = Parser::new(Tokenizer::new(input))?;
+        parser.set_options(ParserOptions { optional_final_semicolon: true, ..ParserOptions::default() });
+        parser.parse_statements()
+    };
+    assert_eq!(parse("SELECT a FROM t")?, build_statements("SELECT a FROM t;")?);
+    assert_eq!(parse("DROP TABLE a; CREATE TABLE b(id INT)")?, build_statements("DROP TABLE a; CREATE TABLE b(id INT);")?);
+    // Only the last statement can go without it
+    assert_eq!(parse("SELECT a FROM t SELECT b FROM t").unwrap_err(), "Expected AS, alias, JOIN, INNER, LEFT, RIGHT, FULL, ',', WHERE, ORDER, LIMIT, FOR or ';', found Keyword(Select)");
+    // Strict by default
+    assert!(build_statements("SELECT a FROM t").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_next_statement() -> Result<(), String> {
+    let input = "SELECT 1; VACUUM users; SELECT 2;";
+    let mut parser = Parser::new(Tokenizer::new(input))?;
+    assert_eq!(parser.parse_next_statement(), Some(build_statement("SELECT 1;")));
+    
+    // The caller handles the statement the parser does not know
+    assert!(input[parser.offset()..].starts_with("VACUUM"));
+    assert!(parser.parse_next_statement().is_some_and(|statement| statement.is_err()));
+    parser.skip_statement();
+    assert_eq!(&input[parser.offset()..], "SELECT 2;");
+    
+    assert_eq!(parser.parse_next_statement(), Some(build_statement("SELECT 2;")));
+    assert_eq!(parser.parse_next_statement(), None);
+    
+    // The rest of the input can also be taken over as tokens
+    let mut parser = Parser::new(Tokenizer::new("SELECT 1; GRANT ALL;"))?;
+    parser.parse_next_statement().transpose()?;
+    let rest = parser.into_tokens().collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(rest, vec![
+        Token::Identifier("GRANT".to_string()),
+        Token::Identifier("ALL".to_string()),
+        Token::Semicolon,
+        Token::Eof,
+    ]);
+    Ok(())
+}
+
+// Parses `VACUUM [table]` into the name of the table, and rejects `COPY`
+struct Maintenance;
+
+impl StatementHandler for Maintenance {
+    fn handles(&self, keyword: &str) -> bool {
+        keyword == "VACUUM" || keyword == "COPY"
+    }
+
+    fn parse(&mut self, keyword: &str, tokens: &[(Token, Span)]) -> Result<String, String> {
+        match (keyword, tokens) {
+            ("VACUUM", []) => Ok(String::new()),
+            ("VACUUM", [(Token::Identifier(table), _)]) => Ok(table.clone()),
+            _ => Err(format!("Can not parse {} with {} tokens", keyword, tokens.len())),
+        }
+    }
+}
+
+#[test]
+fn test_statement_handler() -> Result<(), String> {
+    let mut handler = Maintenance;
+    let mut parser = Parser::new(Tokenizer::new("vacuum users; SELECT 1; VACUUM; COPY users FROM stdin;"))?;
+    parser.set_statement_handler(&mut handler);
+    
+    assert_eq!(parser.parse_next_statement(), Some(Ok(Statement::Custom {
+        keyword: "VACUUM".to_string(),
+        payload: "users".to_string(),
+    })));
+    assert_eq!(parser.parse_next_statement(), Some(build_statement("SELECT 1;")));
+    assert_eq!(parser.parse_next_statement(), Some(Ok(Statement::Custom {
+        keyword: "VACUUM".to_string(),
+        payload: String::new(),
+    })));
+    assert_eq!(parser.parse_next_statement(), Some(Err("Can not parse COPY with 3 tokens".to_string())));
+    
+    // Without a handler the words are not statements
+    assert!(build_statement("VACUUM users;").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_statement_handler_tokens() -> Result<(), String> {
+    struct Recorder(Vec<(Token, Span)>);
+    
+    impl StatementHandler for Recorder {
+        fn handles(&self, keyword: &str) -> bool {
+            keyword == "ANALYZE"
+        }
+
+        fn parse(&mut self, _: &str, tokens: &[(Token, Span)]) -> Result<String, String> {
+            self.0 = tokens.to_vec();
+            Ok(String::new())
+        }
+    }
+    
+    let mut recorder = Recorder(Vec::new());
+    let mut parser = Parser::new(Tokenizer::new("ANALYZE users (id)"))?;
+    parser.set_statement_handler(&mut recorder);
+    parser.set_options(ParserOptions { optional_final_semicolon: true, ..ParserOptions::default() });
+    assert!(parser.parse_next_statement().is_some_and(|statement| statement.is_ok()));
+    assert_eq!(parser.parse_next_statement(), None);
+    drop(parser);
+    
+    assert_eq!(recorder.0, vec![
+        (Token::Identifier("users".to_string()), Span { start: 8, end: 13 }),
+        (Token::LeftParentheses, Span { start: 14, end: 15 }),
+        (Token::Identifier("id".to_string()), Span { start: 15, end: 17 }),
+        (Token::RightParentheses, Span { start: 17, end: 18 }),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_expected_alternatives() {
+    // Every clause that could have followed is listed
+    assert_eq!(build_statement("SELECT 1 2;").unwrap_err(), "Expected operator, ',', FROM, ORDER, LIMIT, FOR or ';', found Number(2)");
+    assert_eq!(
+        build_statement("SELECT a FROM t JOIN u ON t.id = u.id x;").unwrap_err(),
+        "Expected operator, JOIN, INNER, LEFT, RIGHT, FULL, ',', WHERE, ORDER, LIMIT, FOR or ';', found Identifier(\"x\")",
+    );
+    assert_eq!(build_statement("(SELECT 1);").unwrap_err(), "Expected SELECT, WITH, CREATE, INSERT, DROP or COMMENT, found LeftParentheses");
+    assert_eq!(build_statement("SELECT * FROM (1) AS t;").unwrap_err(), "Expected SELECT, found Number(1)");
+    
+    // The typo of a keyword that could have followed is pointed out
+    assert_eq!(
+        build_statement("SELECT a FORM t;").unwrap_err(),
+        "Expected operator, ',', FROM, ORDER, LIMIT, FOR or ';', found Identifier(\"FORM\"). Did you mean FROM?",
+    );
+}
@@ -0,0 +1,52 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement,
+    Expression, Message, Node, Statement
+};
+
+fn kinds(statement: &Statement, path: &str) -> Vec<&'static str> {
+    statement.query(path).unwrap().iter().map(Node::kind).collect()
+}
+
+fn identifiers<'a>(nodes: &[Node<'a>]) -> Vec<&'a str> {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Expression(Expression::Identifier(name)) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_query_fields_and_kinds() {
+    let statement = build_statement("SELECT name, COUNT(*) FROM users WHERE age > 18 AND city = 'Vilnius' ORDER BY name;").unwrap();
+    assert_eq!(kinds(&statement, "select.where"), vec!["BinaryOperation"]);
+    assert_eq!(identifiers(&statement.query("select.where.**.Identifier").unwrap()), vec!["age", "city"]);
+    assert_eq!(kinds(&statement, "SELECT.COLUMNS.*"), vec!["Identifier", "Function"]);
+    assert_eq!(kinds(&statement, "select.from"), vec!["Table"]);
+    assert_eq!(kinds(&statement, "**.Wildcard"), vec!["Wildcard"]);
+    assert_eq!(kinds(&statement, "select"), vec!["Select"]);
+    assert_eq!(kinds(&build_statement("SELECT NULL, a FROM t;").unwrap(), "select.columns"), vec!["Null", "Identifier"]);
+    assert!(statement.query("createtable.column_list").unwrap().is_empty());
+}
+
+#[test]
+fn test_query_nested_statements() {
+    let statement = build_statement(
+        "CREATE VIEW v AS WITH recent AS (SELECT id FROM orders) SELECT x.id FROM recent JOIN (SELECT id FROM users) AS x ON recent.id = x.id WHERE EXISTS (SELECT * FROM t WHERE a = 1);",
+    ).unwrap();
+    assert_eq!(kinds(&statement, "**.Select"), vec!["Select", "Select", "Select", "Select"]);
+    assert_eq!(kinds(&statement, "createview.query.select.from.join.on"), vec!["BinaryOperation"]);
+    assert_eq!(identifiers(&statement.query("**.subquery.**.Identifier").unwrap()), vec!["id", "a"]);
+    
+    let statement = build_statement("CREATE TABLE t(age INT CHECK(age >= 18));").unwrap();
+    assert_eq!(identifiers(&statement.query("createtable.column_list.*.check.**").unwrap()), vec!["age"]);
+}
+
+#[test]
+fn test_invalid_paths() {
+    let statement = build_statement("SELECT 1;").unwrap();
+    for path in ["", "select..where", "select."] {
+        assert_eq!(statement.query(path).unwrap_err(), Message::InvalidPath { path: path.to_string() });
+    }
+}
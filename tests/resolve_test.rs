@@ -0,0 +1,337 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement, resolve,
+    Catalog, ColumnBinding, Constraint, Message, Statement
+};
+
+fn catalog() -> Catalog {
+    let statements: Vec<Statement> = [
+        "CREATE TABLE users(id INT PRIMARY KEY, name VARCHAR(255), age INT);",
+        "CREATE TABLE orders(id INT PRIMARY KEY, user_id INT, total INT CHECK(total >= 0));",
+    ]
+    .iter()
+    .map(|query| build_statement(query).unwrap())
+    .collect();
+    Catalog::from_statements(&statements).unwrap()
+}
+
+fn binding(table: &str, column: &str) -> ColumnBinding {
+    ColumnBinding { table: table.to_string(), column: column.to_string() }
+}
+
+#[test]
+fn test_resolve_joined_columns() {
+    let statement = build_statement("SELECT name, total FROM users JOIN orders ON age = user_id WHERE total > 10 ORDER BY name;").unwrap();
+    let resolved = resolve(statement.clone(), &catalog()).unwrap();
+    assert_eq!(resolved.statement, statement);
+    assert_eq!(resolved.bindings, vec![
+        binding("users", "name"),
+        binding("orders", "total"),
+        binding("users", "age"),
+        binding("orders", "user_id"),
+        binding("orders", "total"),
+        binding("users", "name"),
+    ]);
+}
+
+#[test]
+fn test_resolve_aliased_tables() {
+    let statement = build_statement("SELECT name FROM users AS u JOIN orders o ON age = total;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("u", "name"), binding("u", "age"), binding("o", "total")]);
+    
+    // The same table may appear twice in FROM under different names
+    let statement = build_statement("SELECT total FROM users JOIN users AS parents ON total = total JOIN orders ON id = user_id;").unwrap();
+    assert!(matches!(resolve(statement, &catalog()).unwrap_err(), Message::AmbiguousColumn { .. }));
+}
+
+#[test]
+fn test_resolve_qualified_columns() {
+    let statement = build_statement("SELECT u.id, o.id FROM users u JOIN orders o ON u.id = o.user_id;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![
+        binding("u", "id"),
+        binding("o", "id"),
+        binding("u", "id"),
+        binding("o", "user_id"),
+    ]);
+    
+    let statement = build_statement("SELECT users.id FROM users u;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownTable { name: "users".to_string() });
+    let statement = build_statement("SELECT u.total FROM users u;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err().to_string(), "Unknown column u.total");
+}
+
+#[test]
+fn test_resolve_comma_separated_tables() {
+    let statement = build_statement("SELECT name FROM users, orders WHERE users.id = orders.user_id;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("users", "name"), binding("users", "id"), binding("orders", "user_id")]);
+    
+    let statement = build_statement("SELECT name FROM users, users;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::DuplicateTableInFrom { name: "users".to_string() });
+}
+
+#[test]
+fn test_resolve_derived_tables() {
+    let statement = build_statement("SELECT adults.name FROM (SELECT name, age FROM users WHERE age >= 18) AS adults;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![
+        binding("users", "name"),
+        binding("users", "age"),
+        binding("users", "age"),
+        binding("adults", "name"),
+    ]);
+    
+    // Only the columns the derived table selects are visible outside of it
+    let statement = build_statement("SELECT age FROM (SELECT name FROM users) AS names;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownColumn { name: "age".to_string() });
+}
+
+#[test]
+fn test_resolve_subqueries() {
+    let statement = build_statement("SELECT name FROM users WHERE id IN (SELECT user_id FROM orders WHERE total > age);").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    // The subquery can refer to the columns of the outer query
+    assert_eq!(resolved.bindings, vec![
+        binding("users", "name"),
+        binding("users", "id"),
+        binding("orders", "user_id"),
+        binding("orders", "total"),
+        binding("users", "age"),
+    ]);
+    
+    // The columns of the subquery shadow the columns of the outer query
+    let statement = build_statement("SELECT (SELECT id FROM orders WHERE users.id = user_id) FROM users;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("orders", "id"), binding("users", "id"), binding("orders", "user_id")]);
+    
+    // But the outer query can not see the tables of the subquery
+    let statement = build_statement("SELECT total FROM users WHERE id IN (SELECT user_id FROM orders);").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownColumn { name: "total".to_string() });
+    
+    // A subquery without FROM only sees the outer query
+    let statement = build_statement("SELECT (SELECT age + 1) FROM users;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap().bindings, vec![binding("users", "age")]);
+    let statement = build_statement("SELECT age + 1;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownColumn { name: "age".to_string() });
+}
+
+#[test]
+fn test_resolve_correlated_exists() {
+    let statement = build_statement("SELECT name FROM users u WHERE NOT EXISTS (SELECT * FROM orders o WHERE o.user_id = u.id);").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("u", "name"), binding("o", "user_id"), binding("u", "id")]);
+}
+
+#[test]
+fn test_resolve_window_functions() {
+    let statement = build_statement("SELECT RANK() OVER (PARTITION BY user_id ORDER BY total DESC), COUNT(*) FROM orders;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("orders", "user_id"), binding("orders", "total")]);
+    
+    let statement = build_statement("SELECT SUM(price) FROM orders;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownColumn { name: "price".to_string() });
+}
+
+#[test]
+fn test_ambiguous_column() {
+    let statement = build_statement("SELECT id FROM users JOIN orders ON id = user_id;").unwrap();
+    let error = resolve(statement, &catalog()).unwrap_err();
+    assert_eq!(error, Message::AmbiguousColumn {
+        name: "id".to_string(),
+        tables: vec!["users".to_string(), "orders".to_string()]
+    });
+    assert_eq!(error.to_string(), "Ambiguous column reference id: present in users, orders");
+}
+
+#[test]
+fn test_unknown_table_and_column() {
+    let statement = build_statement("SELECT * FROM customers;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err().to_string(), "Unknown table customers");
+    
+    let statement = build_statement("SELECT email FROM users;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err().to_string(), "Unknown column email");
+}
+
+#[test]
+fn test_resolve_check_constraints() {
+    let statement = build_statement("CREATE TABLE items(price INT, cost INT CHECK(cost < price));").unwrap();
+    let resolved = resolve(statement, &Catalog::new()).unwrap();
+    assert_eq!(resolved.bindings, vec![binding("items", "cost"), binding("items", "price")]);
+    
+    let statement = build_statement("CREATE TABLE items(price INT CHECK(weight > 0));").unwrap();
+    assert!(resolve(statement, &Catalog::new()).is_err());
+    
+    let statement = build_statement("CREATE TABLE items(id INT, price INT) PARTITION BY HASH (id);").unwrap();
+    assert_eq!(resolve(statement, &Catalog::new()).unwrap().bindings, vec![binding("items", "id")]);
+    let statement = build_statement("CREATE TABLE items(id INT) PARTITION BY RANGE (created_at);").unwrap();
+    assert!(resolve(statement, &Catalog::new()).is_err());
+}
+
+#[test]
+fn test_resolve_table_constraints() {
+    let statement = build_statement("CREATE TABLE lines(order_id INT, line INT, PRIMARY KEY (order_id, line));").unwrap();
+    assert!(resolve(statement, &Catalog::new()).is_ok());
+    
+    let statement = build_statement("CREATE TABLE lines(order_id INT, UNIQUE (order_id, line));").unwrap();
+    assert_eq!(resolve(statement, &Catalog::new()).unwrap_err(), Message::UnknownColumn { name: "line".to_string() });
+    
+    let statement = build_statement("CREATE TABLE items(price INT, cost INT, CHECK (price > cost));").unwrap();
+    assert_eq!(resolve(statement, &Catalog::new()).unwrap().bindings, vec![binding("items", "price"), binding("items", "cost")]);
+    let statement = build_statement("CREATE TABLE items(price INT, CHECK (price > cost));").unwrap();
+    assert_eq!(resolve(statement, &Catalog::new()).unwrap_err(), Message::UnknownColumn { name: "cost".to_string() });
+}
+
+#[test]
+fn test_catalog_rejects_duplicate_tables() {
+    let statement = build_statement("CREATE TABLE USERS(id INT);").unwrap();
+    let mut catalog = catalog();
+    assert_eq!(catalog.add_statement(&statement).unwrap_err(), Message::TableAlreadyExists { name: "USERS".to_string() });
+    assert_eq!(catalog.tables().count(), 2);
+}
+
+#[test]
+fn test_comments_in_catalog() {
+    let mut catalog = catalog();
+    for query in [
+        "COMMENT ON TABLE users IS 'Registered users';",
+        "COMMENT ON COLUMN users.name IS 'Full name';",
+        "COMMENT ON COLUMN Users.Name IS 'Display name';",
+    ] {
+        let statement = build_statement(query).unwrap();
+        resolve(statement.clone(), &catalog).unwrap();
+        catalog.add_statement(&statement).unwrap();
+    }
+    assert_eq!(catalog.comment("USERS"), Some("Registered users"));
+    // A new comment replaces the previous one
+    assert_eq!(catalog.table("users").unwrap()[1].constraints, vec![Constraint::Comment("Display name".to_string())]);
+    
+    catalog.add_statement(&build_statement("COMMENT ON TABLE users IS NULL;").unwrap()).unwrap();
+    assert_eq!(catalog.comment("users"), None);
+    
+    let statement = build_statement("COMMENT ON COLUMN users.email IS 'x';").unwrap();
+    assert_eq!(resolve(statement.clone(), &catalog).unwrap_err(), Message::UnknownColumn { name: "users.email".to_string() });
+    assert_eq!(catalog.add_statement(&statement).unwrap_err(), Message::UnknownColumn { name: "users.email".to_string() });
+    let statement = build_statement("COMMENT ON VIEW users IS 'x';").unwrap();
+    assert_eq!(catalog.add_statement(&statement).unwrap_err(), Message::UnknownView { name: "users".to_string() });
+}
+
+#[test]
+fn test_resolve_qualified_wildcard() {
+    let statement = build_statement("SELECT * FROM (SELECT o.*, u.name FROM users u JOIN orders o ON u.id = o.user_id) AS x WHERE user_id = total;").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    // The derived table has every column of orders, and name
+    assert_eq!(resolved.bindings, vec![
+        binding("u", "name"),
+        binding("u", "id"),
+        binding("o", "user_id"),
+        binding("x", "user_id"),
+        binding("x", "total"),
+    ]);
+    
+    let statement = build_statement("SELECT p.* FROM users;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap_err(), Message::UnknownTable { name: "p".to_string() });
+}
+
+#[test]
+fn test_resolve_insert() {
+    let statement = build_statement("INSERT INTO orders (user_id, total) VALUES (1, 10), (2, (SELECT MAX(age) FROM users));").unwrap();
+    let resolved = resolve(statement, &catalog()).unwrap();
+    assert_eq!(resolved.bindings, vec![
+        binding("orders", "user_id"),
+        binding("orders", "total"),
+        binding("users", "age"),
+    ]);
+    
+    let statement = build_statement("INSERT INTO orders SELECT id, id, age FROM users;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap().bindings.len(), 3);
+    
+    // The returned values are read from the inserted row
+    let statement = build_statement("INSERT INTO orders (total) VALUES (5) RETURNING id, total * 2 AS doubled;").unwrap();
+    assert_eq!(resolve(statement, &catalog()).unwrap().bindings, vec![
+        binding("orders", "total"),
+        binding("orders", "id"),
+        binding("orders", "total"),
+    ]);
+    
+    for (query, error) in [
+        ("INSERT INTO items VALUES (1);", Message::UnknownTable { name: "items".to_string() }),
+        ("INSERT INTO orders (id, email) VALUES (1, 2);", Message::UnknownColumn { name: "orders.email".to_string() }),
+        ("INSERT INTO orders VALUES (1, 2);", Message::InsertValueCount { expected: 3, found: 2 }),
+        ("INSERT INTO orders (id) VALUES (1), (2, 3);", Message::InsertValueCount { expected: 1, found: 2 }),
+        // The values can not refer to the columns of the table
+        ("INSERT INTO orders (id) VALUES (total);", Message::UnknownColumn { name: "total".to_string() }),
+        ("INSERT INTO orders (id) VALUES (1) RETURNING age;", Message::UnknownColumn { name: "age".to_string() }),
+    ] {
+        assert_eq!(resolve(build_statement(query).unwrap(), &catalog()).unwrap_err(), error);
+    }
+}
+
+#[test]
+fn test_catalog_keeps_existing_objects_with_if_not_exists() {
+    let mut catalog = catalog();
+    catalog.add_statement(&build_statement("CREATE TABLE IF NOT EXISTS users(email VARCHAR(20));").unwrap()).unwrap();
+    catalog.add_statement(&build_statement("CREATE VIEW IF NOT EXISTS orders AS SELECT 1;").unwrap()).unwrap();
+    assert_eq!(catalog.table("users").unwrap()[0].column_name, "id");
+    assert!(catalog.view("orders").is_none());
+    
+    catalog.add_statement(&build_statement("CREATE TABLE IF NOT EXISTS items(id INT);").unwrap()).unwrap();
+    assert!(catalog.table("items").is_some());
+}
+
+#[test]
+fn test_drop_table() {
+    let mut catalog = catalog();
+    assert_eq!(resolve(build_statement("DROP TABLE IF EXISTS items;").unwrap(), &catalog).unwrap().bindings, vec![]);
+    assert_eq!(
+        resolve(build_statement("DROP TABLE items;").unwrap(), &catalog).unwrap_err(),
+        Message::UnknownTable { name: "items".to_string() }
+    );
+    
+    catalog.add_statement(&build_statement("DROP TABLE Orders;").unwrap()).unwrap();
+    assert!(catalog.table("orders").is_none());
+    assert!(catalog.table("users").is_some());
+    assert_eq!(
+        catalog.add_statement(&build_statement("DROP TABLE orders;").unwrap()).unwrap_err(),
+        Message::UnknownTable { name: "orders".to_string() }
+    );
+    catalog.add_statement(&build_statement("DROP TABLE IF EXISTS orders;").unwrap()).unwrap();
+}
+
+#[test]
+fn test_catalog_replaces_views() {
+    let mut catalog = catalog();
+    catalog.add_statement(&build_statement("CREATE OR REPLACE VIEW adults AS SELECT id FROM users;").unwrap()).unwrap();
+    catalog.add_statement(&build_statement("CREATE OR REPLACE VIEW Adults AS SELECT name FROM users;").unwrap()).unwrap();
+    assert_eq!(catalog.view("adults"), Some(&build_statement("SELECT name FROM users;").unwrap()));
+    assert_eq!(catalog.views().count(), 1);
+    assert_eq!(
+        catalog.add_statement(&build_statement("CREATE OR REPLACE VIEW users AS SELECT 1;").unwrap()).unwrap_err(),
+        Message::TableAlreadyExists { name: "users".to_string() }
+    );
+}
+
+#[test]
+fn test_qualified_table_names() {
+    let catalog = Catalog::from_statements(&[
+        build_statement("CREATE TABLE public.users(id INT, name VARCHAR(10));").unwrap(),
+        build_statement("CREATE VIEW public.names AS SELECT name FROM public.users;").unwrap(),
+    ]).unwrap();
+    
+    // A table is visible under the last part of its name
+    let statement = build_statement("SELECT users.id, id FROM public.users JOIN public.names n ON n.name = users.name;").unwrap();
+    assert_eq!(resolve(statement, &catalog).unwrap().bindings, vec![
+        binding("users", "id"),
+        binding("users", "id"),
+        binding("n", "name"),
+        binding("users", "name"),
+    ]);
+    
+    for (query, error) in [
+        ("SELECT id FROM users;", Message::UnknownTable { name: "users".to_string() }),
+        ("SELECT id FROM other.users;", Message::UnknownTable { name: "other.users".to_string() }),
+        ("SELECT public.users.id FROM public.users;", Message::UnknownColumn { name: "public.users.id".to_string() }),
+    ] {
+        assert_eq!(resolve(build_statement(query).unwrap(), &catalog).unwrap_err(), error);
+    }
+}
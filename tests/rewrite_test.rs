@@ -0,0 +1,234 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement, expand_views, inline_ctes, resolve,
+    Catalog, Message, Statement, Expression, BinaryOperator, UnaryOperator, TableReference, ObjectName
+};
+use programming_languages_project_kyrylo_yezholov::rewrite::{walk_expression, Transformer};
+
+fn catalog(script: &[&str]) -> Catalog {
+    let statements: Vec<Statement> = script
+        .iter()
+        .map(|query| build_statement(query).unwrap())
+        .collect();
+    Catalog::from_statements(&statements).unwrap()
+}
+
+#[test]
+fn test_expand_view() {
+    let catalog = catalog(&[
+        "CREATE TABLE users(id INT, age INT);",
+        "CREATE VIEW adults AS SELECT id FROM users WHERE age >= 18;",
+    ]);
+    let statement = build_statement("SELECT id FROM adults;").unwrap();
+    let expanded = expand_views(statement, &catalog).unwrap();
+    
+    assert_eq!(expanded, Statement::Select {
+        with: vec![],
+        columns: vec![Expression::Identifier("id".to_string())],
+        from: vec![TableReference::Derived {
+            subquery: Box::new(Statement::Select {
+                with: vec![],
+                columns: vec![Expression::Identifier("id".to_string())],
+                from: vec![TableReference::Table { name: ObjectName::new("users"), alias: None }],
+                r#where: Some(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("age".to_string())),
+                    operator: BinaryOperator::GreaterThanOrEqual,
+                    right_operand: Box::new(Expression::Number(18))
+                }),
+                orderby: vec![],
+                limit: None,
+                lock: None
+            }),
+            alias: "adults".to_string()
+        }],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    });
+    
+    // The expanded statement refers to real tables only, and still resolves
+    let resolved = resolve(expanded, &catalog).unwrap();
+    assert_eq!(resolved.bindings.len(), 3);
+}
+
+#[test]
+fn test_expand_nested_views_in_joins() -> Result<(), String> {
+    let catalog = catalog(&[
+        "CREATE TABLE users(id INT, age INT);",
+        "CREATE TABLE orders(user_id INT, total INT);",
+        "CREATE VIEW adults AS SELECT * FROM users WHERE age >= 18;",
+        "CREATE VIEW old_adults AS SELECT * FROM adults WHERE age >= 65;",
+    ]);
+    let statement = build_statement("SELECT total FROM orders JOIN old_adults ON user_id = id;")?;
+    let expanded = expand_views(statement, &catalog)?;
+    
+    let Statement::Select { from, .. } = &expanded else {
+        return Err("Expected SELECT statement".to_string());
+    };
+    let [TableReference::Join { left, right, .. }] = from.as_slice() else {
+        return Err("Expected a join".to_string());
+    };
+    assert_eq!(**left, TableReference::Table { name: ObjectName::new("orders"), alias: None });
+    let TableReference::Derived { subquery, alias } = &**right else {
+        return Err("Expected the view to be expanded".to_string());
+    };
+    assert_eq!(alias, "old_adults");
+    assert!(matches!(
+        &**subquery,
+        Statement::Select { from, .. } if matches!(from.as_slice(), [TableReference::Derived { alias, .. }] if alias == "adults")
+    ));
+    
+    resolve(expanded, &catalog)?;
+    Ok(())
+}
+
+#[test]
+fn test_statements_without_views_are_unchanged() {
+    let catalog = catalog(&["CREATE TABLE users(id INT);"]);
+    let statement = build_statement("SELECT id FROM users WHERE id > 1;").unwrap();
+    assert_eq!(expand_views(statement.clone(), &catalog).unwrap(), statement);
+}
+
+#[test]
+fn test_recursive_view() {
+    let catalog = catalog(&[
+        "CREATE VIEW a AS SELECT * FROM b;",
+        "CREATE VIEW b AS SELECT * FROM a;",
+    ]);
+    let statement = build_statement("SELECT * FROM a;").unwrap();
+    assert_eq!(expand_views(statement, &catalog).unwrap_err(), Message::RecursiveView { name: "a".to_string() });
+}
+
+#[test]
+fn test_inline_single_use_ctes() {
+    let statement = build_statement(
+        "WITH adults AS (SELECT id FROM users WHERE age >= 18), unused AS (SELECT id FROM users) SELECT id FROM adults;"
+    ).unwrap();
+    let expected = build_statement("SELECT id FROM users WHERE age >= 18;").unwrap();
+    
+    let inlined = inline_ctes(statement).unwrap();
+    let Statement::Select { with, from, .. } = inlined else {
+        panic!("Expected SELECT statement");
+    };
+    assert!(with.is_empty());
+    assert_eq!(from, vec![TableReference::Derived {
+        subquery: Box::new(expected),
+        alias: "adults".to_string()
+    }]);
+}
+
+#[test]
+fn test_multi_use_ctes_are_kept() {
+    let statement = build_statement(
+        "WITH a AS (SELECT id FROM t) SELECT id FROM a JOIN b ON id = x JOIN a ON id = y;"
+    ).unwrap();
+    assert_eq!(inline_ctes(statement.clone()).unwrap(), statement);
+}
+
+#[test]
+fn test_deduplicate_identical_ctes() {
+    let statement = build_statement(
+        "WITH a AS (SELECT id FROM t), b AS (SELECT id FROM t) SELECT id FROM a JOIN b ON id = id;"
+    ).unwrap();
+    let expected = build_statement(
+        "WITH a AS (SELECT id FROM t) SELECT id FROM a JOIN a AS b ON id = id;"
+    ).unwrap();
+    assert_eq!(inline_ctes(statement).unwrap(), expected);
+}
+
+#[test]
+fn test_inline_chained_and_nested_ctes() {
+    let statement = build_statement(
+        "WITH a AS (WITH inner_cte AS (SELECT x FROM t) SELECT x FROM inner_cte), b AS (SELECT x FROM a) SELECT x FROM b;"
+    ).unwrap();
+    let inlined = inline_ctes(statement).unwrap();
+    
+    // b is inlined into the query, a into b, and inner_cte into a
+    let expected = Statement::Select {
+        with: vec![],
+        columns: vec![Expression::Identifier("x".to_string())],
+        from: vec![TableReference::Derived {
+            subquery: Box::new(Statement::Select {
+                with: vec![],
+                columns: vec![Expression::Identifier("x".to_string())],
+                from: vec![TableReference::Derived {
+                    subquery: Box::new(Statement::Select {
+                        with: vec![],
+                        columns: vec![Expression::Identifier("x".to_string())],
+                        from: vec![TableReference::Derived {
+                            subquery: Box::new(build_statement("SELECT x FROM t;").unwrap()),
+                            alias: "inner_cte".to_string()
+                        }],
+                        r#where: None,
+                        orderby: vec![],
+                        limit: None,
+                        lock: None
+                    }),
+                    alias: "a".to_string()
+                }],
+                r#where: None,
+                orderby: vec![],
+                limit: None,
+                lock: None
+            }),
+            alias: "b".to_string()
+        }],
+        r#where: None,
+        orderby: vec![],
+        limit: None,
+        lock: None
+    };
+    assert_eq!(inlined, expected);
+}
+
+#[test]
+fn test_resolve_ctes() {
+    let catalog = catalog(&["CREATE TABLE users(id INT, age INT);"]);
+    let statement = build_statement(
+        "WITH adults AS (SELECT id FROM users WHERE age >= 18) SELECT id FROM adults;"
+    ).unwrap();
+    let resolved = resolve(statement.clone(), &catalog).unwrap();
+    let inlined = resolve(inline_ctes(statement).unwrap(), &catalog).unwrap();
+    assert_eq!(resolved.bindings, inlined.bindings);
+    assert_eq!(resolved.bindings.last().unwrap().table, "adults");
+}
+
+#[test]
+fn test_expand_views_in_subqueries() {
+    let catalog = catalog(&[
+        "CREATE TABLE users(id INT, age INT);",
+        "CREATE VIEW adults AS SELECT id FROM users WHERE age >= 18;",
+    ]);
+    let statement = build_statement("SELECT * FROM users WHERE id IN (SELECT id FROM adults);").unwrap();
+    let expected = build_statement(
+        "SELECT * FROM users WHERE id IN (SELECT id FROM (SELECT id FROM users WHERE age >= 18) AS adults);"
+    ).unwrap();
+    assert_eq!(expand_views(statement, &catalog).unwrap(), expected);
+}
+
+// Folds the sign into number literals, which keeps negative constants as literals
+struct SignFolder;
+
+impl Transformer for SignFolder {
+    fn transform_expression(&mut self, expression: Expression) -> Result<Expression, Message> {
+        match walk_expression(self, expression)? {
+            Expression::UnaryOperation { operand, operator: UnaryOperator::Minus } => match *operand {
+                Expression::Number(number) => Ok(Expression::Number(-number)),
+                operand => Ok(Expression::UnaryOperation { operand: Box::new(operand), operator: UnaryOperator::Minus }),
+            },
+            expression => Ok(expression),
+        }
+    }
+}
+
+#[test]
+fn test_fold_negative_numbers() {
+    let statement = build_statement("SELECT id FROM t WHERE balance < -5 AND -id > 0;").unwrap();
+    let Statement::Select { r#where: Some(Expression::BinaryOperation { left_operand, right_operand, .. }), .. } =
+        SignFolder.transform_statement(statement).unwrap()
+    else {
+        panic!("Expected a SELECT with a condition");
+    };
+    assert!(matches!(*left_operand, Expression::BinaryOperation { right_operand, .. } if *right_operand == Expression::Number(-5)));
+    assert!(matches!(*right_operand, Expression::BinaryOperation { left_operand, .. } if matches!(*left_operand, Expression::UnaryOperation { .. })));
+}
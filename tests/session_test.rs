@@ -0,0 +1,49 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement, Message, Session
+};
+
+fn execute(session: &mut Session, query: &str) -> Result<(), Message> {
+    session.execute(build_statement(query).unwrap())
+}
+
+#[test]
+fn test_undo_and_redo() {
+    let mut session = Session::new();
+    execute(&mut session, "CREATE TABLE users(id INT);").unwrap();
+    execute(&mut session, "CREATE VIEW ids AS SELECT id FROM users;").unwrap();
+    // Queries do not change the state, so they are not logged
+    execute(&mut session, "SELECT * FROM ids;").unwrap();
+    assert_eq!(session.log().len(), 2);
+
+    assert_eq!(session.undo().map(|statement| statement.kind()), Some("CREATE VIEW"));
+    assert!(session.catalog().view("ids").is_none());
+    assert!(session.catalog().table("users").is_some());
+
+    assert_eq!(session.undo().map(|statement| statement.kind()), Some("CREATE TABLE"));
+    assert!(session.catalog().table("users").is_none());
+    assert!(session.undo().is_none());
+
+    assert_eq!(session.redo().map(|statement| statement.kind()), Some("CREATE TABLE"));
+    assert_eq!(session.redo().map(|statement| statement.kind()), Some("CREATE VIEW"));
+    assert!(session.redo().is_none());
+    assert!(session.catalog().view("ids").is_some());
+    assert_eq!(session.log().len(), 2);
+}
+
+#[test]
+fn test_undone_name_can_be_reused() {
+    let mut session = Session::new();
+    execute(&mut session, "CREATE TABLE users(id INT);").unwrap();
+    assert_eq!(
+        execute(&mut session, "CREATE TABLE users(name VARCHAR(10));").unwrap_err(),
+        Message::TableAlreadyExists { name: "users".to_string() }
+    );
+    // A rejected statement is not logged
+    assert_eq!(session.log().len(), 1);
+
+    session.undo();
+    execute(&mut session, "CREATE TABLE users(name VARCHAR(10));").unwrap();
+    assert_eq!(session.catalog().table("users").unwrap()[0].column_name, "name");
+    // A new change forgets what was undone
+    assert!(session.redo().is_none());
+}
@@ -0,0 +1,194 @@
+use programming_languages_project_kyrylo_yezholov::{
+    build_statement, Statement, Expression, TableReference, BinaryOperator, UnaryOperator, DBType, Tokenizer, Parser, ParserOptions, Dialect
+};
+
+fn parse_in_dialect(dialect: Dialect, query: &str) -> Result<Statement, String> {
+    let mut parser = Parser::with_options(Tokenizer::new(query), ParserOptions { dialect, ..ParserOptions::default() })?;
+    parser.parse_statement()
+}
+
+// Renders the statement and checks that the SQL parses back into it
+fn assert_round_trip(dialect: Dialect, query: &str) -> String {
+    let statement = parse_in_dialect(dialect, query).expect(query);
+    let sql = statement.to_sql();
+    assert_eq!(parse_in_dialect(dialect, &sql), Ok(statement), "{} was rendered as {}", query, sql);
+    // Rendering is stable once the statement went through it
+    assert_eq!(parse_in_dialect(dialect, &sql).unwrap().to_sql(), sql);
+    sql
+}
+
+fn render(query: &str) -> String {
+    build_statement(query).unwrap().to_string()
+}
+
+#[test]
+fn test_round_trip_queries() {
+    for query in [
+        "SELECT * FROM users;",
+        "SELECT a, b.c, t.* FROM t AS x, s WHERE a > 1 ORDER BY a DESC, b ASC LIMIT 10;",
+        "SELECT 1 + 2;",
+        "SELECT name FROM users JOIN orders o ON users.id = o.user_id LEFT OUTER JOIN items ON o.id = items.order_id;",
+        "SELECT x FROM (SELECT x FROM t) AS d RIGHT JOIN s ON d.x = s.x FULL JOIN u ON TRUE;",
+        "SELECT * FROM a JOIN (b LEFT JOIN (c JOIN d ON c.x = d.x) ON b.x = c.x) ON a.x = b.x;",
+        "WITH a AS (SELECT 1), b AS (SELECT x FROM a) SELECT * FROM b;",
+        "SELECT * FROM t WHERE id IN (SELECT id FROM s) AND id NOT IN (1, 2, 3) AND (a, b) IN ((1, 2), (3, 4));",
+        "SELECT * FROM t WHERE NOT id IN (SELECT id FROM s) OR EXISTS (SELECT 1) OR NOT EXISTS (SELECT 2);",
+        "SELECT * FROM t WHERE name LIKE 'a%' ESCAPE '!' AND name NOT LIKE '%''%' AND note IS NOT NULL AND x IS NULL;",
+        "SELECT * FROM t WHERE a IS DISTINCT FROM b AND a IS NOT DISTINCT FROM c + 1;",
+        "SELECT * FROM t WHERE name SIMILAR TO 'A%' OR name NOT SIMILAR TO 'B%';",
+        "SELECT * FROM t WHERE salary > ALL (SELECT salary FROM s) AND salary = ANY (SELECT 1);",
+        "SELECT COUNT(*), COUNT(DISTINCT name), now(), MAX(a) OVER (PARTITION BY b ORDER BY c DESC), ROW_NUMBER() OVER () FROM t;",
+        "SELECT CAST(a AS DECIMAL(10, 2)), CAST(b AS TIMESTAMP WITH TIME ZONE), EXTRACT(YEAR FROM d) FROM t;",
+        "SELECT DATE '2024-01-31', TIME '12:30:00', TIMESTAMP '2024-01-31 12:30:00', B'1010', ?, $2, :name FROM t;",
+        "SELECT 1.5, 3.0, 1e20, 'it''s', \"say \"\"hi\"\"\", N'national', TRUE, FALSE FROM t;",
+        "SELECT * FROM t WHERE id = 1 FOR UPDATE SKIP LOCKED;",
+        "SELECT a FROM t ORDER BY a FOR SHARE NOWAIT;",
+        "SELECT (SELECT MAX(a) FROM s) FROM t;",
+    ] {
+        assert_round_trip(Dialect::Generic, query);
+    }
+}
+
+#[test]
+fn test_round_trip_operators() {
+    for query in [
+        "SELECT a - b - c, a - (b - c), a / (b * c), (a + b) * c, a + b * c FROM t;",
+        "SELECT a OR b AND c, (a OR b) AND c, NOT a AND b, NOT (a AND b), NOT a = 1, NOT (a = 1) FROM t;",
+        "SELECT -a, - -a, -(-1), -(a + b), -a * b, a * -b, ~a & b, ~(a & b), +a FROM t;",
+        "SELECT -9223372036854775808, -9223372036854775808 * 2, - -9223372036854775808 FROM t;",
+        "SELECT a | b ^ c & d << 2, (a | b) & c, a >> (b + 1), flags & 4 <> 0 FROM t;",
+        "SELECT (a = b) = c, a = (b = c), a < b AND b <= c OR c >= d, a != b FROM t;",
+        "SELECT (a IS NULL) IS NULL, (a OR b) IS NOT NULL, a + 1 IN (1, 2), (a AND b) LIKE c FROM t;",
+        "SELECT (a OR b) DESC, a + 1 ASC FROM t;",
+        "SELECT * FROM t WHERE NOT (EXISTS (SELECT 1));",
+        "SELECT * FROM t ORDER BY -a, (a OR b) DESC;",
+    ] {
+        assert_round_trip(Dialect::Generic, query);
+    }
+    // The power is only read in PostgreSQL, and binds tighter than the signs
+    for query in [
+        "SELECT 2 ^ 3 ^ 2, (2 ^ 3) ^ 2, a * b ^ 2, -a ^ 2, (-a) ^ 2, a ^ -b FROM t;",
+        "SELECT a::INT FROM t;",
+        "SELECT (-9223372036854775808) ^ 2 FROM t;",
+    ] {
+        assert_round_trip(Dialect::Postgres, query);
+    }
+    assert_round_trip(Dialect::MySql, "SELECT * FROM t WHERE name REGEXP '^A' OR code NOT REGEXP '[0-9]';");
+}
+
+#[test]
+fn test_round_trip_long_chains() {
+    // The chains the parser builds without recursion are written out without it too
+    let operands = vec!["a"; 2000];
+    for operator in [" - ", " * ", " AND "] {
+        let query = format!("SELECT {} FROM t;", operands.join(operator));
+        assert_eq!(assert_round_trip(Dialect::Generic, &query), query);
+    }
+}
+
+#[test]
+fn test_round_trip_schema_statements() {
+    for query in [
+        "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(255) NOT NULL, bio VARCHAR NULL, age INT CHECK (age >= 18 AND age < 150));",
+        "CREATE TEMPORARY TABLE IF NOT EXISTS s.t (a BIGINT GENERATED ALWAYS AS IDENTITY, b SMALLINT GENERATED BY DEFAULT AS IDENTITY, c TINYINT AUTO_INCREMENT);",
+        "CREATE TABLE t (a DECIMAL, b NUMERIC(5), c FLOAT, d REAL, e DOUBLE PRECISION, f BLOB, g UUID, h SERIAL, i BIGSERIAL, j DATE, k TIME, l DATETIME, m TIMESTAMP, n BOOL);",
+        "CREATE TABLE t (a INT DEFAULT -1 NOT NULL, b INT DEFAULT (1 = 1), c VARCHAR COMMENT 'the ''c''', d INT DEFAULT 1 + 2);",
+        "CREATE TABLE t (a INT, b INT, CONSTRAINT pk PRIMARY KEY (a, b), UNIQUE (b), CONSTRAINT positive CHECK (a > 0), CHECK (b > a));",
+        "CREATE TABLE t (a INT) WITH (fillfactor = 70, autovacuum enabled = FALSE) PARTITION BY RANGE (a);",
+        "CREATE TABLE t (a INT) PARTITION BY HASH (a, a + 1);",
+        "CREATE VIEW adults AS SELECT * FROM users WHERE age >= 18;",
+        "CREATE OR REPLACE VIEW reports.adults AS SELECT name FROM users;",
+        "CREATE VIEW IF NOT EXISTS v AS WITH a AS (SELECT 1) SELECT * FROM a;",
+        "COMMENT ON TABLE users IS 'People';",
+        "COMMENT ON VIEW adults IS NULL;",
+        "COMMENT ON COLUMN s.users.email IS 'Where to write';",
+        "INSERT INTO users VALUES (1, 'a'), (2, 'b');",
+        "CREATE TABLE t (a INT DEFAULT NULL, b INT DEFAULT NULL NOT NULL);",
+        "INSERT INTO t VALUES (NULL, COALESCE(a, NULL)), (-1, NULL);",
+        "INSERT INTO users (id, name) SELECT id, name FROM staff;",
+        "INSERT INTO users (name) VALUES ('a') RETURNING id, id * 2 AS `double`, *;",
+        "INSERT INTO archive SELECT * FROM orders RETURNING archive.id;",
+        "DROP TABLE users;",
+        "DROP TABLE IF EXISTS s.users;",
+    ] {
+        assert_round_trip(Dialect::Generic, query);
+    }
+    assert_round_trip(Dialect::MySql, "CREATE TABLE t (a INT UNSIGNED, b BIGINT UNSIGNED NOT NULL);");
+}
+
+#[test]
+fn test_quoted_identifiers() {
+    // Keywords and names that are not plain words are quoted with backticks
+    let sql = assert_round_trip(Dialect::Generic, "SELECT `select`, `first name`, `a``b`, `order`.`from` FROM `table` AS `limit`;");
+    assert_eq!(sql, "SELECT `select`, `first name`, `a``b`, `order`.`from` FROM `table` AS `limit`;");
+    assert_eq!(render("SELECT `plain` FROM t;"), "SELECT plain FROM t;");
+    assert_eq!(render("SELECT `left`(a) FROM t;"), "SELECT `left`(a) FROM t;");
+    
+    // Names and tables print as the SQL of the statement does
+    let Ok(Statement::Select { from, .. }) = build_statement("SELECT * FROM `order` AS o JOIN (s.`from` JOIN u ON TRUE) ON TRUE;") else {
+        panic!("Expected SELECT statement");
+    };
+    assert_eq!(from[0].to_string(), "`order` AS o JOIN (s.`from` JOIN u ON TRUE) ON TRUE");
+    let TableReference::Join { right, .. } = &from[0] else {
+        panic!("Expected a join");
+    };
+    let TableReference::Join { left, .. } = &**right else {
+        panic!("Expected a join on the right");
+    };
+    assert!(matches!(&**left, TableReference::Table { name, .. } if name.to_string() == "s.`from`"));
+}
+
+#[test]
+fn test_minimal_parentheses() {
+    assert_eq!(render("SELECT ((a + (b * c))) - (d - e) FROM t;"), "SELECT a + b * c - (d - e) FROM t;");
+    assert_eq!(render("SELECT * FROM t WHERE (a = 1 AND (b = 2)) OR (NOT c);"), "SELECT * FROM t WHERE a = 1 AND b = 2 OR NOT c;");
+    assert_eq!(render("SELECT - (- a), a - -b FROM t;"), "SELECT -(-a), a - -b FROM t;");
+    assert_eq!(render("SELECT * FROM t WHERE NOT (a IN (SELECT 1));"), "SELECT * FROM t WHERE a NOT IN (SELECT 1);");
+    assert_eq!(render("SELECT a <> b, a != b FROM t;"), "SELECT a <> b, a <> b FROM t;");
+}
+
+#[test]
+fn test_statement_to_sql() {
+    assert_eq!(
+        render("create table if not exists t(id int primary key, price decimal(10,2) default 0 not null, tag varchar(8) comment 'isn''t');"),
+        "CREATE TABLE IF NOT EXISTS t (id INT PRIMARY KEY, price DECIMAL(10, 2) DEFAULT 0 NOT NULL, tag VARCHAR(8) COMMENT 'isn''t');"
+    );
+    assert_eq!(
+        render("SELECT \"say \"\"hi\"\"\" FROM t;"),
+        "SELECT \"say \"\"hi\"\"\" FROM t;"
+    );
+    assert_eq!(render("INSERT INTO t(a) VALUES(1),(2);"), "INSERT INTO t (a) VALUES (1), (2);");
+    assert_eq!(render("COMMENT ON COLUMN t.c IS NULL;"), "COMMENT ON COLUMN t.c IS NULL;");
+    assert_eq!(DBType::Timestamp { with_time_zone: true }.to_string(), "TIMESTAMP WITH TIME ZONE");
+    assert_eq!(BinaryOperator::Power.to_string(), "^");
+    // TOP of SQL Server comes back as a LIMIT
+    let statement = parse_in_dialect(Dialect::MsSql, "SELECT TOP 5 name FROM t ORDER BY name;").unwrap();
+    assert_eq!(statement.to_sql(), "SELECT name FROM t ORDER BY name LIMIT 5;");
+    assert_eq!(build_statement(&statement.to_sql()), Ok(statement));
+}
+
+#[test]
+fn test_transformed_statement() {
+    // A statement changed after parsing is written out with the parentheses its
+    // new shape needs
+    let Ok(Statement::Select { r#where: Some(condition), .. }) = build_statement("SELECT * FROM t WHERE a = 1 OR b = 2;") else {
+        panic!("Expected SELECT statement with a condition");
+    };
+    let condition = Expression::BinaryOperation {
+        left_operand: Box::new(condition),
+        operator: BinaryOperator::And,
+        right_operand: Box::new(Expression::UnaryOperation {
+            operand: Box::new(Expression::Identifier("deleted".to_string())),
+            operator: UnaryOperator::Not,
+        }),
+    };
+    assert_eq!(condition.to_sql(), "(a = 1 OR b = 2) AND NOT deleted");
+    let negated = Expression::UnaryOperation { operand: Box::new(condition.clone()), operator: UnaryOperator::Minus };
+    assert_eq!(negated.to_string(), "-((a = 1 OR b = 2) AND NOT deleted)");
+
+    let Ok(Statement::Select { with, columns, from, orderby, limit, lock, .. }) = build_statement("SELECT * FROM t;") else {
+        panic!("Expected SELECT statement");
+    };
+    let statement = Statement::Select { with, columns, from, r#where: Some(condition), orderby, limit, lock };
+    assert_eq!(build_statement(&statement.to_sql()), Ok(statement));
+}
@@ -1,6 +1,6 @@
 use programming_languages_project_kyrylo_yezholov::{
-    Token, Keyword,
-    Tokenizer
+    Token, Keyword, Keywords, Span, Position,
+    Tokenizer, Dialect
 };
 #[test]
 fn test_basic_select() {
@@ -36,6 +36,98 @@ fn test_numbers() {
     ]);
 }
 
+#[test]
+fn test_decimal_numbers() {
+    let input = "9.99 0.5 1. t.1";
+    let tokens: Vec<Token> = Tokenizer::new(input)
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    // Without digits after it the dot is a separate token
+    assert_eq!(tokens, vec![
+        Token::Float(9.99),
+        Token::Float(0.5),
+        Token::Number(1),
+        Token::Dot,
+        Token::Identifier("t".to_string()),
+        Token::Dot,
+        Token::Number(1),
+        Token::Eof
+    ]);
+}
+
+#[test]
+fn test_scientific_notation() {
+    let tokens: Vec<Token> = Tokenizer::new("1e10 2.5E-3 4e+2 1e e5 3E-")
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    // Without digits after it the e is the start of an identifier
+    assert_eq!(tokens, vec![
+        Token::Float(1e10),
+        Token::Float(2.5e-3),
+        Token::Float(400.0),
+        Token::Number(1),
+        Token::Identifier("e".to_string()),
+        Token::Identifier("e5".to_string()),
+        Token::Number(3),
+        Token::Identifier("E".to_string()),
+        Token::Minus,
+        Token::Eof
+    ]);
+}
+
+#[test]
+fn test_float_out_of_range() {
+    // A float that does not fit an f64 is invalid, rather than infinite
+    assert_eq!(Tokenizer::new("1e400").next_token(), Ok(Token::Invalid { text: "1e400".to_string(), span: Span { start: 0, end: 5 } }));
+    assert_eq!(Tokenizer::new("1.5e308").next_token(), Ok(Token::Float(1.5e308)));
+}
+
+#[test]
+fn test_hex_and_bit_string_literals() {
+    let tokens: Vec<Token> = Tokenizer::new("0xFF 0X1a 0x b'1010' B'' b")
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    // Without a hexadecimal digit, 0x is a zero followed by an identifier
+    assert_eq!(tokens, vec![
+        Token::Number(255),
+        Token::Number(26),
+        Token::Number(0),
+        Token::Identifier("x".to_string()),
+        Token::BitString("1010".to_string()),
+        Token::BitString("".to_string()),
+        Token::Identifier("b".to_string()),
+        Token::Eof
+    ]);
+    
+    let error = Tokenizer::new("b'102'").collect::<Result<Vec<Token>, String>>().unwrap_err();
+    assert_eq!(error, "Invalid bit string B'102', it can only contain 0 and 1");
+    assert_eq!(Tokenizer::new("0x1FFFFFFFFFFFFFFFF").next_token(), Ok(Token::Invalid { text: "0x1FFFFFFFFFFFFFFFF".to_string(), span: Span { start: 0, end: 19 } }));
+}
+
+#[test]
+fn test_placeholders() {
+    let tokens: Vec<Token> = Tokenizer::new("? $1 $23 $x :name @user_id a::INT")
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    assert_eq!(tokens, vec![
+        Token::Placeholder("?".to_string()),
+        Token::Placeholder("$1".to_string()),
+        Token::Placeholder("$23".to_string()),
+        Token::Invalid { text: "$".to_string(), span: Span { start: 9, end: 10 } },
+        Token::Identifier("x".to_string()),
+        Token::Placeholder(":name".to_string()),
+        Token::Placeholder("@user_id".to_string()),
+        Token::Identifier("a".to_string()),
+        Token::DoubleColon,
+        Token::Keyword(Keyword::Int),
+        Token::Eof
+    ]);
+}
+
 #[test]
 fn test_strings() {
     let input = "'hello' \"world\"";
@@ -52,7 +144,7 @@ fn test_strings() {
 
 #[test]
 fn test_operators() {
-    let input = "< <= > >= = != + - * /";
+    let input = "< <= > >= = != <> + - * / % :: & | ^ << >>";
     let tokens: Vec<Token> = Tokenizer::new(input)
         .collect::<Result<Vec<Token>, String>>()
         .unwrap();
@@ -64,10 +156,18 @@ fn test_operators() {
         Token::GreaterThanOrEqual,
         Token::Equal,
         Token::NotEqual,
+        Token::NotEqual,
         Token::Plus,
         Token::Minus,
         Token::Star,
         Token::Divide,
+        Token::Percent,
+        Token::DoubleColon,
+        Token::Ampersand,
+        Token::Pipe,
+        Token::Caret,
+        Token::ShiftLeft,
+        Token::ShiftRight,
         Token::Eof
     ]);
 }
@@ -117,14 +217,32 @@ fn test_invalid_number() {
 
 #[test]
 fn test_invalid_operator() {
-    let input = "@";
+    let input = "@ a £€§ b";
     let tokens: Vec<Token> = Tokenizer::new(input)
         .collect::<Result<Vec<Token>, String>>()
         .unwrap();
     
-    // Tokenizer returns Invalid token for unknown character
+    // Tokenizer returns Invalid token for unknown characters, taking a run of them together
+    assert_eq!(tokens, vec![
+        Token::Invalid { text: "@".to_string(), span: Span { start: 0, end: 1 } },
+        Token::Identifier("a".to_string()),
+        Token::Invalid { text: "£€§".to_string(), span: Span { start: 4, end: 11 } },
+        Token::Identifier("b".to_string()),
+        Token::Eof
+    ]);
+}
+
+#[test]
+fn test_single_colon() {
+    let tokens: Vec<Token> = Tokenizer::new("a:1")
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    // A colon only starts a parameter when a name follows it
     assert_eq!(tokens, vec![
-        Token::Invalid('@'),
+        Token::Identifier("a".to_string()),
+        Token::Invalid { text: ":".to_string(), span: Span { start: 1, end: 2 } },
+        Token::Number(1),
         Token::Eof
     ]);
 }
@@ -148,4 +266,257 @@ fn test_string_with_newline() {
         Token::String("string with\nnewline".to_string()),
         Token::Eof
     ]);
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_dotted_identifier() {
+    let input = "users.name";
+    let tokens: Vec<Token> = Tokenizer::new(input)
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    
+    assert_eq!(tokens, vec![
+        Token::Identifier("users".to_string()),
+        Token::Dot,
+        Token::Identifier("name".to_string()),
+        Token::Eof
+    ]);
+}
+
+#[test]
+fn test_line_comments() -> Result<(), String> {
+    let input = "-- the users\nSELECT a -- first column\n, b # MySQL comment\nFROM t; -- at the end";
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer.set_dialect(Dialect::MySql);
+    let tokens = tokenizer.collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::Keyword(Keyword::Select),
+        Token::Identifier("a".to_string()),
+        Token::Comma,
+        Token::Identifier("b".to_string()),
+        Token::Keyword(Keyword::From),
+        Token::Identifier("t".to_string()),
+        Token::Semicolon,
+        Token::Eof
+    ]);
+    
+    // A single minus is still an operator, and comments can not start inside strings
+    let tokens = Tokenizer::new("1 - -2 '--'")
+        .collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::Number(1),
+        Token::Minus,
+        Token::Minus,
+        Token::Number(2),
+        Token::String("--".to_string()),
+        Token::Eof
+    ]);
+    
+    // Outside of MySQL a # does not start a comment
+    for dialect in [Dialect::Generic, Dialect::Postgres, Dialect::Sqlite] {
+        let mut tokenizer = Tokenizer::new("a # b");
+        tokenizer.set_dialect(dialect);
+        assert_eq!(tokenizer.collect::<Result<Vec<Token>, String>>()?, vec![
+            Token::Identifier("a".to_string()),
+            Token::Invalid { text: "#".to_string(), span: Span { start: 2, end: 3 } },
+            Token::Identifier("b".to_string()),
+            Token::Eof
+        ]);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_string_escapes() -> Result<(), String> {
+    let tokens = Tokenizer::new(r#"'it''s' "say ""hi""" '' 'a\b'"#).collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::String("it's".to_string()),
+        Token::String("say \"hi\"".to_string()),
+        Token::String("".to_string()),
+        Token::String("a\\b".to_string()),
+        Token::Eof
+    ]);
+    
+    let mut tokenizer = Tokenizer::new(r"'it\'s' 'a\nb\\' 'c\%' 'it''s'");
+    tokenizer.set_backslash_escapes(true);
+    let tokens = tokenizer.collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::String("it's".to_string()),
+        Token::String("a\nb\\".to_string()),
+        Token::String("c\\%".to_string()),
+        Token::String("it's".to_string()),
+        Token::Eof
+    ]);
+    
+    let mut tokenizer = Tokenizer::new(r"'abc\'");
+    tokenizer.set_backslash_escapes(true);
+    assert_eq!(tokenizer.next(), Some(Err("Unterminated string starting with '".to_string())));
+    Ok(())
+}
+
+#[test]
+fn test_positions() -> Result<(), String> {
+    let mut tokenizer = Tokenizer::new("SELECT a,\n  'ñ' b -- comment\nFROM t;");
+    let mut positions = Vec::new();
+    while let Some(token) = tokenizer.next() {
+        token?;
+        let position = tokenizer.position();
+        positions.push((position.line, position.column, position.offset));
+    }
+    assert_eq!(positions, vec![
+        (1, 1, 0), (1, 8, 7), (1, 9, 8),
+        // Columns count characters, so the two bytes of ñ are one column
+        (2, 3, 12), (2, 7, 17),
+        (3, 1, 30), (3, 6, 35), (3, 7, 36), (3, 8, 37),
+    ]);
+    
+    // Errors are reported where the failed token starts
+    let mut tokenizer = Tokenizer::new("SELECT\n   'abc");
+    assert!(tokenizer.nth(1).is_some_and(|token| token.is_err()));
+    assert_eq!(tokenizer.position().to_string(), "line 2, column 4");
+    Ok(())
+}
+
+#[test]
+fn test_spanned_tokens() -> Result<(), String> {
+    let input = "SELECT name -- who\nFROM \"users\";";
+    let tokens = Tokenizer::new(input).spanned().collect::<Result<Vec<(Token, Span)>, String>>()?;
+    let texts: Vec<&str> = tokens.iter().map(|(_, span)| &input[span.start..span.end]).collect();
+    assert_eq!(texts, vec!["SELECT", "name", "FROM", "\"users\"", ";", ""]);
+    assert_eq!(tokens[3].0, Token::String("users".to_string()));
+    assert_eq!(tokens[5], (Token::Eof, Span { start: input.len(), end: input.len() }));
+    
+    let mut tokens = Tokenizer::new("a 'b").spanned();
+    assert_eq!(tokens.next(), Some(Ok((Token::Identifier("a".to_string()), Span { start: 0, end: 1 }))));
+    assert!(tokens.next().is_some_and(|token| token.is_err()));
+    Ok(())
+}
+
+// Hands out its bytes one at a time, so every character that is more than a
+// byte long gets split between reads
+struct ByteReader<'a>(&'a [u8]);
+
+impl std::io::Read for ByteReader<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buffer[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_reader_input() -> Result<(), String> {
+    let input = "SELECT 'naïve €', 1.5e3 FROM t -- done\n;";
+    let expected = Tokenizer::new(input).collect::<Result<Vec<Token>, String>>()?;
+    let tokens = Tokenizer::from_reader(input.as_bytes()).collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, expected);
+    let tokens = Tokenizer::from_reader(ByteReader(input.as_bytes())).collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, expected);
+    
+    let mut tokenizer = Tokenizer::from_reader(ByteReader(b"SELECT 'a\xFFb'"));
+    assert_eq!(tokenizer.next(), Some(Ok(Token::Keyword(Keyword::Select))));
+    assert_eq!(tokenizer.next(), Some(Err("Can not read the input: invalid UTF-8".to_string())));
+    assert_eq!(tokenizer.next(), Some(Ok(Token::Eof)));
+    
+    let mut tokenizer = Tokenizer::from_reader(&b"x \xE2\x82"[..]);
+    assert_eq!(tokenizer.next(), Some(Ok(Token::Identifier("x".to_string()))));
+    assert_eq!(tokenizer.next(), Some(Err("Can not read the input: the last UTF-8 character is cut off".to_string())));
+    Ok(())
+}
+
+#[test]
+fn test_preserved_trivia() -> Result<(), String> {
+    let input = "SELECT  a -- first\n# second\n\tFROM t";
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer.set_dialect(Dialect::MySql);
+    tokenizer.set_preserve_trivia(true);
+    let tokens = tokenizer.collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::Keyword(Keyword::Select),
+        Token::Whitespace("  ".to_string()),
+        Token::Identifier("a".to_string()),
+        Token::Whitespace(" ".to_string()),
+        Token::Comment("-- first".to_string()),
+        Token::Whitespace("\n".to_string()),
+        Token::Comment("# second".to_string()),
+        Token::Whitespace("\n\t".to_string()),
+        Token::Keyword(Keyword::From),
+        Token::Whitespace(" ".to_string()),
+        Token::Identifier("t".to_string()),
+        Token::Eof,
+    ]);
+    
+    // The spans of the tokens cover the whole input, so it can be written back out
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer.set_dialect(Dialect::MySql);
+    tokenizer.set_preserve_trivia(true);
+    let text: String = tokenizer.spanned().map(|token| token.map(|(_, span)| &input[span.start..span.end])).collect::<Result<_, _>>()?;
+    assert_eq!(text, input);
+    Ok(())
+}
+
+#[test]
+fn test_lenient_errors() -> Result<(), String> {
+    let mut tokenizer = Tokenizer::new("SELECT 'a\" ^ x !\nFROM `t");
+    tokenizer.set_lenient(true);
+    let tokens = tokenizer.by_ref().collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::Keyword(Keyword::Select),
+        Token::Invalid { text: "'a\"".to_string(), span: Span { start: 7, end: 10 } },
+        Token::Caret,
+        Token::Identifier("x".to_string()),
+        Token::Invalid { text: "!".to_string(), span: Span { start: 15, end: 16 } },
+        Token::Keyword(Keyword::From),
+        Token::Invalid { text: "`t".to_string(), span: Span { start: 22, end: 24 } },
+        Token::Eof,
+    ]);
+    
+    let errors = tokenizer.errors();
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].message.starts_with("Mismatched quotes"));
+    assert_eq!(errors[0].position, Position { offset: 7, line: 1, column: 8 });
+    assert_eq!(errors[0].span, Span { start: 7, end: 10 });
+    assert_eq!(errors[1].message, "Invalid token !");
+    assert_eq!(errors[2].message, "Unterminated identifier starting with `");
+    assert_eq!(errors[2].position.to_string(), "line 2, column 6");
+    Ok(())
+}
+
+#[test]
+fn test_custom_keywords() -> Result<(), String> {
+    let mut keywords = Keywords::default();
+    keywords.insert_custom("Geography");
+    keywords.insert("ELSEWHERE", Keyword::Where);
+    assert_eq!(keywords.remove("limit"), Some(Keyword::Limit));
+    
+    let mut tokenizer = Tokenizer::new("geography elsewhere LIMIT select");
+    tokenizer.set_keywords(&keywords);
+    assert_eq!(tokenizer.collect::<Result<Vec<Token>, String>>()?, vec![
+        Token::Keyword(Keyword::Custom("GEOGRAPHY".to_string())),
+        Token::Keyword(Keyword::Where),
+        Token::Identifier("LIMIT".to_string()),
+        Token::Keyword(Keyword::Select),
+        Token::Eof,
+    ]);
+    assert_eq!(Tokenizer::new("geography").next(), Some(Ok(Token::Identifier("geography".to_string()))));
+    Ok(())
+}
+
+#[test]
+fn test_prefixed_strings() -> Result<(), String> {
+    let tokens = Tokenizer::new(r"N'naïve' e'a\tb\'c' E 'x' n").collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens, vec![
+        Token::NationalString("naïve".to_string()),
+        Token::EscapeString("a\tb'c".to_string()),
+        Token::Identifier("E".to_string()),
+        Token::String("x".to_string()),
+        Token::Identifier("n".to_string()),
+        Token::Eof,
+    ]);
+    // The escapes of E'...' do not carry over to the strings after it
+    let tokens = Tokenizer::new(r"E'\n' '\n'").collect::<Result<Vec<Token>, String>>()?;
+    assert_eq!(tokens[1], Token::String("\\n".to_string()));
+    Ok(())
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "trace")]
+
+use programming_languages_project_kyrylo_yezholov::{build_statement, Keyword, Span, Token};
+use programming_languages_project_kyrylo_yezholov::trace::{trace, TraceEvent};
+
+#[test]
+fn test_trace() {
+    let (statement, events) = trace(|| build_statement("SELECT a FROM t;"));
+    assert!(statement.is_ok());
+    assert_eq!(events.events[..4], [
+        TraceEvent::Token(Token::Keyword(Keyword::Select), Span { start: 0, end: 6 }),
+        TraceEvent::Rule("statement.select"),
+        TraceEvent::Token(Token::Identifier("a".to_string()), Span { start: 7, end: 8 }),
+        TraceEvent::Rule("select.columns"),
+    ]);
+    assert_eq!(events.events.last(), Some(&TraceEvent::Token(Token::Eof, Span { start: 16, end: 16 })));
+    assert!(events.to_string().starts_with("    Keyword(Select) at 0..6\nstatement.select\n"));
+}
+
+#[test]
+fn test_nothing_traced_outside() {
+    let _ = build_statement("SELECT a FROM t;");
+    let (_, empty) = trace(|| ());
+    assert!(empty.events.is_empty());
+    
+    // A trace inside of another one is kept apart from it
+    let ((_, inner), outer) = trace(|| {
+        let _ = build_statement("SELECT 1;");
+        trace(|| build_statement("SELECT 2;"))
+    });
+    assert!(inner.events.contains(&TraceEvent::Token(Token::Number(2), Span { start: 7, end: 8 })));
+    assert!(!outer.events.contains(&TraceEvent::Token(Token::Number(2), Span { start: 7, end: 8 })));
+    assert!(outer.events.contains(&TraceEvent::Token(Token::Number(1), Span { start: 7, end: 8 })));
+}